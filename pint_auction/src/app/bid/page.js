@@ -38,7 +38,7 @@ export default function Bid() {
             <li className="mb-2">
               After viewing detials about the item. use {" "}
               <code className="bg-black/[.05] dark:bg-white/[.06] px-1 py-0.5 rounded font-semibold">
-                ./pint-auction place-bid &lt;ACCOUNT_NAME&gt; &lt;ITEM_ID&gt; &lt;AMOUNT&gt; <br></br> "https://bigbangblock.builders" "https://bigbangblock.builders" "./pint/token/"
+                ./pint-auction place-bid &lt;ACCOUNT_NAME&gt; &lt;ITEM_ID&gt; &lt;AMOUNT&gt; <br></br> "https://bigbangblock.builders" "https://bigbangblock.builders" "./pint/auction/"
               </code>
             </li>
             <li className="mb-2">
@@ -48,7 +48,7 @@ export default function Host() {
             <li className="mb-2">
               Create auction using {" "}
               <code className="bg-black/[.05] dark:bg-white/[.06] px-1 py-0.5 rounded font-semibold">
-                ./pint-auction create-auction &lt;NAME&gt; &lt;ITEM_ID&gt; &lt;AMOUNT&gt; <br></br> "https://bigbangblock.builders" "https://bigbangblock.builders" "./pint/token/"
+                ./pint-auction create-auction &lt;NAME&gt; &lt;ITEM_ID&gt; &lt;AMOUNT&gt; <br></br> "https://bigbangblock.builders" "https://bigbangblock.builders" "./pint/auction/"
               </code>
               or transfer them using<br></br>
               <code className="bg-black/[.05] dark:bg-white/[.06] px-1 py-0.5 rounded font-semibold">
@@ -58,13 +58,13 @@ export default function Host() {
             <li className="mb-2">
              You can decrease the cost using {" "}
               <code className="bg-black/[.05] dark:bg-white/[.06] px-1 py-0.5 rounded font-semibold">
-                ./pint-auction step-auction &lt;ACCOUNT_NAME&gt; &lt;ITEM_ID&gt; &lt;DECREASE_AMOUNT&gt; <br></br> "https://bigbangblock.builders" "https://bigbangblock.builders" "./pint/token/"
+                ./pint-auction step-auction &lt;ACCOUNT_NAME&gt; &lt;ITEM_ID&gt; &lt;DECREASE_AMOUNT&gt; <br></br> "https://bigbangblock.builders" "https://bigbangblock.builders" "./pint/auction/"
               </code>
             </li>
             <li className="mb-2">
              To get the latest details (cost and owner) about item {" "}
               <code className="bg-black/[.05] dark:bg-white/[.06] px-1 py-0.5 rounded font-semibold">
-                ./pint-auction view-item-details &lt;ITEM_ID&gt; <br></br> "https://bigbangblock.builders" "./pint/token/"
+                ./pint-auction view-item-details &lt;ITEM_ID&gt; <br></br> "https://bigbangblock.builders" "./pint/auction/"
               </code>
             </li>
           </ol>
@@ -0,0 +1,143 @@
+//! # Accept Offer
+//! Contains functionality for the owner of an item accepting an escrowed offer.
+//!
+//! For items in a collection the creator's royalty stays in escrow and the
+//! owner is paid the rest.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    address_words, buy::royalty, cost, deployed_at, item_collection, offer, release,
+    signature_words, validate, AuctionError, Query,
+};
+
+/// Contains all necessary information to build an accept offer solution.
+pub struct BuildSolution {
+    /// The item the offer is for.
+    pub item_id: Word,
+    /// The hashed key of the owner.
+    pub hashed_key: [Word; 4],
+    /// The hashed key of the buyer whose offer is accepted.
+    pub hashed_buyer_key: [Word; 4],
+    /// The token contract the offer is escrowed in.
+    pub payment_token: ContentAddress,
+    /// The buyer's current offer on the item.
+    pub current_offer: Query,
+    /// The current balance of the owner.
+    pub current_balance: Query,
+    /// The current balance of the escrow.
+    pub current_escrow_balance: Query,
+    /// The owner's current offer nonce.
+    pub current_nonce: Query,
+    /// The owner's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+    /// The collection the item is registered in.
+    pub current_collection: Query,
+    /// The royalty rate of the item's collection in basis points.
+    pub current_royalty_rate: Query,
+    /// The royalties owed to the item's collection in the offer's token.
+    pub current_royalties: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The words the owner signs to accept an offer.
+///
+/// The nonce is the owner's current offer nonce.
+pub fn data_to_sign(
+    item_id: Word,
+    hashed_buyer_key: [Word; 4],
+    offer: Word,
+    nonce: Word,
+) -> Vec<Word> {
+    let mut words = vec![item_id];
+    words.extend(hashed_buyer_key);
+    words.push(offer);
+    words.push(nonce);
+    words
+}
+
+/// Builds an accept offer solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        hashed_buyer_key,
+        payment_token,
+        current_offer,
+        current_balance,
+        current_escrow_balance,
+        current_nonce,
+        signature,
+        current_collection,
+        current_royalty_rate,
+        current_royalties,
+        contract,
+    } = build;
+
+    let amount = offer(current_offer)?;
+    validate::accept_offer(amount)?;
+    let nonce = offer(current_nonce)?;
+    let next_nonce = nonce.checked_add(1).ok_or(AuctionError::Overflow)?;
+    let collection = item_collection(current_collection)?;
+
+    let pub_vars = super::auction::AcceptOffer::PubVars {
+        item_id,
+        buyer: hashed_buyer_key,
+        key: hashed_key,
+        nonce,
+        collection: collection.unwrap_or_default(),
+    };
+    let vars = super::auction::AcceptOffer::Vars {
+        sig: signature_words(&signature),
+        release_pathway: RELEASE_PATHWAY,
+    };
+    let mut mutations = super::auction::storage::mutations()
+        .item_owner(|map| map.entry(item_id, hashed_buyer_key))
+        .offers(|map| map.entry(item_id, |map| map.entry(hashed_buyer_key, 0)))
+        .offer_nonces(|map| map.entry(hashed_key, next_nonce));
+    let mut payout = amount;
+    if let Some(collection) = collection {
+        let royalty = royalty(amount, cost(current_royalty_rate)?)?;
+        if royalty >= amount {
+            rejected!("The royalty takes the whole offer");
+        }
+        payout = amount - royalty;
+        let royalties = cost(current_royalties)?
+            .checked_add(royalty)
+            .ok_or(AuctionError::Overflow)?;
+        let payment_token = address_words(&payment_token);
+        mutations = mutations.collection_royalties(|map| {
+            map.entry(collection, |map| map.entry(payment_token, royalties))
+        });
+    }
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::AcceptOffer::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+
+    // The escrowed offer is released to the owner.
+    let [release, withdraw] = release::build_solution_data(
+        release::BuildSolution {
+            settlement: super::auction::AcceptOffer::ADDRESS,
+            payment_token,
+            hashed_to_key: hashed_key,
+            amount: payout,
+            current_escrow_balance,
+            current_to_balance: current_balance,
+            contract: contract.clone(),
+        },
+        RELEASE_PATHWAY,
+    )?;
+    Ok(Solution {
+        data: vec![solution, release, withdraw],
+    })
+}
+
+/// The index of the escrow release within an accept offer solution.
+const RELEASE_PATHWAY: Word = 1;
@@ -0,0 +1,71 @@
+//! # Action
+//! Contains the trait that every action builds its solution through.
+//!
+//! An action names the state it reads with [`SolutionBuilder::required_keys`],
+//! so generic code can fetch exactly that state, build the solution from it
+//! and then check or submit it the same way for every action.
+use std::collections::BTreeMap;
+
+use essential_types::{solution::Solution, ContentAddress, Key, Value};
+
+use crate::{dry_run::StateSource, AuctionError, Query};
+
+/// A key in the state of a contract.
+pub type StateKey = (ContentAddress, Key);
+
+/// An action that can be built into a solution from the state it reads.
+pub trait SolutionBuilder {
+    /// The keys whose values [`build`](Self::build) reads.
+    fn required_keys(&self) -> Vec<StateKey>;
+
+    /// Builds the solution from the values of the required keys.
+    fn build(&self, state: &State) -> Result<Solution, AuctionError>;
+
+    /// Builds the solution as the part of a larger solution that starts at
+    /// `offset`.
+    ///
+    /// Actions whose solution data refer to each other by index override
+    /// this to shift those indices. The default suits actions that do not.
+    fn build_at(&self, state: &State, _offset: usize) -> Result<Solution, AuctionError> {
+        self.build(state)
+    }
+}
+
+/// The values read for an action, by contract and key.
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    values: BTreeMap<StateKey, Option<Value>>,
+}
+
+impl State {
+    /// Records the value read at `key` in the state of `contract`.
+    pub fn insert(&mut self, contract: ContentAddress, key: Key, value: Option<Value>) {
+        self.values.insert((contract, key), value);
+    }
+
+    /// The value read at `key` in the state of `contract`.
+    ///
+    /// A key that was never read is unset, so a builder that reads a key it
+    /// did not require sees the same as a key the node has no value for.
+    pub fn get(&self, contract: &ContentAddress, key: &Key) -> Query {
+        Query(
+            self.values
+                .get(&(contract.clone(), key.clone()))
+                .cloned()
+                .flatten(),
+        )
+    }
+}
+
+/// Reads the state an action requires from the node.
+pub async fn fetch_state(
+    node: &impl StateSource,
+    action: &impl SolutionBuilder,
+) -> Result<State, AuctionError> {
+    let mut state = State::default();
+    for (contract, key) in action.required_keys() {
+        let value = node.read(&contract, &key).await?;
+        state.insert(contract, key, value);
+    }
+    Ok(state)
+}
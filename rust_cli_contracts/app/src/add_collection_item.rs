@@ -0,0 +1,96 @@
+//! # Add Collection Item
+//! Contains functionality for a creator registering one of their items in a collection.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    collection_creator, cost, deployed_at, item_collection, item_owner, offer, signature_words,
+    validate, AuctionError, Query,
+};
+
+/// Contains all necessary information to build an add collection item solution.
+pub struct BuildSolution {
+    /// The collection the item is added to.
+    pub collection: Word,
+    /// The item being added.
+    pub item_id: Word,
+    /// The hashed key of the creator.
+    pub hashed_key: [Word; 4],
+    /// The current creator of the collection.
+    pub current_creator: Query,
+    /// The current owner of the item.
+    pub current_owner: Query,
+    /// The current number of items in the collection.
+    pub current_item_count: Query,
+    /// The collection the item is currently registered in.
+    pub current_collection: Query,
+    /// The creator's current nonce.
+    pub current_nonce: Query,
+    /// The creator's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The words the creator signs to add an item to a collection.
+///
+/// The nonce is the creator's current nonce.
+pub fn data_to_sign(collection: Word, item_id: Word, nonce: Word) -> Vec<Word> {
+    vec![collection, item_id, nonce, ADD_COLLECTION_ITEM]
+}
+
+/// Builds an add collection item solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        collection,
+        item_id,
+        hashed_key,
+        current_creator,
+        current_owner,
+        current_item_count,
+        current_collection,
+        current_nonce,
+        signature,
+        contract,
+    } = build;
+
+    validate::add_collection_item(
+        collection_creator(current_creator)?,
+        item_owner(current_owner)?,
+        hashed_key,
+        item_collection(current_collection)?,
+    )?;
+    let index = cost(current_item_count)?;
+    let nonce = offer(current_nonce)?;
+    let next_nonce = nonce.checked_add(1).ok_or(AuctionError::Overflow)?;
+
+    let pub_vars = super::auction::AddCollectionItem::PubVars {
+        collection,
+        item_id,
+        key: hashed_key,
+        nonce,
+    };
+    let vars = super::auction::AddCollectionItem::Vars {
+        sig: signature_words(&signature),
+    };
+    let mutations = super::auction::storage::mutations()
+        .collection_items(|map| map.entry(collection, |map| map.entry(index, item_id)))
+        .collection_item_count(|map| map.entry(collection, index + 1))
+        .item_collection(|map| map.entry(item_id, collection))
+        .nonces(|map| map.entry(hashed_key, next_nonce));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::AddCollectionItem::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
+
+/// Tags a signature as adding an item to a collection.
+const ADD_COLLECTION_ITEM: Word = 10;
@@ -0,0 +1,85 @@
+//! # Allow Seller
+//! Contains functionality for a creator choosing who may sell a closed collection.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    collection_creator, deployed_at, offer, signature_words, validate, AuctionError, Query,
+};
+
+/// Contains all necessary information to build an allow seller solution.
+pub struct BuildSolution {
+    /// The collection being managed.
+    pub collection: Word,
+    /// The hashed key of the creator.
+    pub hashed_key: [Word; 4],
+    /// The hashed key of the seller.
+    pub seller: [Word; 4],
+    /// Whether the seller may list items of the collection.
+    pub allowed: bool,
+    /// The current creator of the collection.
+    pub current_creator: Query,
+    /// The creator's current nonce.
+    pub current_nonce: Query,
+    /// The creator's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The words the creator signs to allow or disallow a seller.
+///
+/// The nonce is the creator's current nonce.
+pub fn data_to_sign(collection: Word, seller: [Word; 4], allowed: bool, nonce: Word) -> Vec<Word> {
+    let mut words = vec![collection];
+    words.extend(seller);
+    words.extend([allowed as Word, nonce, ALLOW_SELLER]);
+    words
+}
+
+/// Builds an allow seller solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        collection,
+        hashed_key,
+        seller,
+        allowed,
+        current_creator,
+        current_nonce,
+        signature,
+        contract,
+    } = build;
+
+    validate::allow_seller(collection_creator(current_creator)?, hashed_key)?;
+    let nonce = offer(current_nonce)?;
+    let next_nonce = nonce.checked_add(1).ok_or(AuctionError::Overflow)?;
+
+    let pub_vars = super::auction::AllowSeller::PubVars {
+        collection,
+        key: hashed_key,
+        seller,
+        allowed,
+        nonce,
+    };
+    let vars = super::auction::AllowSeller::Vars {
+        sig: signature_words(&signature),
+    };
+    let mutations = super::auction::storage::mutations()
+        .collection_sellers(|map| map.entry(collection, |map| map.entry(seller, allowed)))
+        .nonces(|map| map.entry(hashed_key, next_nonce));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::AllowSeller::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
+
+/// Tags a signature as allowing or disallowing a seller.
+const ALLOW_SELLER: Word = 11;
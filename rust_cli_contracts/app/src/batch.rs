@@ -0,0 +1,87 @@
+//! # Batch
+//! Contains functionality for submitting several actions as one solution.
+//!
+//! The actions of a [`SolutionBatch`] are checked and included together, so
+//! there is no window between them where only some have happened.
+//!
+//! Every action is built from the same state, as each predicate reads the
+//! state from before the whole solution. Two actions that write the same key
+//! cannot both hold, since each constrains the change to that key on its own,
+//! so a batch where they do is rejected rather than sent to fail.
+use std::collections::BTreeSet;
+
+use essential_types::solution::Solution;
+
+use crate::{
+    action::{SolutionBuilder, State, StateKey},
+    AuctionError,
+};
+
+/// Several actions that are built into a single solution.
+#[derive(Default)]
+pub struct SolutionBatch<'a> {
+    actions: Vec<Box<dyn SolutionBuilder + 'a>>,
+}
+
+impl<'a> SolutionBatch<'a> {
+    /// Creates an empty batch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an action after those already in the batch.
+    pub fn push(&mut self, action: impl SolutionBuilder + 'a) -> &mut Self {
+        self.actions.push(Box::new(action));
+        self
+    }
+
+    /// The number of actions in the batch.
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// Whether the batch has no actions.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+impl SolutionBuilder for SolutionBatch<'_> {
+    fn required_keys(&self) -> Vec<StateKey> {
+        let mut seen = BTreeSet::new();
+        self.actions
+            .iter()
+            .flat_map(|action| action.required_keys())
+            .filter(|key| seen.insert(key.clone()))
+            .collect()
+    }
+
+    fn build(&self, state: &State) -> Result<Solution, AuctionError> {
+        self.build_at(state, 0)
+    }
+
+    fn build_at(&self, state: &State, offset: usize) -> Result<Solution, AuctionError> {
+        if self.actions.is_empty() {
+            rejected!("Batch has no actions");
+        }
+        let mut data = Vec::new();
+        let mut written = BTreeSet::new();
+        for action in &self.actions {
+            let solution = action.build_at(state, offset + data.len())?;
+            for solution_data in &solution.data {
+                let contract = &solution_data.predicate_to_solve.contract;
+                for mutation in &solution_data.state_mutations {
+                    if !written.insert((contract.clone(), mutation.key.clone())) {
+                        rejected!(
+                            "Two actions write key {:?} of contract {}",
+                            mutation.key,
+                            contract
+                        );
+                    }
+                }
+            }
+            data.extend(solution.data);
+        }
+        Ok(Solution { data })
+    }
+}
@@ -0,0 +1,238 @@
+//! # Bid Intent
+//! Contains functionality for signed bid intents that solvers fill.
+//!
+//! A bidder signs "I will pay up to `limit` for `item_id` until block `expiry`"
+//! and publishes it to an intent pool. Any solver can later complete it with
+//! [`build_solution`] once the Dutch auction's price is within the limit.
+use essential_sign::secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+use essential_types::{
+    convert::{u8_32_from_word_4, word_4_from_u8_32},
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+use serde_json::{json, Value};
+
+use crate::{
+    address_words, buy::royalty, cost, deployed_at, escrow_key, fixed_price, item_collection,
+    item_owner, signature_words, transfer, validate, AuctionError, Query,
+};
+
+/// A bidder's signed promise to pay up to a limit for an item.
+#[derive(Debug, Clone)]
+pub struct BidIntent {
+    /// The item the bidder wants.
+    pub item_id: Word,
+    /// The hashed key of the bidder.
+    pub bidder: [Word; 4],
+    /// The most the bidder will pay.
+    pub limit: Word,
+    /// The last block the intent can be filled in.
+    pub expiry: Word,
+    /// The token contract the bidder pays in.
+    pub payment_token: ContentAddress,
+    /// The bidder's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+}
+
+impl BidIntent {
+    /// Encodes the intent for an intent pool file.
+    pub fn to_json(&self) -> Value {
+        let (recovery_id, signature) = self.signature.serialize_compact();
+        let mut signature = signature.to_vec();
+        signature.push(recovery_id.to_i32() as u8);
+        json!({
+            "item_id": self.item_id,
+            "bidder": hex::encode(u8_32_from_word_4(self.bidder)),
+            "limit": self.limit,
+            "expiry": self.expiry,
+            "payment_token": hex::encode(self.payment_token.0),
+            "signature": hex::encode(signature),
+        })
+    }
+
+    /// Decodes an intent read from an intent pool file.
+    pub fn from_json(value: &Value) -> anyhow::Result<Self> {
+        let word = |name: &str| {
+            value[name]
+                .as_i64()
+                .ok_or_else(|| anyhow::anyhow!("Intent is missing {}", name))
+        };
+        let bytes_32 = |name: &str| -> anyhow::Result<[u8; 32]> {
+            let hex = value[name]
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("Intent is missing {}", name))?;
+            hex::decode(hex)?
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("Intent {} must be 32 bytes", name))
+        };
+        let signature = value["signature"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Intent is missing signature"))?;
+        let signature = hex::decode(signature)?;
+        let Some((recovery_id, signature)) = signature.split_last() else {
+            anyhow::bail!("Intent signature is empty");
+        };
+        let recovery_id = RecoveryId::from_i32(*recovery_id as i32)?;
+        Ok(Self {
+            item_id: word("item_id")?,
+            bidder: word_4_from_u8_32(bytes_32("bidder")?),
+            limit: word("limit")?,
+            expiry: word("expiry")?,
+            payment_token: ContentAddress(bytes_32("payment_token")?),
+            signature: RecoverableSignature::from_compact(signature, recovery_id)?,
+        })
+    }
+}
+
+/// The words a bidder signs to publish a bid intent.
+pub fn data_to_sign(
+    item_id: Word,
+    limit: Word,
+    expiry: Word,
+    payment_token: &ContentAddress,
+) -> Vec<Word> {
+    let mut words = vec![item_id, limit, expiry];
+    words.extend_from_slice(&address_words(payment_token));
+    words.push(BID_INTENT);
+    words
+}
+
+/// Whether an intent can be filled at the current price and block.
+pub fn fillable(intent: &BidIntent, current_cost: Word, block_number: Word) -> bool {
+    current_cost > 0 && current_cost <= intent.limit && block_number <= intent.expiry
+}
+
+/// Contains all necessary information to build a fill bid intent solution.
+pub struct BuildSolution {
+    /// The intent being filled.
+    pub intent: BidIntent,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The current block number.
+    pub current_block_number: Query,
+    /// The current price of the item.
+    pub current_cost: Query,
+    /// The current owner of the item.
+    pub current_owner: Query,
+    /// Whether the item is listed at a fixed price.
+    pub current_fixed_price: Query,
+    /// The current balance of the bidder.
+    pub current_bidder_balance: Query,
+    /// The current balance of the owner.
+    /// This is the escrow's balance when the item is in a collection.
+    pub current_owner_balance: Query,
+    /// The collection the item is registered in.
+    pub current_collection: Query,
+    /// The royalty rate of the item's collection in basis points.
+    pub current_royalty_rate: Query,
+    /// The royalties owed to the item's collection in the payment token.
+    pub current_royalties: Query,
+    /// The unclaimed proceeds of the item's last sale.
+    pub current_proceeds: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a fill bid intent solution based on the provided data.
+///
+/// The bidder pays the current price, which may be less than their limit.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        intent,
+        block_state,
+        current_block_number,
+        current_cost,
+        current_owner,
+        current_fixed_price,
+        current_bidder_balance,
+        current_owner_balance,
+        current_collection,
+        current_royalty_rate,
+        current_royalties,
+        current_proceeds,
+        contract,
+    } = build;
+
+    let price = cost(current_cost)?;
+    validate::fill_bid_intent(
+        fixed_price(current_fixed_price)?,
+        price,
+        intent.limit,
+        intent.expiry,
+        cost(current_block_number)?,
+    )?;
+    let owner = item_owner(current_owner)?;
+    let collection = item_collection(current_collection)?;
+
+    let BidIntent {
+        item_id,
+        bidder,
+        limit,
+        expiry,
+        payment_token,
+        signature,
+    } = intent;
+    let pub_vars = super::auction::FillBidIntent::PubVars {
+        item_id,
+        bidder,
+        limit,
+        expiry,
+        payment_token: address_words(&payment_token),
+        collection: collection.unwrap_or_default(),
+        block_state: address_words(&block_state),
+    };
+    let vars = super::auction::FillBidIntent::Vars {
+        sig: signature_words(&signature),
+        transfer_pathway: TRANSFER_PATHWAY,
+    };
+    let mut mutations = super::auction::storage::mutations()
+        .item_owner(|map| map.entry(item_id, bidder))
+        .auction_ended(|map| map.entry(item_id, true));
+    if let Some(collection) = collection {
+        // The price is escrowed and split between the seller and the collection.
+        if cost(current_proceeds)? != 0 {
+            rejected!("The seller has not claimed the proceeds of the last sale");
+        }
+        let royalty = royalty(price, cost(current_royalty_rate)?)?;
+        let royalties = cost(current_royalties)? + royalty;
+        let payment_token = address_words(&payment_token);
+        mutations = mutations
+            .proceeds(|map| map.entry(item_id, price - royalty))
+            .proceeds_seller(|map| map.entry(item_id, owner))
+            .collection_royalties(|map| {
+                map.entry(collection, |map| map.entry(payment_token, royalties))
+            });
+    }
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::FillBidIntent::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+
+    // The bidder pays the owner through the transfer predicate of the token.
+    let transfer = transfer::build_solution_data(
+        payment_token,
+        transfer::BuildSolution {
+            hashed_from_key: bidder,
+            hashed_to_key: if collection.is_some() {
+                escrow_key(&contract)
+            } else {
+                owner
+            },
+            amount: price,
+            current_from_balance: current_bidder_balance,
+            current_to_balance: current_owner_balance,
+            authority: 0,
+        },
+    )?;
+    Ok(Solution {
+        data: vec![solution, transfer],
+    })
+}
+
+/// Tags a signature as a bid intent.
+const BID_INTENT: Word = 3;
+
+/// The index of the token transfer within a fill bid intent solution.
+const TRANSFER_PATHWAY: Word = 1;
@@ -0,0 +1,131 @@
+//! # Buy
+//! Contains functionality for buying an item listed at a fixed price.
+//!
+//! Items in a collection are paid into escrow so the creator's royalty
+//! can be split off before the seller claims the rest.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    address_words, cost, deployed_at, escrow_key, item_collection, transfer, AuctionError, Query,
+};
+
+/// Contains all necessary information to build a buy solution.
+pub struct BuildSolution {
+    /// The item being bought.
+    pub item_id: Word,
+    /// The hashed key of the buyer.
+    pub hashed_from_key: [Word; 4],
+    /// The hashed key of the owner.
+    pub hashed_to_key: [Word; 4],
+    /// The current balance of the buyer.
+    pub current_from_balance: Query,
+    /// The current balance of the owner.
+    /// This is the escrow's balance when the item is in a collection.
+    pub current_to_balance: Query,
+    /// The listed price of the item.
+    pub current_cost: Query,
+    /// The token contract the item is priced in.
+    pub payment_token: ContentAddress,
+    /// The collection the item is registered in.
+    pub current_collection: Query,
+    /// The royalty rate of the item's collection in basis points.
+    pub current_royalty_rate: Query,
+    /// The royalties owed to the item's collection in the payment token.
+    pub current_royalties: Query,
+    /// The unclaimed proceeds of the item's last sale.
+    pub current_proceeds: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The royalty owed on a sale at `price` with a rate in basis points.
+pub fn royalty(price: Word, rate: Word) -> Result<Word, AuctionError> {
+    match price.checked_mul(rate) {
+        Some(total) => Ok(total / ROYALTY_BASIS),
+        None => Err(AuctionError::Overflow),
+    }
+}
+
+/// Builds a buy solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_from_key,
+        hashed_to_key,
+        current_from_balance,
+        current_to_balance,
+        current_cost,
+        payment_token,
+        current_collection,
+        current_royalty_rate,
+        current_royalties,
+        current_proceeds,
+        contract,
+    } = build;
+
+    let price = cost(current_cost)?;
+    let collection = item_collection(current_collection)?;
+
+    let pub_vars = super::auction::Buy::PubVars {
+        item_id,
+        buyer: hashed_from_key,
+        payment_token: address_words(&payment_token),
+        collection: collection.unwrap_or_default(),
+    };
+    let vars = super::auction::Buy::Vars {
+        transfer_pathway: TRANSFER_PATHWAY,
+    };
+    let mut mutations = super::auction::storage::mutations()
+        .item_owner(|map| map.entry(item_id, hashed_from_key))
+        .auction_ended(|map| map.entry(item_id, true))
+        .fixed_price(|map| map.entry(item_id, false));
+    if let Some(collection) = collection {
+        if cost(current_proceeds)? != 0 {
+            rejected!("The seller has not claimed the proceeds of the last sale");
+        }
+        let royalty = royalty(price, cost(current_royalty_rate)?)?;
+        let royalties = cost(current_royalties)? + royalty;
+        let payment_token = address_words(&payment_token);
+        mutations = mutations
+            .proceeds(|map| map.entry(item_id, price - royalty))
+            .proceeds_seller(|map| map.entry(item_id, hashed_to_key))
+            .collection_royalties(|map| {
+                map.entry(collection, |map| map.entry(payment_token, royalties))
+            });
+    }
+    let buy = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::Buy::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+
+    // The price is paid through the transfer predicate of the item's token.
+    let transfer = transfer::build_solution_data(
+        payment_token,
+        transfer::BuildSolution {
+            hashed_from_key,
+            hashed_to_key: if collection.is_some() {
+                escrow_key(&contract)
+            } else {
+                hashed_to_key
+            },
+            amount: price,
+            current_from_balance,
+            current_to_balance,
+            authority: 0,
+        },
+    )?;
+    Ok(Solution {
+        data: vec![buy, transfer],
+    })
+}
+
+/// The index of the token transfer within a buy solution.
+const TRANSFER_PATHWAY: Word = 1;
+
+/// Royalty rates are in basis points of the price.
+const ROYALTY_BASIS: Word = 10000;
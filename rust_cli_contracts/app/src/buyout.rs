@@ -0,0 +1,82 @@
+//! # Buyout
+//! Contains functionality for buying a fractionalized item at its reserve price.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    address_words, cost, deployed_at, escrow_key, transfer, validate, AuctionError, Query,
+};
+
+/// Contains all necessary information to build a buyout solution.
+pub struct BuildSolution {
+    /// The fractionalized item.
+    pub item_id: Word,
+    /// The hashed key of the buyer.
+    pub hashed_key: [Word; 4],
+    /// The token contract the buyout is paid in.
+    pub payment_token: ContentAddress,
+    /// The buyout price of the item.
+    pub current_reserve: Query,
+    /// The current balance of the buyer.
+    pub current_balance: Query,
+    /// The current balance of the escrow.
+    pub current_escrow_balance: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a buyout solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        payment_token,
+        current_reserve,
+        current_balance,
+        current_escrow_balance,
+        contract,
+    } = build;
+
+    let reserve = cost(current_reserve)?;
+    validate::buyout(reserve)?;
+
+    let pub_vars = super::auction::Buyout::PubVars {
+        item_id,
+        buyer: hashed_key,
+        payment_token: address_words(&payment_token),
+    };
+    let vars = super::auction::Buyout::Vars {
+        transfer_pathway: TRANSFER_PATHWAY,
+    };
+    let mutations = super::auction::storage::mutations()
+        .item_owner(|map| map.entry(item_id, hashed_key))
+        .fractionalized(|map| map.entry(item_id, false))
+        .bought_out(|map| map.entry(item_id, true));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::Buyout::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+
+    // The reserve is escrowed for the shareholders to redeem.
+    let transfer = transfer::build_solution_data(
+        payment_token,
+        transfer::BuildSolution {
+            hashed_from_key: hashed_key,
+            hashed_to_key: escrow_key(&contract),
+            amount: reserve,
+            current_from_balance: current_balance,
+            current_to_balance: current_escrow_balance,
+            authority: 0,
+        },
+    )?;
+    Ok(Solution {
+        data: vec![solution, transfer],
+    })
+}
+
+/// The index of the escrow transfer within a buyout solution.
+const TRANSFER_PATHWAY: Word = 1;
@@ -0,0 +1,52 @@
+//! # Cancel Ascending
+//! Contains functionality for closing an ascending auction that no one leads once reveals close.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{address_words, deployed_at, leader, validate, AuctionError, Query};
+
+/// Contains all necessary information to build a cancel ascending solution.
+pub struct BuildSolution {
+    /// The item being auctioned.
+    pub item_id: Word,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The current leading bidder.
+    pub current_leader: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a cancel ascending solution based on the provided data.
+///
+/// The item stays with its owner, and any bidder that never revealed can
+/// still reveal to free their deposit.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        block_state,
+        current_leader,
+        contract,
+    } = build;
+
+    validate::cancel_ascending(leader(current_leader)?)?;
+
+    let pub_vars = super::auction::CancelAscending::PubVars {
+        item_id,
+        block_state: address_words(&block_state),
+    };
+    let mutations = super::auction::storage::mutations()
+        .auction_ended(|map| map.entry(item_id, true))
+        .ascending(|map| map.entry(item_id, false));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::CancelAscending::ADDRESS, &contract),
+        decision_variables: Default::default(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
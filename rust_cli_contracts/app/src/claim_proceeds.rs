@@ -0,0 +1,77 @@
+//! # Claim Proceeds
+//! Contains functionality for a seller claiming a bundle bid from escrow.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{cost, deployed_at, release, AuctionError, Query};
+
+/// Contains all necessary information to build a claim proceeds solution.
+pub struct BuildSolution {
+    /// The item that was sold.
+    pub item_id: Word,
+    /// The hashed key of the seller.
+    pub hashed_seller_key: [Word; 4],
+    /// The token contract the item was sold in.
+    pub payment_token: ContentAddress,
+    /// The current proceeds of the item.
+    pub current_proceeds: Query,
+    /// The current balance of the seller.
+    pub current_seller_balance: Query,
+    /// The current balance of the escrow.
+    pub current_escrow_balance: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a claim proceeds solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_seller_key,
+        payment_token,
+        current_proceeds,
+        current_seller_balance,
+        current_escrow_balance,
+        contract,
+    } = build;
+
+    let proceeds = cost(current_proceeds)?;
+    if proceeds <= 0 {
+        rejected!("No proceeds to claim");
+    }
+
+    let pub_vars = super::auction::ClaimProceeds::PubVars { item_id };
+    let vars = super::auction::ClaimProceeds::Vars {
+        release_pathway: RELEASE_PATHWAY,
+    };
+    let mutations = super::auction::storage::mutations()
+        .proceeds(|map| map.entry(item_id, 0));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::ClaimProceeds::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+
+    // The proceeds are released from escrow to the seller.
+    let [release, withdraw] = release::build_solution_data(
+        release::BuildSolution {
+            settlement: super::auction::ClaimProceeds::ADDRESS,
+            payment_token,
+            hashed_to_key: hashed_seller_key,
+            amount: proceeds,
+            current_escrow_balance,
+            current_to_balance: current_seller_balance,
+            contract: contract.clone(),
+        },
+        RELEASE_PATHWAY,
+    )?;
+    Ok(Solution {
+        data: vec![solution, release, withdraw],
+    })
+}
+
+/// The index of the escrow release within a claim proceeds solution.
+const RELEASE_PATHWAY: Word = 1;
@@ -0,0 +1,78 @@
+//! # Claim Refund
+//! Contains functionality for a bidder reclaiming escrow from an ascending auction.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{cost, deployed_at, release, validate, AuctionError, Query};
+
+/// Contains all necessary information to build a claim refund solution.
+pub struct BuildSolution {
+    /// The item that was bid on.
+    pub item_id: Word,
+    /// The hashed key of the bidder.
+    pub hashed_bidder_key: [Word; 4],
+    /// The token contract the item is priced in.
+    pub payment_token: ContentAddress,
+    /// The current reclaimable escrow of the bidder.
+    pub current_refund: Query,
+    /// The current balance of the bidder.
+    pub current_bidder_balance: Query,
+    /// The current balance of the escrow.
+    pub current_escrow_balance: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a claim refund solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_bidder_key,
+        payment_token,
+        current_refund,
+        current_bidder_balance,
+        current_escrow_balance,
+        contract,
+    } = build;
+
+    let refund = cost(current_refund)?;
+    validate::claim_refund(refund)?;
+
+    let pub_vars = super::auction::ClaimRefund::PubVars {
+        item_id,
+        bidder: hashed_bidder_key,
+    };
+    let vars = super::auction::ClaimRefund::Vars {
+        release_pathway: RELEASE_PATHWAY,
+    };
+    let mutations = super::auction::storage::mutations()
+        .refunds(|map| map.entry(item_id, |map| map.entry(hashed_bidder_key, 0)));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::ClaimRefund::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+
+    // The escrow is released back to the bidder.
+    let [release, withdraw] = release::build_solution_data(
+        release::BuildSolution {
+            settlement: super::auction::ClaimRefund::ADDRESS,
+            payment_token,
+            hashed_to_key: hashed_bidder_key,
+            amount: refund,
+            current_escrow_balance,
+            current_to_balance: current_bidder_balance,
+            contract: contract.clone(),
+        },
+        RELEASE_PATHWAY,
+    )?;
+    Ok(Solution {
+        data: vec![solution, release, withdraw],
+    })
+}
+
+/// The index of the escrow release within a claim refund solution.
+const RELEASE_PATHWAY: Word = 1;
@@ -0,0 +1,82 @@
+//! # Claim Royalties
+//! Contains functionality for releasing a collection's royalties to its creator.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    address_words, collection_creator, cost, deployed_at, release, validate, AuctionError, Query,
+};
+
+/// Contains all necessary information to build a claim royalties solution.
+pub struct BuildSolution {
+    /// The collection the royalties are owed to.
+    pub collection: Word,
+    /// The token contract the royalties were paid in.
+    pub payment_token: ContentAddress,
+    /// The current creator of the collection.
+    pub current_creator: Query,
+    /// The current royalties owed to the collection.
+    pub current_royalties: Query,
+    /// The current balance of the creator.
+    pub current_creator_balance: Query,
+    /// The current balance of the escrow.
+    pub current_escrow_balance: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a claim royalties solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        collection,
+        payment_token,
+        current_creator,
+        current_royalties,
+        current_creator_balance,
+        current_escrow_balance,
+        contract,
+    } = build;
+
+    let creator = collection_creator(current_creator)?;
+    let royalties = cost(current_royalties)?;
+    validate::claim_royalties(royalties)?;
+
+    let payment_token_words = address_words(&payment_token);
+    let pub_vars = super::auction::ClaimRoyalties::PubVars {
+        collection,
+        payment_token: payment_token_words,
+    };
+    let vars = super::auction::ClaimRoyalties::Vars {
+        release_pathway: RELEASE_PATHWAY,
+    };
+    let mutations = super::auction::storage::mutations()
+        .collection_royalties(|map| map.entry(collection, |map| map.entry(payment_token_words, 0)));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::ClaimRoyalties::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+
+    // The royalties are released from escrow to the creator.
+    let [release, withdraw] = release::build_solution_data(
+        release::BuildSolution {
+            settlement: super::auction::ClaimRoyalties::ADDRESS,
+            payment_token,
+            hashed_to_key: creator,
+            amount: royalties,
+            current_escrow_balance,
+            current_to_balance: current_creator_balance,
+            contract: contract.clone(),
+        },
+        RELEASE_PATHWAY,
+    )?;
+    Ok(Solution {
+        data: vec![solution, release, withdraw],
+    })
+}
+
+/// The index of the escrow release within a claim royalties solution.
+const RELEASE_PATHWAY: Word = 1;
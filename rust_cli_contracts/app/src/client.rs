@@ -0,0 +1,306 @@
+//! # Client
+//! Contains a client for services that embed the auction.
+//!
+//! [`AuctionClient`] holds the node and builder clients, the auction contract
+//! address and the signing account, so each action is a single call that
+//! reads the state it needs, checks it and submits the solution. Any other
+//! [`SolutionBuilder`] goes through the same pipeline with [`AuctionClient::run`].
+//!
+//! The client reads through any [`StateSource`] and sends through any
+//! [`Submit`], so the same calls run against the REST services or against
+//! an in-process node and builder.
+use std::future::Future;
+
+use essential_rest_client::{
+    builder_client::EssentialBuilderClient, node_client::EssentialNodeClient,
+};
+use essential_types::{
+    convert::word_4_from_u8_32, solution::Solution, ContentAddress, Key, Value, Word,
+};
+use essential_wallet::Wallet;
+
+use crate::{
+    action::{fetch_state, SolutionBuilder, State},
+    balance, balance_key, create_auction,
+    dry_run::{self, Contracts, Report, StateSource},
+    fetch_item, item_collection, item_collection_key, mint, place_bid, step_auction, transfer,
+    validate, AuctionError, AuctionItem, Query,
+};
+
+/// Where a client sends the solutions it builds.
+///
+/// Implemented for the REST builder client. Tests implement it for the
+/// databases of an in-process builder.
+pub trait Submit: Send + Sync {
+    /// Sends `solution`, returning its content address.
+    fn submit(
+        &self,
+        solution: &Solution,
+    ) -> impl Future<Output = anyhow::Result<ContentAddress>> + Send;
+}
+
+impl Submit for EssentialBuilderClient {
+    async fn submit(&self, solution: &Solution) -> anyhow::Result<ContentAddress> {
+        Ok(self.submit_solution(solution).await?)
+    }
+}
+
+/// Acts on the auction contract as a single account.
+pub struct AuctionClient<N = EssentialNodeClient, B = EssentialBuilderClient> {
+    node: N,
+    builder: B,
+    contract: ContentAddress,
+    contracts: Contracts,
+    wallet: Wallet,
+    hashed_key: [Word; 4],
+    dry_run: bool,
+}
+
+impl AuctionClient {
+    /// Connects to the node and builder, signing as `account` from `wallet`.
+    ///
+    /// `contract` is the address of the auction contract, which is
+    /// [`crate::auction::ADDRESS`] unless it was deployed from other sources.
+    pub fn new(
+        node_api: String,
+        builder_api: String,
+        contract: ContentAddress,
+        wallet: Wallet,
+        account: &str,
+    ) -> anyhow::Result<Self> {
+        Self::with_services(
+            EssentialNodeClient::new(node_api)?,
+            EssentialBuilderClient::new(builder_api)?,
+            contract,
+            wallet,
+            account,
+        )
+    }
+}
+
+impl<N: StateSource + Clone, B: Submit> AuctionClient<N, B> {
+    /// Reads state from `node` and sends solutions to `builder`, signing as
+    /// `account` from `wallet`.
+    pub fn with_services(
+        node: N,
+        builder: B,
+        contract: ContentAddress,
+        mut wallet: Wallet,
+        account: &str,
+    ) -> anyhow::Result<Self> {
+        let hashed_key = hash_key(&mut wallet, account)?;
+        Ok(Self {
+            node,
+            builder,
+            contracts: Contracts::generated(&contract)?,
+            contract,
+            wallet,
+            hashed_key,
+            dry_run: false,
+        })
+    }
+
+    /// Checks solutions against `contracts` rather than the generated
+    /// contracts, for an auction contract deployed from other sources.
+    pub fn with_contracts(mut self, contracts: Contracts) -> Self {
+        self.contracts = contracts;
+        self
+    }
+
+    /// Checks every solution against the node instead of sending it when
+    /// `dry_run` is set.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// The hashed key of the signing account.
+    pub fn hashed_key(&self) -> [Word; 4] {
+        self.hashed_key
+    }
+
+    /// The address of the auction contract.
+    pub fn contract(&self) -> &ContentAddress {
+        &self.contract
+    }
+
+    /// The node the client reads state from.
+    pub fn node(&self) -> &N {
+        &self.node
+    }
+
+    /// The wallet holding the signing account, for actions that need a signature.
+    pub fn wallet(&mut self) -> &mut Wallet {
+        &mut self.wallet
+    }
+
+    /// The balance of `hashed_key` in the `token` contract.
+    pub async fn balance(
+        &self,
+        token: &ContentAddress,
+        hashed_key: [Word; 4],
+    ) -> Result<Word, AuctionError> {
+        let value = self.query(token, balance_key(hashed_key)).await?;
+        balance(Query(value))
+    }
+
+    /// Reads an item, or `None` if it has never been listed.
+    pub async fn item(&self, item_id: Word) -> Result<Option<AuctionItem>, AuctionError> {
+        fetch_item(&self.node, &self.contract, item_id).await
+    }
+
+    /// Reads every item, stopping at the first id that has never been listed.
+    pub async fn list_items(&self) -> Result<Vec<AuctionItem>, AuctionError> {
+        let mut items = Vec::new();
+        while let Some(item) = self.item(items.len() as Word).await? {
+            items.push(item);
+        }
+        Ok(items)
+    }
+
+    /// Mints `amount` of the token contract to the signing account.
+    pub async fn mint(&self, amount: Word) -> anyhow::Result<ContentAddress> {
+        let action = mint::Mint {
+            hashed_key: self.hashed_key,
+            amount,
+        };
+        let state = self.fetch(&action).await?;
+        let minted = state
+            .get(&crate::token::ADDRESS, &balance_key(self.hashed_key))
+            .as_opt_int()?;
+        validate::mint(minted, amount).map_err(AuctionError::from)?;
+        self.submit(&action.build(&state)?).await
+    }
+
+    /// Transfers `amount` of the token contract from the signing account to `to`.
+    pub async fn transfer(&self, to: [Word; 4], amount: Word) -> anyhow::Result<ContentAddress> {
+        let action = transfer::Transfer {
+            hashed_from_key: self.hashed_key,
+            hashed_to_key: to,
+            amount,
+        };
+        let state = self.fetch(&action).await?;
+        let from_balance = state.get(&crate::token::ADDRESS, &balance_key(self.hashed_key));
+        validate::transfer(balance(from_balance)?, amount).map_err(AuctionError::from)?;
+        self.submit(&action.build(&state)?).await
+    }
+
+    /// Starts a Dutch auction of `item_id` owned by the signing account.
+    pub async fn create_auction(
+        &self,
+        item_id: Word,
+        initial_cost: Word,
+        payment_token: ContentAddress,
+    ) -> anyhow::Result<ContentAddress> {
+        let existing = self.item(item_id).await?;
+        validate::create_auction(existing.as_ref(), initial_cost).map_err(AuctionError::from)?;
+        self.run(&create_auction::CreateAuction {
+            item_id,
+            hashed_key: self.hashed_key,
+            initial_cost,
+            payment_token,
+            contract: self.contract.clone(),
+        })
+        .await
+    }
+
+    /// Lowers the price of a Dutch auction owned by the signing account.
+    pub async fn step_auction(
+        &self,
+        item_id: Word,
+        decrease: Word,
+    ) -> anyhow::Result<ContentAddress> {
+        let item = self.listed_item(item_id).await?;
+        validate::step_auction(&item, self.hashed_key, decrease).map_err(AuctionError::from)?;
+        self.run(&step_auction::StepAuction {
+            item_id,
+            hashed_key: self.hashed_key,
+            decrease,
+            contract: self.contract.clone(),
+        })
+        .await
+    }
+
+    /// Bids `amount` on a Dutch auction, buying the item.
+    pub async fn place_bid(&self, item_id: Word, amount: Word) -> anyhow::Result<ContentAddress> {
+        let item = self.listed_item(item_id).await?;
+        let collection = self
+            .query(&self.contract, item_collection_key(item_id))
+            .await?;
+        let action = place_bid::PlaceBid {
+            item_id,
+            hashed_from_key: self.hashed_key,
+            hashed_to_key: item.owner,
+            amount,
+            payment_token: item.payment_token.clone(),
+            collection: item_collection(Query(collection))?,
+            contract: self.contract.clone(),
+        };
+        let state = self.fetch(&action).await?;
+        let from_balance = state.get(&item.payment_token, &balance_key(self.hashed_key));
+        validate::place_bid(&item, amount, &item.payment_token, balance(from_balance)?)
+            .map_err(AuctionError::from)?;
+        self.submit(&action.build(&state)?).await
+    }
+
+    /// Reads the state `action` requires, builds it and submits it.
+    pub async fn run(&self, action: &impl SolutionBuilder) -> anyhow::Result<ContentAddress> {
+        let state = self.fetch(action).await?;
+        self.submit(&action.build(&state)?).await
+    }
+
+    /// Reads the state `action` requires, builds it and checks it against the
+    /// node without submitting it.
+    pub async fn check(&self, action: &impl SolutionBuilder) -> anyhow::Result<Report> {
+        let state = self.fetch(action).await?;
+        let solution = action.build(&state)?;
+        Ok(dry_run::check(self.node.clone(), &self.contracts, &solution).await?)
+    }
+
+    /// Reads the state `action` requires.
+    pub async fn fetch(&self, action: &impl SolutionBuilder) -> Result<State, AuctionError> {
+        fetch_state(&self.node, action).await
+    }
+
+    /// Sends a solution to the builder.
+    ///
+    /// In a dry run the solution is checked against the node instead, and is
+    /// an error listing every failing constraint if the builder would reject it.
+    pub async fn submit(&self, solution: &Solution) -> anyhow::Result<ContentAddress> {
+        if !self.dry_run {
+            return self.builder.submit(solution).await;
+        }
+        let report = dry_run::check(self.node.clone(), &self.contracts, solution).await?;
+        if !report.passed() {
+            anyhow::bail!("Dry run failed, the solution was not sent\n{}", report);
+        }
+        Ok(essential_hash::content_addr(solution))
+    }
+
+    /// Reads an item that must already be listed.
+    async fn listed_item(&self, item_id: Word) -> anyhow::Result<AuctionItem> {
+        match self.item(item_id).await? {
+            Some(item) => Ok(item),
+            None => anyhow::bail!("Item {} has not been listed", item_id),
+        }
+    }
+
+    /// Reads the value at `key` in the state of `contract`.
+    async fn query(
+        &self,
+        contract: &ContentAddress,
+        key: Key,
+    ) -> Result<Option<Value>, AuctionError> {
+        self.node.read(contract, &key).await
+    }
+}
+
+/// Hashes the public key of `account`, as the contracts identify accounts.
+pub fn hash_key(wallet: &mut Wallet, account: &str) -> anyhow::Result<[Word; 4]> {
+    let public_key = wallet.get_public_key(account)?;
+    let essential_signer::PublicKey::Secp256k1(public_key) = public_key else {
+        anyhow::bail!("Account {} does not have a secp256k1 key", account);
+    };
+    let encoded = essential_sign::encode::public_key(&public_key);
+    Ok(word_4_from_u8_32(essential_hash::hash_words(&encoded)))
+}
@@ -0,0 +1,56 @@
+//! # Close Raffle
+//! Contains functionality for taking an item out of a raffle that can't be drawn.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{address_words, cost, deployed_at, validate, AuctionError, Query};
+
+/// Contains all necessary information to build a close raffle solution.
+pub struct BuildSolution {
+    /// The item being raffled.
+    pub item_id: Word,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// Whether the seller has revealed their secret.
+    pub current_seller_revealed: Query,
+    /// The number of entries in the raffle.
+    pub current_entry_count: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a close raffle solution based on the provided data.
+///
+/// The item stays with the seller, and every entrant can still reclaim
+/// their ticket.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        block_state,
+        current_seller_revealed,
+        current_entry_count,
+        contract,
+    } = build;
+
+    let seller_revealed = current_seller_revealed.as_opt_bool()?.unwrap_or_default();
+    validate::close_raffle(seller_revealed, cost(current_entry_count)?)?;
+
+    let pub_vars = super::auction::CloseRaffle::PubVars {
+        item_id,
+        block_state: address_words(&block_state),
+    };
+    let mutations = super::auction::storage::mutations()
+        .raffle(|map| map.entry(item_id, false))
+        .auction_ended(|map| map.entry(item_id, true));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::CloseRaffle::ADDRESS, &contract),
+        decision_variables: Default::default(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
@@ -0,0 +1,112 @@
+//! # Commit Bid
+//! Contains functionality for sealing a proxy bid in an ascending auction.
+//!
+//! The bidder commits to their maximum and escrows a deposit that covers it.
+//! Only the deposit is public until the maximum is revealed with a proxy bid
+//! once bidding closes.
+use essential_types::{
+    convert::word_4_from_u8_32,
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    address_words, cost, deployed_at, escrow_key, transfer, validate, AuctionError, Query,
+};
+
+/// Contains all necessary information to build a commit bid solution.
+pub struct BuildSolution {
+    /// The item being bid on.
+    pub item_id: Word,
+    /// The hashed key of the bidder.
+    pub hashed_key: [Word; 4],
+    /// The most the bidder is willing to pay.
+    pub max: Word,
+    /// The salt the maximum is sealed with.
+    pub salt: [Word; 4],
+    /// The amount escrowed to cover the maximum.
+    pub deposit: Word,
+    /// The token contract the item is priced in.
+    pub payment_token: ContentAddress,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The current visible price of the item.
+    pub current_cost: Query,
+    /// The bidder's current commitment on the item.
+    pub current_commitment: Query,
+    /// The current balance of the bidder.
+    pub current_balance: Query,
+    /// The current balance of the escrow.
+    pub current_escrow_balance: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The commitment to a maximum that is stored until it is revealed.
+pub fn commitment(max: Word, salt: [Word; 4]) -> [Word; 4] {
+    let mut words = vec![max];
+    words.extend_from_slice(&salt);
+    word_4_from_u8_32(essential_hash::hash_words(&words))
+}
+
+/// Builds a commit bid solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        max,
+        salt,
+        deposit,
+        payment_token,
+        block_state,
+        current_cost,
+        current_commitment,
+        current_balance,
+        current_escrow_balance,
+        contract,
+    } = build;
+
+    let sealed = current_commitment.as_opt_b256()?;
+    validate::commit_bid(sealed, max, deposit, cost(current_cost)?)?;
+    let commitment = commitment(max, salt);
+
+    let pub_vars = super::auction::CommitBid::PubVars {
+        item_id,
+        bidder: hashed_key,
+        commitment,
+        deposit,
+        payment_token: address_words(&payment_token),
+        block_state: address_words(&block_state),
+    };
+    let vars = super::auction::CommitBid::Vars {
+        transfer_pathway: TRANSFER_PATHWAY,
+    };
+    let mutations = super::auction::storage::mutations()
+        .bid_commitments(|map| map.entry(item_id, |map| map.entry(hashed_key, commitment)))
+        .bid_deposits(|map| map.entry(item_id, |map| map.entry(hashed_key, deposit)));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::CommitBid::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+
+    // The deposit is escrowed through the transfer predicate of the token.
+    let transfer = transfer::build_solution_data(
+        payment_token,
+        transfer::BuildSolution {
+            hashed_from_key: hashed_key,
+            hashed_to_key: escrow_key(&contract),
+            amount: deposit,
+            current_from_balance: current_balance,
+            current_to_balance: current_escrow_balance,
+            authority: 0,
+        },
+    )?;
+    Ok(Solution {
+        data: vec![solution, transfer],
+    })
+}
+
+/// The index of the escrow transfer within a commit bid solution.
+const TRANSFER_PATHWAY: Word = 1;
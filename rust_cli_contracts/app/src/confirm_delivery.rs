@@ -0,0 +1,91 @@
+//! # Confirm Delivery
+//! Contains functionality for a buyer releasing a sale held in escrow to the seller.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    cost, deployed_at, escrow_buyer, item_owner, signature_words, validate, AuctionError, Query,
+    ESCROW_SETTLED,
+};
+
+/// Contains all necessary information to build a confirm delivery solution.
+pub struct BuildSolution {
+    /// The item that was delivered.
+    pub item_id: Word,
+    /// The hashed key of the buyer.
+    pub hashed_key: [Word; 4],
+    /// The buyer of the held sale.
+    pub current_buyer: Query,
+    /// The seller of the held sale.
+    pub current_seller: Query,
+    /// The escrow status of the sale.
+    pub current_escrow_status: Query,
+    /// The number of the item's sales held in escrow, which names this sale.
+    pub current_escrow_sale: Query,
+    /// The amount held in escrow.
+    pub current_escrow_amount: Query,
+    /// The current unclaimed proceeds of the item.
+    pub current_proceeds: Query,
+    /// The buyer's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The words the buyer signs to confirm delivery.
+pub fn data_to_sign(item_id: Word, sale: Word) -> Vec<Word> {
+    vec![item_id, sale, CONFIRM]
+}
+
+/// Builds a confirm delivery solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        current_buyer,
+        current_seller,
+        current_escrow_status,
+        current_escrow_sale,
+        current_escrow_amount,
+        current_proceeds,
+        signature,
+        contract,
+    } = build;
+
+    validate::confirm_delivery(
+        escrow_buyer(current_buyer)?,
+        hashed_key,
+        cost(current_escrow_status)?,
+    )?;
+    let seller = item_owner(current_seller)?;
+    let proceeds = cost(current_proceeds)? + cost(current_escrow_amount)?;
+
+    let pub_vars = super::auction::ConfirmDelivery::PubVars {
+        item_id,
+        buyer: hashed_key,
+        sale: cost(current_escrow_sale)?,
+    };
+    let vars = super::auction::ConfirmDelivery::Vars {
+        sig: signature_words(&signature),
+    };
+    // The seller claims the released sale with `claim_proceeds`.
+    let mutations = super::auction::storage::mutations()
+        .proceeds(|map| map.entry(item_id, proceeds))
+        .proceeds_seller(|map| map.entry(item_id, seller))
+        .escrow_status(|map| map.entry(item_id, ESCROW_SETTLED));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::ConfirmDelivery::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
+
+/// Tags a signature as a delivery confirmation.
+const CONFIRM: Word = 0;
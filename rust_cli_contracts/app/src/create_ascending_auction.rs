@@ -0,0 +1,101 @@
+//! # Create Ascending Auction
+//! Contains functionality for listing an item in an ascending auction with proxy bids.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    address_words, cost, curator_admin, deployed_at, validate, AuctionError, Query, LISTING_PENDING,
+};
+
+/// Contains all necessary information to build a create ascending auction solution.
+pub struct BuildSolution {
+    /// The item being auctioned.
+    pub item_id: Word,
+    /// The hashed key of the seller.
+    pub hashed_key: [Word; 4],
+    /// The reserve price bidding starts from.
+    pub reserve: Word,
+    /// The amount the leader's price rises above a losing maximum.
+    pub increment: Word,
+    /// The block number at which bidding closes and sealed maximums are revealed.
+    pub deadline: Word,
+    /// The block number at which reveals close and the auction can be settled.
+    pub reveals_close: Word,
+    /// The token contract the item is priced in.
+    pub payment_token: ContentAddress,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The collection the item is registered in, if any.
+    pub collection: Option<Word>,
+    /// The current curator admin.
+    /// Once set, the auction stays pending until a curator approves it.
+    pub current_curator_admin: Query,
+    /// The current number of listings created under curation.
+    pub current_curated_item_count: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a create ascending auction solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        reserve,
+        increment,
+        deadline,
+        reveals_close,
+        payment_token,
+        block_state,
+        collection,
+        current_curator_admin,
+        current_curated_item_count,
+        contract,
+    } = build;
+
+    validate::create_ascending_auction(reserve, increment, deadline, reveals_close)?;
+
+    let curated = curator_admin(current_curator_admin)?.is_some();
+    let curated_index = cost(current_curated_item_count)?;
+
+    let payment_token = address_words(&payment_token);
+    let pub_vars = super::auction::CreateAscendingAuction::PubVars {
+        item_id,
+        initial_cost: reserve,
+        increment,
+        deadline,
+        reveals_close,
+        key: hashed_key,
+        payment_token,
+        block_state: address_words(&block_state),
+        collection: collection.unwrap_or_default(),
+        curated_index,
+    };
+    let mut mutations = super::auction::storage::mutations()
+        .item_owner(|map| map.entry(item_id, hashed_key))
+        .auction_ended(|map| map.entry(item_id, false))
+        .cost(|map| map.entry(item_id, reserve))
+        .payment_token(|map| map.entry(item_id, payment_token))
+        .ascending(|map| map.entry(item_id, true))
+        .bid_increment(|map| map.entry(item_id, increment))
+        .auction_deadline(|map| map.entry(item_id, deadline))
+        .bid_reveal_deadline(|map| map.entry(item_id, reveals_close));
+    if curated {
+        // The auction is pending until a curator reviews it.
+        mutations = mutations
+            .listing_status(|map| map.entry(item_id, LISTING_PENDING))
+            .curated_items(|map| map.entry(curated_index, item_id))
+            .curated_item_count(curated_index + 1);
+    }
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::CreateAscendingAuction::ADDRESS, &contract),
+        decision_variables: Default::default(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
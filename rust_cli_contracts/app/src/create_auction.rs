@@ -4,10 +4,14 @@
 use essential_types::{
     convert::word_4_from_u8_32,
     solution::{Solution, SolutionData},
-    Word,
+    ContentAddress, Word,
 };
 
-use crate::{balance, Query};
+use crate::{
+    action::{SolutionBuilder, State, StateKey},
+    address_words, balance, cost, curated_item_count_key, curator_admin, curator_admin_key,
+    deployed_at, item_collection, item_collection_key, AuctionError, Query, LISTING_PENDING,
+};
 
 /// details
 pub struct BuildSolution {
@@ -17,27 +21,62 @@ pub struct BuildSolution {
     pub hashed_key: [Word; 4],
     /// The amount of tokens to mint.
     pub amount: Word,
+    /// The token contract the item is priced in.
+    pub payment_token: ContentAddress,
+    /// The collection the item is registered in, if any.
+    pub collection: Option<Word>,
+    /// The current curator admin.
+    /// Once set, the auction stays pending until a curator approves it.
+    pub current_curator_admin: Query,
+    /// The current number of auctions created under curation.
+    pub current_curated_item_count: Query,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
 }
 
 /// Builds a mint solution based on the provided data.
-pub fn build_solution(build: BuildSolution) -> anyhow::Result<Solution> {
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
     let BuildSolution {
         item_id,
         hashed_key,
         amount,
+        payment_token,
+        collection,
+        current_curator_admin,
+        current_curated_item_count,
+        block_state,
+        contract,
     } = build;
 
-    let pub_vars = super::token::CreateAuction::PubVars {
+    let curated = curator_admin(current_curator_admin)?.is_some();
+    let curated_index = cost(current_curated_item_count)?;
+
+    let payment_token = address_words(&payment_token);
+    let pub_vars = super::auction::CreateAuction::PubVars {
         item_id: item_id,
         initial_cost: amount,
         key: hashed_key,
+        payment_token,
+        collection: collection.unwrap_or_default(),
+        curated_index,
+        block_state: address_words(&block_state),
     };
-    let mutations = super::token::storage::mutations()
+    let mut mutations = super::auction::storage::mutations()
         .item_owner(|map| map.entry(item_id, hashed_key))
         .auction_ended(|map| map.entry(item_id, false))
-        .cost(|map| map.entry(item_id, amount));
+        .cost(|map| map.entry(item_id, amount))
+        .payment_token(|map| map.entry(item_id, payment_token));
+    if curated {
+        // The auction is pending until a curator reviews it.
+        mutations = mutations
+            .listing_status(|map| map.entry(item_id, LISTING_PENDING))
+            .curated_items(|map| map.entry(curated_index, item_id))
+            .curated_item_count(curated_index + 1);
+    }
     let solution = SolutionData {
-        predicate_to_solve: super::token::CreateAuction::ADDRESS,
+        predicate_to_solve: deployed_at(super::auction::CreateAuction::ADDRESS, &contract),
         decision_variables: Default::default(),
         transient_data: pub_vars.into(),
         state_mutations: mutations.into(),
@@ -46,3 +85,42 @@ pub fn build_solution(build: BuildSolution) -> anyhow::Result<Solution> {
         data: vec![solution],
     })
 }
+
+/// Starts a Dutch auction of an item.
+pub struct CreateAuction {
+    /// The item being auctioned.
+    pub item_id: Word,
+    /// The hashed key of the owner.
+    pub hashed_key: [Word; 4],
+    /// The price the auction starts at.
+    pub initial_cost: Word,
+    /// The token contract the item is priced in.
+    pub payment_token: ContentAddress,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+impl SolutionBuilder for CreateAuction {
+    fn required_keys(&self) -> Vec<StateKey> {
+        vec![
+            (self.contract.clone(), item_collection_key(self.item_id)),
+            (self.contract.clone(), curator_admin_key()),
+            (self.contract.clone(), curated_item_count_key()),
+        ]
+    }
+
+    fn build(&self, state: &State) -> Result<Solution, AuctionError> {
+        let auction = &self.contract;
+        build_solution(BuildSolution {
+            item_id: self.item_id,
+            hashed_key: self.hashed_key,
+            amount: self.initial_cost,
+            payment_token: self.payment_token.clone(),
+            collection: item_collection(state.get(auction, &item_collection_key(self.item_id)))?,
+            current_curator_admin: state.get(auction, &curator_admin_key()),
+            current_curated_item_count: state.get(auction, &curated_item_count_key()),
+            block_state: super::block_state::ADDRESS,
+            contract: auction.clone(),
+        })
+    }
+}
@@ -0,0 +1,59 @@
+//! # Create Collection
+//! Contains functionality for creating a collection with a royalty on its sales.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{collection_creator, deployed_at, validate, AuctionError, Query};
+
+/// Contains all necessary information to build a create collection solution.
+pub struct BuildSolution {
+    /// The collection being created.
+    pub collection: Word,
+    /// The hashed key of the creator.
+    pub hashed_key: [Word; 4],
+    /// The royalty on fixed price sales in basis points.
+    pub royalty: Word,
+    /// Whether anyone can sell the items of the collection.
+    pub open: bool,
+    /// The current creator of the collection.
+    pub current_creator: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a create collection solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        collection,
+        hashed_key,
+        royalty,
+        open,
+        current_creator,
+        contract,
+    } = build;
+
+    validate::create_collection(current_creator.as_opt_b256()?, royalty)?;
+
+    let pub_vars = super::auction::CreateCollection::PubVars {
+        collection,
+        key: hashed_key,
+        royalty,
+        open,
+    };
+    let mutations = super::auction::storage::mutations()
+        .collection_creator(|map| map.entry(collection, hashed_key))
+        .collection_royalty(|map| map.entry(collection, royalty))
+        .collection_open(|map| map.entry(collection, open))
+        .collection_item_count(|map| map.entry(collection, 0));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::CreateCollection::ADDRESS, &contract),
+        decision_variables: Default::default(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
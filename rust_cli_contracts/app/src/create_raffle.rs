@@ -0,0 +1,107 @@
+//! # Create Raffle
+//! Contains functionality for offering an item in a raffle.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    address_words, cost, curator_admin, deployed_at, reveal_raffle::commitment, validate,
+    AuctionError, Query, LISTING_PENDING,
+};
+
+/// Contains all necessary information to build a create raffle solution.
+pub struct BuildSolution {
+    /// The item being raffled.
+    pub item_id: Word,
+    /// The hashed key of the seller.
+    pub hashed_key: [Word; 4],
+    /// The price of a ticket.
+    pub ticket_price: Word,
+    /// The block number at which entries close and reveals open.
+    pub entries_close: Word,
+    /// The block number at which reveals close.
+    pub reveals_close: Word,
+    /// The seller's secret contribution to the draw.
+    pub secret: Word,
+    /// The salt hiding the seller's secret.
+    pub salt: [Word; 4],
+    /// The token contract tickets are paid in.
+    pub payment_token: ContentAddress,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The collection the item is registered in, if any.
+    pub collection: Option<Word>,
+    /// The current curator admin.
+    /// Once set, the raffle stays pending until a curator approves it.
+    pub current_curator_admin: Query,
+    /// The current number of listings created under curation.
+    pub current_curated_item_count: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a create raffle solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        ticket_price,
+        entries_close,
+        reveals_close,
+        secret,
+        salt,
+        payment_token,
+        block_state,
+        collection,
+        current_curator_admin,
+        current_curated_item_count,
+        contract,
+    } = build;
+
+    validate::create_raffle(ticket_price, entries_close, reveals_close)?;
+
+    let curated = curator_admin(current_curator_admin)?.is_some();
+    let curated_index = cost(current_curated_item_count)?;
+    let commitment = commitment(secret, salt)?;
+    let payment_token = address_words(&payment_token);
+    let pub_vars = super::auction::CreateRaffle::PubVars {
+        item_id,
+        key: hashed_key,
+        price: ticket_price,
+        entries_close,
+        reveals_close,
+        commitment,
+        payment_token,
+        block_state: address_words(&block_state),
+        collection: collection.unwrap_or_default(),
+        curated_index,
+    };
+    let mut mutations = super::auction::storage::mutations()
+        .item_owner(|map| map.entry(item_id, hashed_key))
+        .auction_ended(|map| map.entry(item_id, false))
+        .payment_token(|map| map.entry(item_id, payment_token))
+        .raffle(|map| map.entry(item_id, true))
+        .ticket_price(|map| map.entry(item_id, ticket_price))
+        .ticket_token(|map| map.entry(item_id, payment_token))
+        .entry_deadline(|map| map.entry(item_id, entries_close))
+        .reveal_deadline(|map| map.entry(item_id, reveals_close))
+        .raffle_entry_count(|map| map.entry(item_id, 0))
+        .raffle_commitments(|map| map.entry(item_id, |map| map.entry(hashed_key, commitment)));
+    if curated {
+        // The raffle is pending until a curator reviews it.
+        mutations = mutations
+            .listing_status(|map| map.entry(item_id, LISTING_PENDING))
+            .curated_items(|map| map.entry(curated_index, item_id))
+            .curated_item_count(curated_index + 1);
+    }
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::CreateRaffle::ADDRESS, &contract),
+        decision_variables: Default::default(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
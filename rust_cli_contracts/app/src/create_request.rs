@@ -0,0 +1,90 @@
+//! # Create Request
+//! Contains functionality for a buyer opening a reverse auction with an escrowed budget.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{address_words, deployed_at, escrow_key, transfer, validate, AuctionError, Query};
+
+/// Contains all necessary information to build a create request solution.
+pub struct BuildSolution {
+    /// The item being requested.
+    pub item_id: Word,
+    /// The hashed key of the buyer.
+    pub hashed_key: [Word; 4],
+    /// The budget escrowed for the request.
+    pub budget: Word,
+    /// The token contract the budget is escrowed in.
+    pub payment_token: ContentAddress,
+    /// The block number at which asks close.
+    pub deadline: Word,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The current balance of the buyer.
+    pub current_balance: Query,
+    /// The current balance of the escrow.
+    pub current_escrow_balance: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a create request solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        budget,
+        payment_token,
+        deadline,
+        block_state,
+        current_balance,
+        current_escrow_balance,
+        contract,
+    } = build;
+
+    validate::create_request(budget)?;
+
+    let pub_vars = super::auction::CreateRequest::PubVars {
+        item_id,
+        key: hashed_key,
+        budget,
+        payment_token: address_words(&payment_token),
+        deadline,
+        block_state: address_words(&block_state),
+    };
+    let vars = super::auction::CreateRequest::Vars {
+        transfer_pathway: TRANSFER_PATHWAY,
+    };
+    let mutations = super::auction::storage::mutations()
+        .request_buyer(|map| map.entry(item_id, hashed_key))
+        .request_budget(|map| map.entry(item_id, budget))
+        .request_token(|map| map.entry(item_id, address_words(&payment_token)))
+        .request_deadline(|map| map.entry(item_id, deadline))
+        .request_settled(|map| map.entry(item_id, false));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::CreateRequest::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+
+    // The budget is escrowed through the transfer predicate of the token.
+    let transfer = transfer::build_solution_data(
+        payment_token,
+        transfer::BuildSolution {
+            hashed_from_key: hashed_key,
+            hashed_to_key: escrow_key(&contract),
+            amount: budget,
+            current_from_balance: current_balance,
+            current_to_balance: current_escrow_balance,
+            authority: 0,
+        },
+    )?;
+    Ok(Solution {
+        data: vec![solution, transfer],
+    })
+}
+
+/// The index of the escrow transfer within a create request solution.
+const TRANSFER_PATHWAY: Word = 1;
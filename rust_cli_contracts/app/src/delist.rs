@@ -0,0 +1,69 @@
+//! # Delist
+//! Contains functionality for taking a fixed price listing off sale.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{deployed_at, offer, signature_words, AuctionError, Query};
+
+/// Contains all necessary information to build a delist solution.
+pub struct BuildSolution {
+    /// The item being delisted.
+    pub item_id: Word,
+    /// The hashed key of the owner.
+    pub hashed_key: [Word; 4],
+    /// The owner's current nonce.
+    pub current_nonce: Query,
+    /// The owner's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The words the owner signs to delist an item.
+///
+/// The nonce is the owner's current nonce.
+pub fn data_to_sign(item_id: Word, nonce: Word) -> Vec<Word> {
+    vec![item_id, nonce, DELIST]
+}
+
+/// Builds a delist solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        current_nonce,
+        signature,
+        contract,
+    } = build;
+
+    let nonce = offer(current_nonce)?;
+    let next_nonce = nonce.checked_add(1).ok_or(AuctionError::Overflow)?;
+
+    let pub_vars = super::auction::Delist::PubVars {
+        item_id,
+        key: hashed_key,
+        nonce,
+    };
+    let vars = super::auction::Delist::Vars {
+        sig: signature_words(&signature),
+    };
+    let mutations = super::auction::storage::mutations()
+        .auction_ended(|map| map.entry(item_id, true))
+        .fixed_price(|map| map.entry(item_id, false))
+        .nonces(|map| map.entry(hashed_key, next_nonce));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::Delist::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
+
+/// Tags a signature as a delisting.
+const DELIST: Word = 7;
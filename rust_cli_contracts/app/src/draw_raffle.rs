@@ -0,0 +1,101 @@
+//! # Draw Raffle
+//! Contains functionality for handing a raffled item to the entrant picked by the seed.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    address_words, buy::royalty, cost, deployed_at, item_collection, item_owner, raffle_entrant,
+    validate, AuctionError, Query,
+};
+
+/// Contains all necessary information to build a draw raffle solution.
+pub struct BuildSolution {
+    /// The item being raffled.
+    pub item_id: Word,
+    /// The token contract tickets are paid in.
+    pub payment_token: ContentAddress,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The current owner of the item.
+    pub current_owner: Query,
+    /// The price of a ticket.
+    pub current_ticket_price: Query,
+    /// The entrant with the winning entry number.
+    pub current_winner: Query,
+    /// The collection the item is registered in.
+    pub current_collection: Query,
+    /// The royalty rate of the item's collection in basis points.
+    pub current_royalty_rate: Query,
+    /// The royalties owed to the item's collection in the ticket token.
+    pub current_royalties: Query,
+    /// The winning entry number, see [`winning_index`].
+    pub index: Word,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The entry number the seed picks out of `entry_count` entries.
+pub fn winning_index(seed: Word, entry_count: Word) -> Result<Word, AuctionError> {
+    validate::draw_raffle(entry_count)?;
+    Ok(seed % entry_count)
+}
+
+/// Builds a draw raffle solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        payment_token,
+        block_state,
+        current_owner,
+        current_ticket_price,
+        current_winner,
+        current_collection,
+        current_royalty_rate,
+        current_royalties,
+        index,
+        contract,
+    } = build;
+
+    let seller = item_owner(current_owner)?;
+    let winner = raffle_entrant(current_winner)?;
+    let price = cost(current_ticket_price)?;
+    let collection = item_collection(current_collection)?;
+    let royalty = match collection {
+        Some(_) => royalty(price, cost(current_royalty_rate)?)?,
+        None => 0,
+    };
+
+    let payment_token = address_words(&payment_token);
+    let pub_vars = super::auction::DrawRaffle::PubVars {
+        item_id,
+        winner,
+        index,
+        payment_token,
+        collection: collection.unwrap_or_default(),
+        block_state: address_words(&block_state),
+    };
+    let mut mutations = super::auction::storage::mutations()
+        .item_owner(|map| map.entry(item_id, winner))
+        .raffle_winner(|map| map.entry(item_id, winner))
+        .proceeds(|map| map.entry(item_id, price - royalty))
+        .proceeds_seller(|map| map.entry(item_id, seller))
+        .auction_ended(|map| map.entry(item_id, true));
+    if let Some(collection) = collection {
+        // The creator's royalty is split off before the seller claims the rest.
+        let royalties = cost(current_royalties)? + royalty;
+        mutations = mutations.collection_royalties(|map| {
+            map.entry(collection, |map| map.entry(payment_token, royalties))
+        });
+    }
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::DrawRaffle::ADDRESS, &contract),
+        decision_variables: Default::default(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
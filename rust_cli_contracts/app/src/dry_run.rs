@@ -0,0 +1,329 @@
+//! # Dry Run
+//! Contains functionality for checking a solution against state without submitting it.
+//!
+//! The solution is run through essential's own predicate checks, reading the
+//! pre state from a [`StateSource`] and the post state from the same source
+//! with the solution's mutations applied, so a dry run fails on the same
+//! constraints the builder would. The report says which constraints of each
+//! predicate fail, so a failure can be traced back to the contract.
+use std::{collections::HashMap, fmt, future::Future, pin::Pin, sync::Arc};
+
+use essential_check::{
+    solution::{
+        check_predicates, CheckPredicateConfig, PredicateConstraintsError, PredicateError,
+        PredicatesError,
+    },
+    state_read_vm::StateRead,
+};
+use essential_rest_client::node_client::EssentialNodeClient;
+use essential_types::{
+    contract::Contract, predicate::Predicate, solution::Solution, ContentAddress, Key,
+    PredicateAddress, Value, Word,
+};
+
+use crate::{generated_contracts, AuctionError, ContractAddresses};
+
+/// Where a dry run reads state from.
+///
+/// Implemented for the REST node client and, with the `test-utils` feature,
+/// for the databases of an in-process node.
+pub trait StateSource: Send + Sync + 'static {
+    /// Reads the value at `key` in the state of `contract`.
+    fn read(
+        &self,
+        contract: &ContentAddress,
+        key: &Key,
+    ) -> impl Future<Output = Result<Option<Value>, AuctionError>> + Send;
+}
+
+impl StateSource for EssentialNodeClient {
+    async fn read(
+        &self,
+        contract: &ContentAddress,
+        key: &Key,
+    ) -> Result<Option<Value>, AuctionError> {
+        self.query_state(contract.clone(), key.clone())
+            .await
+            .map_err(AuctionError::node)
+    }
+}
+
+/// Reads state from the databases of an in-process node, as tests run one.
+#[cfg(feature = "test-utils")]
+#[derive(Clone)]
+pub struct NodeDb(pub essential_node::db::ConnectionPool);
+
+#[cfg(feature = "test-utils")]
+impl StateSource for NodeDb {
+    async fn read(
+        &self,
+        contract: &ContentAddress,
+        key: &Key,
+    ) -> Result<Option<Value>, AuctionError> {
+        essential_app_utils::node::query_state_head(&self.0, contract, key)
+            .await
+            .map_err(AuctionError::node)
+    }
+}
+
+/// The predicates a dry run can check, by the address they are deployed at.
+#[derive(Debug, Clone, Default)]
+pub struct Contracts(HashMap<PredicateAddress, Arc<Predicate>>);
+
+impl Contracts {
+    /// The contracts the [`crate::token`] and [`crate::auction`] modules were
+    /// generated from, with the auction contract deployed at `auction`.
+    pub fn generated(auction: &ContentAddress) -> Result<Self, AuctionError> {
+        let mut contracts = Self::default();
+        for contract in generated_contracts()? {
+            let address = essential_hash::contract_addr::from_contract(&contract);
+            if address == crate::auction::ADDRESS {
+                contracts.insert(auction.clone(), &contract);
+            } else {
+                contracts.insert(address, &contract);
+            }
+        }
+        Ok(contracts)
+    }
+
+    /// The generated contracts together with the contract a command acts
+    /// on, at the address it was overridden to if it was.
+    pub fn in_use(addresses: &ContractAddresses) -> Result<Self, AuctionError> {
+        let mut contracts = Self::generated(&crate::auction::ADDRESS)?;
+        contracts.insert(addresses.contract.clone(), &addresses.compiled);
+        Ok(contracts)
+    }
+
+    /// Adds the predicates of `contract`, deployed at `address`.
+    pub fn insert(&mut self, address: ContentAddress, contract: &Contract) {
+        for predicate in &contract.predicates {
+            let predicate_address = PredicateAddress {
+                contract: address.clone(),
+                predicate: essential_hash::content_addr(predicate),
+            };
+            self.0
+                .insert(predicate_address, Arc::new(predicate.clone()));
+        }
+    }
+}
+
+/// How one predicate of a solution fared in a dry run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checked {
+    /// The predicate the solution data solves.
+    pub predicate: PredicateAddress,
+    /// Whether each constraint of the predicate holds, in order.
+    pub constraints: Vec<Result<(), String>>,
+    /// Why the predicate could not be checked at all, if it could not.
+    pub error: Option<String>,
+}
+
+impl Checked {
+    /// Whether the predicate could be checked and all its constraints hold.
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.constraints.iter().all(Result::is_ok)
+    }
+}
+
+/// How every predicate of a solution fared in a dry run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// One entry for each solution data, in order.
+    pub predicates: Vec<Checked>,
+}
+
+impl Report {
+    /// Whether the builder would accept the solution against this state.
+    pub fn passed(&self) -> bool {
+        self.predicates.iter().all(Checked::passed)
+    }
+}
+
+/// One line for each predicate that passes and for each failure, numbered by
+/// the solution data it is about.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, checked) in self.predicates.iter().enumerate() {
+            let predicate = &checked.predicate.predicate;
+            if checked.passed() {
+                writeln!(f, "{}: predicate {} passes", index, predicate)?;
+            }
+            if let Some(err) = &checked.error {
+                writeln!(f, "{}: predicate {} fails: {}", index, predicate, err)?;
+            }
+            for (constraint, outcome) in checked.constraints.iter().enumerate() {
+                if let Err(err) = outcome {
+                    writeln!(
+                        f,
+                        "{}: predicate {} constraint {} fails: {}",
+                        index, predicate, constraint, err
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks `solution` against the state in `source`.
+///
+/// Every predicate the solution solves must be in `contracts`, otherwise it
+/// is reported as failing without running any checks.
+pub async fn check<S: StateSource>(
+    source: S,
+    contracts: &Contracts,
+    solution: &Solution,
+) -> Result<Report, AuctionError> {
+    let Contracts(predicates) = contracts;
+    let mut report = Report {
+        predicates: solution
+            .data
+            .iter()
+            .map(|data| match predicates.get(&data.predicate_to_solve) {
+                Some(predicate) => Checked {
+                    predicate: data.predicate_to_solve.clone(),
+                    constraints: vec![Ok(()); predicate.constraints.len()],
+                    error: None,
+                },
+                None => Checked {
+                    predicate: data.predicate_to_solve.clone(),
+                    constraints: Vec::new(),
+                    error: Some("predicate is not in a known contract".to_string()),
+                },
+            })
+            .collect(),
+    };
+    if !report.passed() {
+        return Ok(report);
+    }
+
+    let mut mutations = HashMap::new();
+    for data in &solution.data {
+        for mutation in &data.state_mutations {
+            mutations.insert(
+                (
+                    data.predicate_to_solve.contract.clone(),
+                    mutation.key.clone(),
+                ),
+                mutation.value.clone(),
+            );
+        }
+    }
+    let source = Arc::new(source);
+    let pre_state = View {
+        source: source.clone(),
+        mutations: Default::default(),
+    };
+    let post_state = View {
+        source,
+        mutations: Arc::new(mutations),
+    };
+
+    let result = check_predicates(
+        &pre_state,
+        &post_state,
+        Arc::new(solution.clone()),
+        |address: &PredicateAddress| predicates[address].clone(),
+        Arc::new(CheckPredicateConfig::default()),
+    )
+    .await;
+    match result {
+        Ok(_) => {}
+        Err(PredicatesError::Failed(failed)) => {
+            for (index, err) in failed.0 {
+                let checked = &mut report.predicates[usize::from(index)];
+                match err {
+                    PredicateError::Constraints(PredicateConstraintsError::Unsatisfied(
+                        unsatisfied,
+                    )) => {
+                        for constraint in unsatisfied.0 {
+                            fail(checked, constraint, "not satisfied".to_string());
+                        }
+                    }
+                    PredicateError::Constraints(PredicateConstraintsError::Failed(failed)) => {
+                        for (constraint, err) in failed.0 {
+                            fail(checked, constraint, err.to_string());
+                        }
+                    }
+                    err => checked.error = Some(err.to_string()),
+                }
+            }
+        }
+        Err(err) => {
+            return Err(AuctionError::MalformedState(format!(
+                "Solution could not be checked: {}",
+                err
+            )))
+        }
+    }
+    Ok(report)
+}
+
+/// Records that a constraint of a checked predicate fails.
+fn fail(checked: &mut Checked, constraint: usize, reason: String) {
+    match checked.constraints.get_mut(constraint) {
+        Some(outcome) => *outcome = Err(reason),
+        None => {
+            checked.error = Some(format!("constraint {} fails: {}", constraint, reason));
+        }
+    }
+}
+
+/// A view of the state in a source, with mutations applied on top.
+struct View<S> {
+    source: Arc<S>,
+    mutations: Arc<HashMap<(ContentAddress, Key), Value>>,
+}
+
+impl<S> Clone for View<S> {
+    fn clone(&self) -> Self {
+        Self {
+            source: self.source.clone(),
+            mutations: self.mutations.clone(),
+        }
+    }
+}
+
+impl<S: StateSource> StateRead for View<S> {
+    type Error = AuctionError;
+    type Future = Pin<Box<dyn Future<Output = Result<Vec<Vec<Word>>, AuctionError>> + Send>>;
+
+    fn key_range(
+        &self,
+        contract_addr: ContentAddress,
+        mut key: Key,
+        num_values: usize,
+    ) -> Self::Future {
+        let view = self.clone();
+        Box::pin(async move {
+            let mut values = Vec::with_capacity(num_values);
+            for _ in 0..num_values {
+                let value = match view.mutations.get(&(contract_addr.clone(), key.clone())) {
+                    Some(value) => value.clone(),
+                    None => view
+                        .source
+                        .read(&contract_addr, &key)
+                        .await?
+                        .unwrap_or_default(),
+                };
+                values.push(value);
+                key = next_key(key)?;
+            }
+            Ok(values)
+        })
+    }
+}
+
+/// The key after `key` in a key range.
+fn next_key(mut key: Key) -> Result<Key, AuctionError> {
+    for word in key.iter_mut().rev() {
+        match word.checked_add(1) {
+            Some(next) => {
+                *word = next;
+                return Ok(key);
+            }
+            None => *word = Word::MIN,
+        }
+    }
+    Err(AuctionError::Overflow)
+}
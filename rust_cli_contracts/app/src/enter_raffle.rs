@@ -0,0 +1,99 @@
+//! # Enter Raffle
+//! Contains functionality for buying a raffle ticket into escrow.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    address_words, cost, deployed_at, escrow_key, reveal_raffle::commitment, transfer, validate,
+    AuctionError, Query,
+};
+
+/// Contains all necessary information to build an enter raffle solution.
+pub struct BuildSolution {
+    /// The item being raffled.
+    pub item_id: Word,
+    /// The hashed key of the entrant.
+    pub hashed_key: [Word; 4],
+    /// The entrant's secret contribution to the draw.
+    pub secret: Word,
+    /// The salt hiding the entrant's secret.
+    pub salt: [Word; 4],
+    /// The token contract tickets are paid in.
+    pub payment_token: ContentAddress,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The price of a ticket.
+    pub current_ticket_price: Query,
+    /// The current number of entries.
+    pub current_entry_count: Query,
+    /// The current balance of the entrant.
+    pub current_balance: Query,
+    /// The current balance of the escrow.
+    pub current_escrow_balance: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds an enter raffle solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        secret,
+        salt,
+        payment_token,
+        block_state,
+        current_ticket_price,
+        current_entry_count,
+        current_balance,
+        current_escrow_balance,
+        contract,
+    } = build;
+
+    let price = cost(current_ticket_price)?;
+    validate::enter_raffle(price)?;
+    let entry_count = cost(current_entry_count)?;
+    let commitment = commitment(secret, salt)?;
+
+    let pub_vars = super::auction::EnterRaffle::PubVars {
+        item_id,
+        entrant: hashed_key,
+        commitment,
+        block_state: address_words(&block_state),
+    };
+    let vars = super::auction::EnterRaffle::Vars {
+        transfer_pathway: TRANSFER_PATHWAY,
+    };
+    let mutations = super::auction::storage::mutations()
+        .raffle_entrants(|map| map.entry(item_id, |map| map.entry(entry_count, hashed_key)))
+        .raffle_entry_count(|map| map.entry(item_id, entry_count + 1))
+        .ticket_held(|map| map.entry(item_id, |map| map.entry(hashed_key, true)))
+        .raffle_commitments(|map| map.entry(item_id, |map| map.entry(hashed_key, commitment)));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::EnterRaffle::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+
+    // The ticket is escrowed through the transfer predicate of the token.
+    let transfer = transfer::build_solution_data(
+        payment_token,
+        transfer::BuildSolution {
+            hashed_from_key: hashed_key,
+            hashed_to_key: escrow_key(&contract),
+            amount: price,
+            current_from_balance: current_balance,
+            current_to_balance: current_escrow_balance,
+            authority: 0,
+        },
+    )?;
+    Ok(Solution {
+        data: vec![solution, transfer],
+    })
+}
+
+/// The index of the ticket payment within an enter raffle solution.
+const TRANSFER_PATHWAY: Word = 1;
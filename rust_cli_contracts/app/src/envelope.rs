@@ -0,0 +1,407 @@
+//! # Envelope
+//! Contains functionality for moving a solution between machines as a file.
+//!
+//! A solution that needs a signature can be built where the node is reachable,
+//! signed on a machine that holds the keys but has no network and submitted
+//! from anywhere. An [`Envelope`] carries the solution with a blank signature,
+//! the words to sign and where the signature goes, along with what the
+//! solution does and the state it was built from.
+use anyhow::bail;
+use essential_sign::secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+use essential_types::{
+    convert::{u8_32_from_word_4, word_4_from_u8_32},
+    solution::{Solution, SolutionData},
+    ContentAddress, Value, Word,
+};
+use pint_abi::types::PredicateABI;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    accept_offer, bid_intent, confirm_delivery, generated_abi, generated_addresses, make_offer,
+    open_dispute, resolve, signature_words, signed_transfer, withdraw_offer,
+};
+
+/// Where the signature goes in a solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureSlot {
+    /// The index of the solution data whose predicate checks the signature.
+    pub data: usize,
+    /// The index of the signature in its decision variables.
+    pub var: usize,
+}
+
+impl SignatureSlot {
+    /// Finds where the signature of `action` goes in `solution`.
+    ///
+    /// The data is the first that solves the action's predicate and the
+    /// variable is the position of its `sig` in the contract's ABI.
+    pub fn find(action: &str, solution: &Solution) -> anyhow::Result<Self> {
+        let (contract, name) = signed_predicate(action)?;
+        let abi = predicate_abi(contract, name)?;
+        let predicate = generated_addresses(contract)?.predicate(name)?.predicate;
+        let Some(data) = solution
+            .data
+            .iter()
+            .position(|data| data.predicate_to_solve.predicate == predicate)
+        else {
+            bail!("The {} solution does not solve {}", action, name);
+        };
+        let Some(var) = abi
+            .vars
+            .iter()
+            .position(|var| short_name(&var.name) == "sig")
+        else {
+            bail!("{} takes no signature", name);
+        };
+        Ok(Self { data, var })
+    }
+}
+
+/// A solution on its way from being built to being submitted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Envelope {
+    /// The action the solution takes, such as `make-offer`.
+    pub action: String,
+    /// The item the action is on, if it is on one.
+    pub item_id: Option<Word>,
+    /// The block number of the state the solution was built from, if the
+    /// block state contract has recorded one.
+    pub height: Option<Word>,
+    /// The hashed key of the account that must sign.
+    #[serde(with = "hex_key")]
+    pub signer: [Word; 4],
+    /// The words the signer signs.
+    pub to_sign: Vec<Word>,
+    /// Where the signature goes, or `None` if the solution takes none.
+    pub slot: Option<SignatureSlot>,
+    /// The signature, once the envelope has been signed.
+    #[serde(with = "hex_signature")]
+    pub signature: Option<RecoverableSignature>,
+    /// The solution, with a blank signature until the envelope is signed.
+    pub solution: Solution,
+}
+
+impl Envelope {
+    /// Puts the signer's signature over [`Envelope::data_to_sign`] into the solution.
+    pub fn sign(&mut self, signature: RecoverableSignature) -> anyhow::Result<()> {
+        let Some(SignatureSlot { data, var }) = self.slot else {
+            bail!("The {} solution takes no signature", self.action);
+        };
+        let Some(value) = self
+            .solution
+            .data
+            .get_mut(data)
+            .and_then(|data| data.decision_variables.get_mut(var))
+        else {
+            bail!(
+                "Solution has no signature at data {} variable {}",
+                data,
+                var
+            );
+        };
+        let (first, second, recovery_id) = signature_words(&signature);
+        *value = first
+            .into_iter()
+            .chain(second)
+            .chain([recovery_id])
+            .collect();
+        self.signature = Some(signature);
+        Ok(())
+    }
+
+    /// Whether the envelope has been signed, or needs no signature.
+    pub fn is_signed(&self) -> bool {
+        self.slot.is_none() || self.signature.is_some()
+    }
+
+    /// The signed solution, ready to submit.
+    pub fn into_solution(self) -> anyhow::Result<Solution> {
+        if !self.is_signed() {
+            bail!("The {} solution has not been signed", self.action);
+        }
+        Ok(self.solution)
+    }
+
+    /// Recomputes the words to sign from the public variables of the solution,
+    /// so the signer signs what the solution does rather than what the
+    /// envelope claims.
+    ///
+    /// An accepted offer is state the solution clears rather than a public
+    /// variable, so its amount is taken from [`Envelope::to_sign`] and only
+    /// checked against what the solution pays the owner. The contract rejects
+    /// the signature unless the escrowed offer is exactly that amount.
+    pub fn data_to_sign(&self) -> anyhow::Result<Vec<Word>> {
+        if self.slot.is_none() {
+            return Ok(Vec::new());
+        }
+        let vars = self.pub_vars()?;
+        Ok(match self.action.as_str() {
+            "make-offer" => make_offer::data_to_sign(
+                vars.int("item_id")?,
+                vars.int("amount")?,
+                &vars.address("payment_token")?,
+                vars.int("nonce")?,
+            ),
+            "accept-offer" => accept_offer::data_to_sign(
+                vars.int("item_id")?,
+                vars.b256("buyer")?,
+                self.accepted_offer()?,
+                vars.int("nonce")?,
+            ),
+            "withdraw-offer" => withdraw_offer::data_to_sign(
+                vars.int("item_id")?,
+                vars.b256("buyer")?,
+                vars.int("nonce")?,
+            ),
+            "confirm-delivery" => {
+                confirm_delivery::data_to_sign(vars.int("item_id")?, vars.int("sale")?)
+            }
+            "open-dispute" => open_dispute::data_to_sign(vars.int("item_id")?, vars.int("sale")?),
+            "resolve" => {
+                resolve::data_to_sign(vars.int("item_id")?, vars.int("refund")?, vars.int("sale")?)
+            }
+            "fill-bid-intent" => bid_intent::data_to_sign(
+                vars.int("item_id")?,
+                vars.int("limit")?,
+                vars.int("expiry")?,
+                &vars.address("payment_token")?,
+            ),
+            "transfer" => signed_transfer::data_to_sign(
+                vars.b256("to")?,
+                vars.int("amount")?,
+                vars.int("nonce")?,
+            ),
+            action => bail!("Unknown action {}", action),
+        })
+    }
+
+    /// Describes what signing the envelope agrees to, one public variable of
+    /// the signed predicate per line.
+    pub fn describe(&self) -> anyhow::Result<String> {
+        let mut description = self.action.clone();
+        if let Some(item_id) = self.item_id {
+            description.push_str(&format!(" on item {}", item_id));
+        }
+        if self.slot.is_none() {
+            description.push_str(", which takes no signature");
+            return Ok(description);
+        }
+        let vars = self.pub_vars()?;
+        for (index, name) in vars.names.iter().enumerate() {
+            let value = pub_var(vars.data, index).map_or(&[][..], Vec::as_slice);
+            description.push_str(&format!("\n  {}: {:?}", name, value));
+        }
+        if self.action == "accept-offer" {
+            description.push_str(&format!(
+                "\n  offer: {} (read from the node when built and not verified here, \
+                 the signature only holds if the escrowed offer is exactly this)",
+                self.accepted_offer()?
+            ));
+        }
+        Ok(description)
+    }
+
+    /// The offer an accept-offer envelope signs for.
+    ///
+    /// The solution pays the owner the offer less any royalty, so an offer
+    /// below that payment cannot be what the solution accepts.
+    fn accepted_offer(&self) -> anyhow::Result<Word> {
+        let Some(offer) = self.to_sign.get(5).copied() else {
+            bail!("The accept-offer envelope does not name the offer");
+        };
+        let Some(slot) = self.slot else {
+            bail!("The {} solution takes no signature", self.action);
+        };
+        // The release that pays the owner comes right after the acceptance.
+        let release = PubVars::of(&self.solution, slot.data + 1, "auction", "Release")?;
+        let paid = release.int("amount")?;
+        if offer <= 0 || offer < paid {
+            bail!(
+                "The envelope names an offer of {} but the solution pays {}",
+                offer,
+                paid
+            );
+        }
+        Ok(offer)
+    }
+
+    /// The public variables of the solution data the signature goes in.
+    fn pub_vars(&self) -> anyhow::Result<PubVars<'_>> {
+        let Some(slot) = self.slot else {
+            bail!("The {} solution takes no signature", self.action);
+        };
+        let (contract, name) = signed_predicate(&self.action)?;
+        PubVars::of(&self.solution, slot.data, contract, name)
+    }
+}
+
+/// The public variables of a solution data, by their names in the ABI.
+struct PubVars<'a> {
+    data: &'a SolutionData,
+    names: Vec<String>,
+}
+
+impl<'a> PubVars<'a> {
+    /// The public variables of the data at `index`, which solves the
+    /// predicate `name` of the generated `contract`.
+    fn of(
+        solution: &'a Solution,
+        index: usize,
+        contract: &str,
+        name: &str,
+    ) -> anyhow::Result<Self> {
+        let Some(data) = solution.data.get(index) else {
+            bail!("Solution has no data {}", index);
+        };
+        let predicate = generated_addresses(contract)?.predicate(name)?.predicate;
+        if data.predicate_to_solve.predicate != predicate {
+            bail!("Solution data {} does not solve {}", index, name);
+        }
+        Ok(PubVars {
+            data,
+            names: predicate_abi(contract, name)?
+                .pub_vars
+                .iter()
+                .map(|var| short_name(&var.name).to_string())
+                .collect(),
+        })
+    }
+
+    /// The words of the public variable called `name`.
+    fn words(&self, name: &str) -> anyhow::Result<&Value> {
+        let index = self.names.iter().position(|var| var == name);
+        match index.and_then(|index| pub_var(self.data, index)) {
+            Some(words) => Ok(words),
+            None => bail!("Solution has no public variable {}", name),
+        }
+    }
+
+    /// Decodes a single word.
+    fn int(&self, name: &str) -> anyhow::Result<Word> {
+        match self.words(name)?.as_slice() {
+            [word] => Ok(*word),
+            words => bail!("Expected {} to be one word, got: {:?}", name, words),
+        }
+    }
+
+    /// Decodes four words.
+    fn b256(&self, name: &str) -> anyhow::Result<[Word; 4]> {
+        match self.words(name)?.as_slice() {
+            [a, b, c, d] => Ok([*a, *b, *c, *d]),
+            words => bail!("Expected {} to be four words, got: {:?}", name, words),
+        }
+    }
+
+    /// Decodes a contract address.
+    fn address(&self, name: &str) -> anyhow::Result<ContentAddress> {
+        Ok(ContentAddress(u8_32_from_word_4(self.b256(name)?)))
+    }
+}
+
+/// The value of the public variable at `index`, which the solution data
+/// keeps under the key `[index]`.
+fn pub_var(data: &SolutionData, index: usize) -> Option<&Value> {
+    data.transient_data
+        .iter()
+        .find(|mutation| mutation.key == [index as Word])
+        .map(|mutation| &mutation.value)
+}
+
+/// The generated contract and the name of the predicate that checks the
+/// signature of `action`.
+fn signed_predicate(action: &str) -> anyhow::Result<(&'static str, &'static str)> {
+    Ok(match action {
+        "make-offer" => ("auction", "MakeOffer"),
+        "accept-offer" => ("auction", "AcceptOffer"),
+        "withdraw-offer" => ("auction", "WithdrawOffer"),
+        "confirm-delivery" => ("auction", "ConfirmDelivery"),
+        "open-dispute" => ("auction", "OpenDispute"),
+        "resolve" => ("auction", "Resolve"),
+        "fill-bid-intent" => ("auction", "FillBidIntent"),
+        "transfer" => ("token", "SignedTransfer"),
+        action => bail!("The {} action takes no signature", action),
+    })
+}
+
+/// The ABI of the predicate `name` of the generated `contract`.
+fn predicate_abi(contract: &str, name: &str) -> anyhow::Result<PredicateABI> {
+    let abi = generated_abi(contract)?;
+    match abi
+        .predicates
+        .into_iter()
+        .find(|predicate| short_name(&predicate.name) == name)
+    {
+        Some(predicate) => Ok(predicate),
+        None => bail!("No predicate named {} in the {} contract", name, contract),
+    }
+}
+
+/// A name from the ABI without the path before it, e.g. `sig` for `::sig`.
+fn short_name(name: &str) -> &str {
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// A stand in for the signature of a solution that is built before it is signed.
+pub fn blank_signature() -> RecoverableSignature {
+    let recovery_id = RecoveryId::from_i32(0).expect("Zero is a valid recovery id");
+    RecoverableSignature::from_compact(&[0; 64], recovery_id)
+        .expect("Compact signature must be 64 bytes")
+}
+
+/// Encodes a signature as hex, with the recovery id as the last byte.
+fn encode_signature(signature: &RecoverableSignature) -> String {
+    let (recovery_id, signature) = signature.serialize_compact();
+    let mut signature = signature.to_vec();
+    signature.push(recovery_id.to_i32() as u8);
+    hex::encode(signature)
+}
+
+/// Decodes a signature encoded by [`encode_signature`].
+fn decode_signature(signature: &str) -> anyhow::Result<RecoverableSignature> {
+    let signature = hex::decode(signature)?;
+    let Some((recovery_id, signature)) = signature.split_last() else {
+        bail!("Envelope signature is empty");
+    };
+    let recovery_id = RecoveryId::from_i32(*recovery_id as i32)?;
+    Ok(RecoverableSignature::from_compact(signature, recovery_id)?)
+}
+
+/// Writes a hashed key as hex.
+mod hex_key {
+    use super::*;
+
+    pub fn serialize<S: serde::Serializer>(key: &[Word; 4], s: S) -> Result<S::Ok, S::Error> {
+        hex::encode(u8_32_from_word_4(*key)).serialize(s)
+    }
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(d: D) -> Result<[Word; 4], D::Error> {
+        let key = hex::decode(String::deserialize(d)?).map_err(serde::de::Error::custom)?;
+        let key: [u8; 32] = key
+            .try_into()
+            .map_err(|_| serde::de::Error::custom("Envelope signer must be 32 bytes"))?;
+        Ok(word_4_from_u8_32(key))
+    }
+}
+
+/// Writes a signature as hex, or `null` before the envelope is signed.
+mod hex_signature {
+    use super::*;
+
+    pub fn serialize<S: serde::Serializer>(
+        signature: &Option<RecoverableSignature>,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        signature.as_ref().map(encode_signature).serialize(s)
+    }
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        d: D,
+    ) -> Result<Option<RecoverableSignature>, D::Error> {
+        match Option::<String>::deserialize(d)? {
+            Some(signature) => decode_signature(&signature)
+                .map(Some)
+                .map_err(serde::de::Error::custom),
+            None => Ok(None),
+        }
+    }
+}
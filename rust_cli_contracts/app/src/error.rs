@@ -0,0 +1,83 @@
+//! # Error
+//! Contains the errors raised when building solutions and reading state.
+//!
+//! Solution builders return `Result<_, AuctionError>`, so callers match on
+//! the variant rather than on messages.
+use essential_types::Word;
+
+use crate::validate::Rule;
+
+/// Why a solution could not be built or state could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuctionError {
+    /// The account does not hold enough to cover the amount.
+    InsufficientBalance {
+        /// What the account holds.
+        available: Word,
+        /// What the action needs.
+        required: Word,
+    },
+    /// An amount does not fit in a word.
+    Overflow,
+    /// A bid or price is below what the item currently costs.
+    PriceBelowCost {
+        /// The price offered.
+        price: Word,
+        /// The current cost of the item.
+        cost: Word,
+    },
+    /// The auction of the item has already ended.
+    AuctionEnded(Word),
+    /// The key acting on the item does not own it.
+    NotOwner(Word),
+    /// State read from the node is missing or not in the expected shape.
+    MalformedState(String),
+    /// The node could not be reached or returned an error.
+    Node(String),
+    /// The solution would break a constraint of the predicate.
+    Violation(Rule),
+    /// The action does not apply to the item in its current state.
+    Rejected(String),
+}
+
+impl AuctionError {
+    /// Wraps an error from the node client.
+    pub fn node(err: impl std::fmt::Display) -> Self {
+        AuctionError::Node(err.to_string())
+    }
+}
+
+impl std::fmt::Display for AuctionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuctionError::InsufficientBalance {
+                available,
+                required,
+            } => write!(
+                f,
+                "Insufficient balance: {} available, {} required",
+                available, required
+            ),
+            AuctionError::Overflow => write!(f, "Amount overflows"),
+            AuctionError::PriceBelowCost { price, cost } => {
+                write!(f, "Price {} is below the cost of {}", price, cost)
+            }
+            AuctionError::AuctionEnded(item_id) => {
+                write!(f, "Auction of item {} has ended", item_id)
+            }
+            AuctionError::NotOwner(item_id) => write!(f, "Not the owner of item {}", item_id),
+            AuctionError::MalformedState(reason) => write!(f, "Malformed state: {}", reason),
+            AuctionError::Node(reason) => write!(f, "Node error: {}", reason),
+            AuctionError::Violation(rule) => write!(f, "Violates constraint: {}", rule),
+            AuctionError::Rejected(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::error::Error for AuctionError {}
+
+impl From<Rule> for AuctionError {
+    fn from(rule: Rule) -> Self {
+        AuctionError::Violation(rule)
+    }
+}
@@ -0,0 +1,112 @@
+//! # Fractionalize
+//! Contains functionality for locking an item and minting its shares to the owner.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    address_words, deployed_at, escrow_key, item_owner, offer, signature_words, validate,
+    AuctionError, Query,
+};
+
+/// Contains all necessary information to build a fractionalize solution.
+pub struct BuildSolution {
+    /// The item being split.
+    pub item_id: Word,
+    /// The hashed key of the owner.
+    pub hashed_key: [Word; 4],
+    /// The number of shares to mint.
+    pub supply: Word,
+    /// The price anyone can pay to buy the whole item.
+    pub reserve: Word,
+    /// The token contract the buyout is paid in.
+    pub payment_token: ContentAddress,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The current owner of the item.
+    pub current_owner: Query,
+    /// The owner's current nonce.
+    pub current_nonce: Query,
+    /// The owner's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The most shares an item can be split into.
+///
+/// The contract bounds the supply so redemptions can't overflow.
+pub const MAX_SUPPLY: Word = 1 << 31;
+
+/// The words the owner signs to split an item.
+///
+/// The nonce is the owner's current nonce.
+pub fn data_to_sign(
+    item_id: Word,
+    supply: Word,
+    reserve: Word,
+    payment_token: &ContentAddress,
+    nonce: Word,
+) -> Vec<Word> {
+    let mut words = vec![item_id, supply, reserve];
+    words.extend(address_words(payment_token));
+    words.extend([nonce, FRACTIONALIZE]);
+    words
+}
+
+/// Builds a fractionalize solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        supply,
+        reserve,
+        payment_token,
+        block_state,
+        current_owner,
+        current_nonce,
+        signature,
+        contract,
+    } = build;
+
+    validate::fractionalize(item_owner(current_owner)?, hashed_key, supply, reserve)?;
+    let nonce = offer(current_nonce)?;
+    let next_nonce = nonce.checked_add(1).ok_or(AuctionError::Overflow)?;
+
+    let payment_token = address_words(&payment_token);
+    let pub_vars = super::auction::Fractionalize::PubVars {
+        item_id,
+        key: hashed_key,
+        supply,
+        reserve,
+        payment_token,
+        block_state: address_words(&block_state),
+        nonce,
+    };
+    let vars = super::auction::Fractionalize::Vars {
+        sig: signature_words(&signature),
+    };
+    // The item is held by the contract until it is bought out.
+    let mutations = super::auction::storage::mutations()
+        .item_owner(|map| map.entry(item_id, escrow_key(&contract)))
+        .payment_token(|map| map.entry(item_id, payment_token))
+        .fractionalized(|map| map.entry(item_id, true))
+        .share_supply(|map| map.entry(item_id, supply))
+        .share_balances(|map| map.entry(item_id, |map| map.entry(hashed_key, supply)))
+        .buyout_reserve(|map| map.entry(item_id, reserve))
+        .nonces(|map| map.entry(hashed_key, next_nonce));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::Fractionalize::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
+
+/// Tags a signature as a split.
+const FRACTIONALIZE: Word = 8;
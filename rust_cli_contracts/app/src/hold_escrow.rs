@@ -0,0 +1,63 @@
+//! # Hold Escrow
+//! Contains functionality for holding the auction contract's escrow in a payment token.
+//!
+//! The auction contract takes no payments into escrow in a token until it
+//! holds its balance there, after which only its predicates can spend it.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, PredicateAddress, Word,
+};
+
+use crate::{address_words, deployed_at, escrow_key, AuctionError};
+
+/// Contains all necessary information to build a hold escrow solution.
+pub struct BuildSolution {
+    /// The token contract the escrow is held in.
+    pub payment_token: ContentAddress,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a hold escrow solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        payment_token,
+        contract,
+    } = build;
+
+    let pub_vars = super::auction::HoldEscrow::PubVars {
+        payment_token: address_words(&payment_token),
+    };
+    let vars = super::auction::HoldEscrow::Vars {
+        hold_pathway: HOLD_PATHWAY,
+    };
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::HoldEscrow::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: Default::default(),
+    };
+
+    // The token records the escrow as held, authorized by the hold escrow predicate.
+    let pub_vars = super::token::Hold::PubVars {
+        key: escrow_key(&contract),
+        authority: 0,
+    };
+    let mutations =
+        super::token::storage::mutations().held(|map| map.entry(escrow_key(&contract), true));
+    let hold = SolutionData {
+        predicate_to_solve: PredicateAddress {
+            contract: payment_token,
+            predicate: super::token::Hold::ADDRESS.predicate,
+        },
+        decision_variables: Default::default(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution, hold],
+    })
+}
+
+/// The index of the token's hold within a hold escrow solution.
+const HOLD_PATHWAY: Word = 1;
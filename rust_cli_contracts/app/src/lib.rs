@@ -4,7 +4,25 @@
 //! Taken contract front end implementation
 
 use anyhow::bail;
-use essential_types::{Key, Value, Word};
+pub use action::SolutionBuilder;
+pub use batch::SolutionBatch;
+pub use client::AuctionClient;
+use dry_run::StateSource;
+pub use error::AuctionError;
+use essential_app_utils::compile::compile_pint_project;
+use essential_rest_client::node_client::EssentialNodeClient;
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    contract::Contract,
+    convert::{u8_32_from_word_4, word_4_from_u8_32},
+    ContentAddress, Key, PredicateAddress, Value, Word,
+};
+use pint_abi::types::ContractABI;
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
 /// Module containing the token contract ABI.
 #[allow(missing_docs)]
@@ -15,15 +33,387 @@ pub mod token {
     }
 }
 
+/// Module containing the auction contract ABI.
+#[allow(missing_docs)]
+pub mod auction {
+    pint_abi::gen_from_file! {
+        abi: "../pint/auction/out/debug/auction-abi.json",
+        contract:  "../pint/auction/out/debug/auction.json",
+    }
+}
+
+/// Module containing the block state contract ABI.
+#[allow(missing_docs)]
+pub mod block_state {
+    pint_abi::gen_from_file! {
+        abi: "../pint/block_state/out/debug/block_state-abi.json",
+        contract:  "../pint/block_state/out/debug/block_state.json",
+    }
+}
+
+/// Returns early with [`AuctionError::MalformedState`], like `bail!`.
+macro_rules! malformed {
+    ($($arg:tt)*) => {
+        return Err($crate::AuctionError::MalformedState(format!($($arg)*)))
+    };
+}
+
+/// Returns early with [`AuctionError::Rejected`], like `bail!`.
+macro_rules! rejected {
+    ($($arg:tt)*) => {
+        return Err($crate::AuctionError::Rejected(format!($($arg)*)))
+    };
+}
+
 pub mod mint;
 pub mod transfer;
 pub mod place_bid;
 pub mod step_auction;
 pub mod create_auction;
+pub mod make_offer;
+pub mod accept_offer;
+pub mod withdraw_offer;
+pub mod list;
+pub mod delist;
+pub mod buy;
+pub mod hold_escrow;
+pub mod release;
+pub mod create_request;
+pub mod submit_ask;
+pub mod settle_request;
+pub mod refund_request;
+pub mod claim_proceeds;
+pub mod create_ascending_auction;
+pub mod commit_bid;
+pub mod proxy_bid;
+pub mod settle_ascending;
+pub mod cancel_ascending;
+pub mod claim_refund;
+pub mod create_raffle;
+pub mod enter_raffle;
+pub mod reveal_raffle;
+pub mod draw_raffle;
+pub mod close_raffle;
+pub mod refund_ticket;
+pub mod fractionalize;
+pub mod transfer_shares;
+pub mod buyout;
+pub mod redeem_shares;
+pub mod offer_rental;
+pub mod rent;
+pub mod set_arbiter;
+pub mod confirm_delivery;
+pub mod open_dispute;
+pub mod resolve;
+pub mod create_collection;
+pub mod add_collection_item;
+pub mod allow_seller;
+pub mod claim_royalties;
+pub mod error;
+pub mod set_curator_admin;
+pub mod set_curator;
+pub mod review_listing;
+pub mod release_listing;
+pub mod bid_intent;
+pub mod validate;
+pub mod solver;
+pub mod dry_run;
+pub mod client;
+pub mod action;
+pub mod batch;
+pub mod envelope;
+pub mod signed_transfer;
 
 /// Represents a query result, which may or may not contain a value.
+#[derive(Clone)]
 pub struct Query(pub Option<Value>);
 
+impl Query {
+    /// Decodes a single word, failing if the state is unset.
+    pub fn as_int(&self) -> Result<Word, AuctionError> {
+        expect_set(self.as_opt_int()?)
+    }
+
+    /// Decodes a single word, or `None` if the state is unset.
+    pub fn as_opt_int(&self) -> Result<Option<Word>, AuctionError> {
+        match self.words() {
+            None => Ok(None),
+            Some([word]) => Ok(Some(*word)),
+            Some(words) => malformed!("Expected single word, got: {:?}", words),
+        }
+    }
+
+    /// Decodes a bool, failing if the state is unset.
+    pub fn as_bool(&self) -> Result<bool, AuctionError> {
+        expect_set(self.as_opt_bool()?)
+    }
+
+    /// Decodes a bool, or `None` if the state is unset.
+    pub fn as_opt_bool(&self) -> Result<Option<bool>, AuctionError> {
+        match self.as_opt_int()? {
+            None => Ok(None),
+            Some(0) => Ok(Some(false)),
+            Some(1) => Ok(Some(true)),
+            Some(word) => malformed!("Expected a bool, got: {}", word),
+        }
+    }
+
+    /// Decodes a `b256` such as a hashed key, failing if the state is unset.
+    pub fn as_b256(&self) -> Result<[Word; 4], AuctionError> {
+        expect_set(self.as_opt_b256()?)
+    }
+
+    /// Decodes a `b256` such as a hashed key, or `None` if the state is unset.
+    pub fn as_opt_b256(&self) -> Result<Option<[Word; 4]>, AuctionError> {
+        match self.words() {
+            None => Ok(None),
+            Some([a, b, c, d]) => Ok(Some([*a, *b, *c, *d])),
+            Some(words) => malformed!("Expected four words, got: {:?}", words),
+        }
+    }
+
+    /// The words of the value, or `None` if the state is unset.
+    ///
+    /// The node returns an empty value for deleted state, which is unset too.
+    fn words(&self) -> Option<&[Word]> {
+        self.0.as_deref().filter(|words| !words.is_empty())
+    }
+}
+
+/// Fails with [`AuctionError::MalformedState`] if a decoded value is unset.
+fn expect_set<T>(value: Option<T>) -> Result<T, AuctionError> {
+    match value {
+        Some(value) => Ok(value),
+        None => malformed!("Expected a value, but the state is unset"),
+    }
+}
+
+/// The addresses of a compiled contract and its predicates.
+pub struct ContractAddresses {
+    /// The address of the contract.
+    pub contract: ContentAddress,
+    /// The address of each predicate keyed by predicate name, e.g. `PlaceBid`.
+    pub predicates: BTreeMap<String, PredicateAddress>,
+    /// The compiled contract, for checking solutions against it.
+    pub compiled: Contract,
+}
+
+impl ContractAddresses {
+    /// Moves the contract and its predicates to another address.
+    ///
+    /// Predicates are addressed by their content, so only the contract part
+    /// of each address changes.
+    pub fn at(mut self, contract: ContentAddress) -> Self {
+        for address in self.predicates.values_mut() {
+            address.contract = contract.clone();
+        }
+        self.contract = contract;
+        self
+    }
+
+    /// Looks up the address of a predicate by name.
+    pub fn predicate(&self, name: &str) -> anyhow::Result<PredicateAddress> {
+        match self.predicates.get(name) {
+            Some(address) => Ok(address.clone()),
+            None => bail!("No predicate named {} in contract", name),
+        }
+    }
+}
+
+/// Compiles a pint project and returns the address of every predicate by name.
+pub async fn compile_addresses(pint_directory: PathBuf) -> anyhow::Result<ContractAddresses> {
+    compile_pint_project(pint_directory.clone()).await?;
+    let contract = read_contract(&pint_directory)?;
+    let abi = read_abi(&pint_directory)?;
+    addresses(&contract, &abi)
+}
+
+/// Returns the address of every predicate of a pint project without compiling it.
+///
+/// The addresses come from the output of an earlier `pint build`. A project
+/// without build output is an error; use [`generated_addresses`] to act on
+/// the contracts embedded in this crate instead.
+pub fn read_addresses(pint_directory: &Path) -> anyhow::Result<ContractAddresses> {
+    let out = pint_directory.join("out").join("debug");
+    if !out.is_dir() {
+        bail!("{} has not been built", pint_directory.display());
+    }
+    let contract = read_contract(pint_directory)?;
+    let abi = read_abi(pint_directory)?;
+    addresses(&contract, &abi)
+}
+
+/// Returns the address of every predicate of a contract this crate was
+/// generated from, so the `pint` toolchain and sources are never needed.
+///
+/// `name` is one of `token`, `auction` or `block_state`.
+pub fn generated_addresses(name: &str) -> anyhow::Result<ContractAddresses> {
+    match GENERATED.iter().find(|generated| generated.0 == name) {
+        Some((_, contract, abi)) => addresses(
+            &serde_json::from_str(contract)?,
+            &serde_json::from_str(abi)?,
+        ),
+        None => bail!("{} is not a generated contract", name),
+    }
+}
+
+/// Returns the address of every predicate of a compiled contract file.
+///
+/// The predicate names are checked against the `-abi.json` that `pint build`
+/// writes next to it.
+pub fn addresses_from_json(contract_json: &Path) -> anyhow::Result<ContractAddresses> {
+    let contract: CompiledContract = serde_json::from_slice(&std::fs::read(contract_json)?)?;
+    let abi = contract_json.with_file_name(format!(
+        "{}-abi.json",
+        contract_json
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default()
+    ));
+    let abi = serde_json::from_slice(&std::fs::read(abi)?)?;
+    addresses(&contract, &abi)
+}
+
+/// A contract as `pint build` writes it, with the name of each predicate.
+#[derive(Deserialize)]
+struct CompiledContract {
+    names: Vec<String>,
+    #[serde(flatten)]
+    contract: Contract,
+}
+
+/// Names each predicate of a compiled contract and checks the names against the ABI.
+fn addresses(compiled: &CompiledContract, abi: &ContractABI) -> anyhow::Result<ContractAddresses> {
+    let CompiledContract { names, contract } = compiled;
+    if names.len() != contract.predicates.len() {
+        bail!(
+            "Contract names {} predicates but has {}",
+            names.len(),
+            contract.predicates.len()
+        );
+    }
+    let contract_address = essential_hash::contract_addr::from_contract(contract);
+    let predicates: BTreeMap<_, _> = names
+        .iter()
+        .zip(&contract.predicates)
+        .map(|(name, predicate)| {
+            let address = PredicateAddress {
+                contract: contract_address.clone(),
+                predicate: essential_hash::content_addr(predicate),
+            };
+            (name.trim_start_matches("::").to_string(), address)
+        })
+        .collect();
+    if predicates.len() != abi.predicates.len() {
+        bail!(
+            "ABI has {} predicates but contract has {}",
+            abi.predicates.len(),
+            predicates.len()
+        );
+    }
+    for predicate in &abi.predicates {
+        let name = predicate.name.trim_start_matches("::");
+        if !predicates.contains_key(name) {
+            bail!("ABI predicate {} is not in the contract", name);
+        }
+    }
+    Ok(ContractAddresses {
+        contract: contract_address,
+        predicates,
+        compiled: contract.clone(),
+    })
+}
+
+/// The name, contract and ABI of each contract this crate was generated from.
+///
+/// These are the same files the [`token`] and [`auction`] modules are
+/// generated from, embedded at build time.
+const GENERATED: [(&str, &str, &str); 3] = [
+    (
+        "token",
+        include_str!("../../pint/token/out/debug/token.json"),
+        include_str!("../../pint/token/out/debug/token-abi.json"),
+    ),
+    (
+        "auction",
+        include_str!("../../pint/auction/out/debug/auction.json"),
+        include_str!("../../pint/auction/out/debug/auction-abi.json"),
+    ),
+    (
+        "block_state",
+        include_str!("../../pint/block_state/out/debug/block_state.json"),
+        include_str!("../../pint/block_state/out/debug/block_state-abi.json"),
+    ),
+];
+
+/// The contracts the [`token`] and [`auction`] modules were generated from.
+pub fn generated_contracts() -> Result<Vec<Contract>, AuctionError> {
+    GENERATED
+        .iter()
+        .map(|(_, contract, _)| {
+            serde_json::from_str(contract)
+                .map_err(|err| AuctionError::MalformedState(format!("Bad contract: {}", err)))
+        })
+        .collect()
+}
+
+/// The ABI of a contract this crate was generated from, by name.
+pub(crate) fn generated_abi(name: &str) -> anyhow::Result<ContractABI> {
+    match GENERATED.iter().find(|generated| generated.0 == name) {
+        Some((_, _, abi)) => Ok(serde_json::from_str(abi)?),
+        None => bail!("{} is not a generated contract", name),
+    }
+}
+
+/// Reads the contract that `pint build` writes for the package in `pint_directory`.
+fn read_contract(pint_directory: &Path) -> anyhow::Result<CompiledContract> {
+    let path = build_output(pint_directory, ".json")?;
+    match std::fs::read(&path) {
+        Ok(contract) => Ok(serde_json::from_slice(&contract)?),
+        Err(err) => bail!("No contract found at {}: {}", path.display(), err),
+    }
+}
+
+/// Reads the ABI that `pint build` writes next to the compiled contract.
+fn read_abi(pint_directory: &Path) -> anyhow::Result<ContractABI> {
+    let path = build_output(pint_directory, "-abi.json")?;
+    match std::fs::read(&path) {
+        Ok(abi) => Ok(serde_json::from_slice(&abi)?),
+        Err(err) => bail!("No ABI found at {}: {}", path.display(), err),
+    }
+}
+
+/// The path of a file `pint build` writes for the package in `pint_directory`.
+///
+/// Build output is named after the package, so a stale file left by a
+/// renamed package is never read in its place.
+fn build_output(pint_directory: &Path, suffix: &str) -> anyhow::Result<PathBuf> {
+    let name = package_name(pint_directory)?;
+    Ok(pint_directory
+        .join("out")
+        .join("debug")
+        .join(format!("{}{}", name, suffix)))
+}
+
+/// Reads the package name from the `pint.toml` in `pint_directory`.
+fn package_name(pint_directory: &Path) -> anyhow::Result<String> {
+    let manifest = pint_directory.join("pint.toml");
+    let mut in_package = false;
+    for line in std::fs::read_to_string(&manifest)?.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if in_package && key.trim() == "name" {
+            return Ok(value.trim().trim_matches('"').to_string());
+        }
+    }
+    bail!("No package name in {}", manifest.display())
+}
+
 /// Generates the key for querying an account's balance.
 pub fn balance_key(hashed_key: [Word; 4]) -> Key {
     let balance: Vec<_> = token::storage::keys::keys()
@@ -33,31 +423,38 @@ pub fn balance_key(hashed_key: [Word; 4]) -> Key {
 }
 
 
+/// Generates the key for the transfer nonce of an account in the token contract.
+pub fn transfer_nonce_key(hashed_key: [Word; 4]) -> Key {
+    let nonce: Vec<_> = token::storage::keys::keys()
+        .nonces(|e| e.entry(hashed_key))
+        .into();
+    nonce.into_iter().next().expect("Must be a key")
+}
+
 /// Extracts the balance from a Query result.
-pub fn balance(balance: Query) -> anyhow::Result<Word> {
-    let r = match balance.0 {
-        Some(balance) => match &balance[..] {
-            [] => 0,
-            [balance] => *balance,
-            _ => bail!("Expected single word, got: {:?}", balance),
-        },
-        None => 0,
-    };
-    Ok(r)
+pub fn balance(balance: Query) -> Result<Word, AuctionError> {
+    Ok(balance.as_opt_int()?.unwrap_or_default())
 }
 
 /// get item owner key
 pub fn get_item_owner_key(item_id: Word) -> Key {
-    let owner: Vec<_> = token::storage::keys::keys()
+    let owner: Vec<_> = auction::storage::keys::keys()
         .item_owner(|e| e.entry(item_id))
         .into();
     owner.into_iter().next().expect("Must be a key")
 }
 
+/// Extracts the item owner's hashed key from a Query result.
+pub fn item_owner(owner: Query) -> Result<[Word; 4], AuctionError> {
+    match owner.as_opt_b256()? {
+        Some(owner) => Ok(owner),
+        None => malformed!("Item has no owner"),
+    }
+}
 
 /// Generates the key for querying an account's balance.
 pub fn cost_key(item_id: Word) -> Key {
-    let cost: Vec<_> = token::storage::keys::keys()
+    let cost: Vec<_> = auction::storage::keys::keys()
         .cost(|e| e.entry(item_id))
         .into();
     cost.into_iter().next().expect("Must be a key")
@@ -65,22 +462,864 @@ pub fn cost_key(item_id: Word) -> Key {
 
 /// Generates the key for auction status.
 pub fn auction_status_key(item_id: Word) -> Key {
-    let status: Vec<_> = token::storage::keys::keys()
+    let status: Vec<_> = auction::storage::keys::keys()
         .auction_ended(|e| e.entry(item_id))
         .into();
     status.into_iter().next().expect("Must be a key")
 }
 
+/// Generates the key for the token contract an item is priced in.
+pub fn payment_token_key(item_id: Word) -> Key {
+    let token: Vec<_> = auction::storage::keys::keys()
+        .payment_token(|e| e.entry(item_id))
+        .into();
+    token.into_iter().next().expect("Must be a key")
+}
 
-/// Extracts the balance from a Query result.
-pub fn cost(cost: Query) -> anyhow::Result<Word> {
-    let r = match cost.0 {
-        Some(cost) => match &cost[..] {
-            [] => 0,
-            [cost] => *cost,
-            _ => bail!("Expected single word, got: {:?}", cost),
-        },
-        None => 0,
+/// Extracts the payment token contract address from a Query result.
+pub fn payment_token(token: Query) -> Result<ContentAddress, AuctionError> {
+    match token.as_opt_b256()? {
+        Some(token) => Ok(ContentAddress(u8_32_from_word_4(token))),
+        None => malformed!("Item has no payment token"),
+    }
+}
+
+/// Generates the key for whether an item is listed at a fixed price.
+pub fn fixed_price_key(item_id: Word) -> Key {
+    let fixed_price: Vec<_> = auction::storage::keys::keys()
+        .fixed_price(|e| e.entry(item_id))
+        .into();
+    fixed_price.into_iter().next().expect("Must be a key")
+}
+
+/// Extracts whether an item is listed at a fixed price from a Query result.
+pub fn fixed_price(fixed_price: Query) -> Result<bool, AuctionError> {
+    Ok(fixed_price.as_opt_bool()?.unwrap_or_default())
+}
+
+/// Generates the key for the buyer of a request.
+pub fn request_buyer_key(item_id: Word) -> Key {
+    let buyer: Vec<_> = auction::storage::keys::keys()
+        .request_buyer(|e| e.entry(item_id))
+        .into();
+    buyer.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the escrowed budget of a request.
+pub fn request_budget_key(item_id: Word) -> Key {
+    let budget: Vec<_> = auction::storage::keys::keys()
+        .request_budget(|e| e.entry(item_id))
+        .into();
+    budget.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the token contract a request is paid in.
+pub fn request_token_key(item_id: Word) -> Key {
+    let token: Vec<_> = auction::storage::keys::keys()
+        .request_token(|e| e.entry(item_id))
+        .into();
+    token.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the block number at which asks on a request close.
+pub fn request_deadline_key(item_id: Word) -> Key {
+    let deadline: Vec<_> = auction::storage::keys::keys()
+        .request_deadline(|e| e.entry(item_id))
+        .into();
+    deadline.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the lowest ask on a request.
+pub fn best_ask_key(item_id: Word) -> Key {
+    let ask: Vec<_> = auction::storage::keys::keys()
+        .best_ask(|e| e.entry(item_id))
+        .into();
+    ask.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the provider of the lowest ask on a request.
+pub fn best_provider_key(item_id: Word) -> Key {
+    let provider: Vec<_> = auction::storage::keys::keys()
+        .best_provider(|e| e.entry(item_id))
+        .into();
+    provider.into_iter().next().expect("Must be a key")
+}
+
+/// Extracts the buyer's hashed key of a request from a Query result.
+pub fn request_buyer(buyer: Query) -> Result<[Word; 4], AuctionError> {
+    match buyer.as_opt_b256()? {
+        Some(buyer) => Ok(buyer),
+        None => malformed!("No request for item"),
+    }
+}
+
+/// Extracts the provider of the lowest ask from a Query result.
+///
+/// Returns `None` if nobody has asked yet.
+pub fn best_provider(provider: Query) -> Result<Option<[Word; 4]>, AuctionError> {
+    provider.as_opt_b256()
+}
+
+/// Generates the key for the unclaimed proceeds of an item sold in a bundle.
+pub fn proceeds_key(item_id: Word) -> Key {
+    let proceeds: Vec<_> = auction::storage::keys::keys()
+        .proceeds(|e| e.entry(item_id))
+        .into();
+    proceeds.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the seller of an item sold in a bundle.
+pub fn proceeds_seller_key(item_id: Word) -> Key {
+    let seller: Vec<_> = auction::storage::keys::keys()
+        .proceeds_seller(|e| e.entry(item_id))
+        .into();
+    seller.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for whether an item is in an ascending auction.
+pub fn ascending_key(item_id: Word) -> Key {
+    let ascending: Vec<_> = auction::storage::keys::keys()
+        .ascending(|e| e.entry(item_id))
+        .into();
+    ascending.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the minimum raise of an ascending auction.
+pub fn bid_increment_key(item_id: Word) -> Key {
+    let increment: Vec<_> = auction::storage::keys::keys()
+        .bid_increment(|e| e.entry(item_id))
+        .into();
+    increment.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the block number an ascending auction closes at.
+pub fn auction_deadline_key(item_id: Word) -> Key {
+    let deadline: Vec<_> = auction::storage::keys::keys()
+        .auction_deadline(|e| e.entry(item_id))
+        .into();
+    deadline.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the leading bidder of an ascending auction.
+pub fn leader_key(item_id: Word) -> Key {
+    let leader: Vec<_> = auction::storage::keys::keys()
+        .leader(|e| e.entry(item_id))
+        .into();
+    leader.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the escrowed maximum of the leading bidder.
+pub fn leader_escrow_key(item_id: Word) -> Key {
+    let escrow: Vec<_> = auction::storage::keys::keys()
+        .leader_escrow(|e| e.entry(item_id))
+        .into();
+    escrow.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for a bidder's reclaimable escrow on an item.
+pub fn refund_key(item_id: Word, bidder: [Word; 4]) -> Key {
+    let refund: Vec<_> = auction::storage::keys::keys()
+        .refunds(|e| e.entry(item_id, |e| e.entry(bidder)))
+        .into();
+    refund.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the block number reveals of an ascending auction close at.
+pub fn bid_reveal_deadline_key(item_id: Word) -> Key {
+    let deadline: Vec<_> = auction::storage::keys::keys()
+        .bid_reveal_deadline(|e| e.entry(item_id))
+        .into();
+    deadline.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for a bidder's commitment to their sealed maximum.
+pub fn bid_commitment_key(item_id: Word, bidder: [Word; 4]) -> Key {
+    let commitment: Vec<_> = auction::storage::keys::keys()
+        .bid_commitments(|e| e.entry(item_id, |e| e.entry(bidder)))
+        .into();
+    commitment.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the deposit covering a bidder's sealed maximum.
+pub fn bid_deposit_key(item_id: Word, bidder: [Word; 4]) -> Key {
+    let deposit: Vec<_> = auction::storage::keys::keys()
+        .bid_deposits(|e| e.entry(item_id, |e| e.entry(bidder)))
+        .into();
+    deposit.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for whether a bidder has revealed their sealed maximum.
+pub fn bid_revealed_key(item_id: Word, bidder: [Word; 4]) -> Key {
+    let revealed: Vec<_> = auction::storage::keys::keys()
+        .bid_revealed(|e| e.entry(item_id, |e| e.entry(bidder)))
+        .into();
+    revealed.into_iter().next().expect("Must be a key")
+}
+
+/// Extracts the leading bidder of an ascending auction from a Query result.
+pub fn leader(leader: Query) -> Result<Option<[Word; 4]>, AuctionError> {
+    leader.as_opt_b256()
+}
+
+/// Generates the key for whether an item is being raffled.
+pub fn raffle_key(item_id: Word) -> Key {
+    let raffle: Vec<_> = auction::storage::keys::keys()
+        .raffle(|e| e.entry(item_id))
+        .into();
+    raffle.into_iter().next().expect("Must be a key")
+}
+
+/// Extracts a flag such as `ascending` or `raffle` from a Query result.
+pub fn flag(flag: Query) -> Result<bool, AuctionError> {
+    Ok(flag.as_opt_bool()?.unwrap_or_default())
+}
+
+/// Generates the key for the ticket price of a raffle.
+pub fn ticket_price_key(item_id: Word) -> Key {
+    let price: Vec<_> = auction::storage::keys::keys()
+        .ticket_price(|e| e.entry(item_id))
+        .into();
+    price.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the number of entries in a raffle.
+pub fn raffle_entry_count_key(item_id: Word) -> Key {
+    let count: Vec<_> = auction::storage::keys::keys()
+        .raffle_entry_count(|e| e.entry(item_id))
+        .into();
+    count.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the entrant with the given entry number.
+pub fn raffle_entrant_key(item_id: Word, index: Word) -> Key {
+    let entrant: Vec<_> = auction::storage::keys::keys()
+        .raffle_entrants(|e| e.entry(item_id, |e| e.entry(index)))
+        .into();
+    entrant.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the sum of the revealed secrets of a raffle.
+pub fn raffle_seed_key(item_id: Word) -> Key {
+    let seed: Vec<_> = auction::storage::keys::keys()
+        .raffle_seed(|e| e.entry(item_id))
+        .into();
+    seed.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the winner of a raffle.
+pub fn raffle_winner_key(item_id: Word) -> Key {
+    let winner: Vec<_> = auction::storage::keys::keys()
+        .raffle_winner(|e| e.entry(item_id))
+        .into();
+    winner.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the token contract tickets of a raffle are paid in.
+pub fn ticket_token_key(item_id: Word) -> Key {
+    let token: Vec<_> = auction::storage::keys::keys()
+        .ticket_token(|e| e.entry(item_id))
+        .into();
+    token.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for whether the seller or an entrant has revealed their secret.
+pub fn raffle_revealed_key(item_id: Word, revealer: [Word; 4]) -> Key {
+    let revealed: Vec<_> = auction::storage::keys::keys()
+        .raffle_revealed(|e| e.entry(item_id, |e| e.entry(revealer)))
+        .into();
+    revealed.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for whether an entrant holds an unrefunded ticket.
+pub fn ticket_held_key(item_id: Word, entrant: [Word; 4]) -> Key {
+    let held: Vec<_> = auction::storage::keys::keys()
+        .ticket_held(|e| e.entry(item_id, |e| e.entry(entrant)))
+        .into();
+    held.into_iter().next().expect("Must be a key")
+}
+
+/// Extracts whether an entrant holds an unrefunded ticket from a Query result.
+pub fn ticket_held(held: Query) -> Result<bool, AuctionError> {
+    Ok(held.as_opt_bool()?.unwrap_or_default())
+}
+
+/// Extracts the hashed key of a raffle entrant from a Query result.
+pub fn raffle_entrant(entrant: Query) -> Result<[Word; 4], AuctionError> {
+    match entrant.as_opt_b256()? {
+        Some(entrant) => Ok(entrant),
+        None => malformed!("Raffle has no such entry"),
+    }
+}
+
+/// Generates the key for an account's shares in a fractionalized item.
+pub fn share_balance_key(item_id: Word, hashed_key: [Word; 4]) -> Key {
+    let shares: Vec<_> = auction::storage::keys::keys()
+        .share_balances(|e| e.entry(item_id, |e| e.entry(hashed_key)))
+        .into();
+    shares.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the number of share transfers a key has signed.
+pub fn share_nonce_key(hashed_key: [Word; 4]) -> Key {
+    let nonce: Vec<_> = auction::storage::keys::keys()
+        .share_nonces(|e| e.entry(hashed_key))
+        .into();
+    nonce.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the number of shares an item was split into.
+pub fn share_supply_key(item_id: Word) -> Key {
+    let supply: Vec<_> = auction::storage::keys::keys()
+        .share_supply(|e| e.entry(item_id))
+        .into();
+    supply.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the shares of an item redeemed since its buyout.
+pub fn shares_redeemed_key(item_id: Word) -> Key {
+    let redeemed: Vec<_> = auction::storage::keys::keys()
+        .shares_redeemed(|e| e.entry(item_id))
+        .into();
+    redeemed.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the buyout price of a fractionalized item.
+pub fn buyout_reserve_key(item_id: Word) -> Key {
+    let reserve: Vec<_> = auction::storage::keys::keys()
+        .buyout_reserve(|e| e.entry(item_id))
+        .into();
+    reserve.into_iter().next().expect("Must be a key")
+}
+
+/// Extracts a share balance from a Query result.
+pub fn share_balance(shares: Query) -> Result<Word, AuctionError> {
+    Ok(shares.as_opt_int()?.unwrap_or_default())
+}
+
+/// Generates the key for the price of leasing an item.
+pub fn rental_price_key(item_id: Word) -> Key {
+    let price: Vec<_> = auction::storage::keys::keys()
+        .rental_price(|e| e.entry(item_id))
+        .into();
+    price.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the number of blocks a lease lasts.
+pub fn rental_blocks_key(item_id: Word) -> Key {
+    let blocks: Vec<_> = auction::storage::keys::keys()
+        .rental_blocks(|e| e.entry(item_id))
+        .into();
+    blocks.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the current or last renter of an item.
+pub fn renter_key(item_id: Word) -> Key {
+    let renter: Vec<_> = auction::storage::keys::keys()
+        .renter(|e| e.entry(item_id))
+        .into();
+    renter.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the block number a lease expires at.
+pub fn rental_expiry_key(item_id: Word) -> Key {
+    let expiry: Vec<_> = auction::storage::keys::keys()
+        .rental_expiry(|e| e.entry(item_id))
+        .into();
+    expiry.into_iter().next().expect("Must be a key")
+}
+
+/// The key of the block number in the block state contract.
+pub fn block_number_key() -> Key {
+    let number: Vec<_> = block_state::storage::keys::keys().number().into();
+    number.into_iter().next().expect("Must be a key")
+}
+
+/// Extracts the renter of an item from a Query result.
+pub fn renter(renter: Query) -> Result<Option<[Word; 4]>, AuctionError> {
+    renter.as_opt_b256()
+}
+
+/// The key that may use an item at the given block.
+///
+/// This is the renter while a lease is running and the owner otherwise.
+pub fn user_of(
+    owner: [Word; 4],
+    renter: Option<[Word; 4]>,
+    expiry: Word,
+    block_number: Word,
+) -> [Word; 4] {
+    match renter {
+        Some(renter) if block_number < expiry => renter,
+        _ => owner,
+    }
+}
+
+/// The escrow status of a sale held until delivery is confirmed.
+pub const ESCROW_HELD: Word = 1;
+/// The escrow status of a sale waiting on the arbiter.
+pub const ESCROW_DISPUTED: Word = 2;
+/// The escrow status of a sale that has been released or resolved.
+pub const ESCROW_SETTLED: Word = 3;
+
+/// Generates the key for the arbiter of an item sold in escrow.
+pub fn arbiter_key(item_id: Word) -> Key {
+    let arbiter: Vec<_> = auction::storage::keys::keys()
+        .arbiter(|e| e.entry(item_id))
+        .into();
+    arbiter.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the escrow status of an item's sale.
+pub fn escrow_status_key(item_id: Word) -> Key {
+    let status: Vec<_> = auction::storage::keys::keys()
+        .escrow_status(|e| e.entry(item_id))
+        .into();
+    status.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the amount held in escrow for an item's sale.
+pub fn escrow_amount_key(item_id: Word) -> Key {
+    let amount: Vec<_> = auction::storage::keys::keys()
+        .escrow_amount(|e| e.entry(item_id))
+        .into();
+    amount.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the seller of an item sold in escrow.
+pub fn escrow_seller_key(item_id: Word) -> Key {
+    let seller: Vec<_> = auction::storage::keys::keys()
+        .escrow_seller(|e| e.entry(item_id))
+        .into();
+    seller.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the buyer of an item sold in escrow.
+pub fn escrow_buyer_key(item_id: Word) -> Key {
+    let buyer: Vec<_> = auction::storage::keys::keys()
+        .escrow_buyer(|e| e.entry(item_id))
+        .into();
+    buyer.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the number of an item's sales held in escrow.
+pub fn escrow_sale_key(item_id: Word) -> Key {
+    let sale: Vec<_> = auction::storage::keys::keys()
+        .escrow_sale(|e| e.entry(item_id))
+        .into();
+    sale.into_iter().next().expect("Must be a key")
+}
+
+/// Extracts the arbiter of an item from a Query result.
+pub fn arbiter(arbiter: Query) -> Result<Option<[Word; 4]>, AuctionError> {
+    arbiter.as_opt_b256()
+}
+
+/// Extracts the buyer of an item sold in escrow from a Query result.
+pub fn escrow_buyer(buyer: Query) -> Result<[Word; 4], AuctionError> {
+    match buyer.as_opt_b256()? {
+        Some(buyer) => Ok(buyer),
+        None => malformed!("Item was not sold in escrow"),
+    }
+}
+
+/// Generates the key for the creator of a collection.
+pub fn collection_creator_key(collection: Word) -> Key {
+    let creator: Vec<_> = auction::storage::keys::keys()
+        .collection_creator(|e| e.entry(collection))
+        .into();
+    creator.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the royalty rate of a collection.
+pub fn collection_royalty_key(collection: Word) -> Key {
+    let royalty: Vec<_> = auction::storage::keys::keys()
+        .collection_royalty(|e| e.entry(collection))
+        .into();
+    royalty.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for whether anyone can sell the items of a collection.
+pub fn collection_open_key(collection: Word) -> Key {
+    let open: Vec<_> = auction::storage::keys::keys()
+        .collection_open(|e| e.entry(collection))
+        .into();
+    open.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for whether a seller is allowed in a closed collection.
+pub fn collection_seller_key(collection: Word, seller: [Word; 4]) -> Key {
+    let allowed: Vec<_> = auction::storage::keys::keys()
+        .collection_sellers(|e| e.entry(collection, |e| e.entry(seller)))
+        .into();
+    allowed.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the item registered at an index of a collection.
+pub fn collection_item_key(collection: Word, index: Word) -> Key {
+    let item: Vec<_> = auction::storage::keys::keys()
+        .collection_items(|e| e.entry(collection, |e| e.entry(index)))
+        .into();
+    item.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the number of items in a collection.
+pub fn collection_item_count_key(collection: Word) -> Key {
+    let count: Vec<_> = auction::storage::keys::keys()
+        .collection_item_count(|e| e.entry(collection))
+        .into();
+    count.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the collection an item is registered in.
+pub fn item_collection_key(item_id: Word) -> Key {
+    let collection: Vec<_> = auction::storage::keys::keys()
+        .item_collection(|e| e.entry(item_id))
+        .into();
+    collection.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the royalties owed to a collection in a token.
+pub fn collection_royalties_key(collection: Word, payment_token: &ContentAddress) -> Key {
+    let royalties: Vec<_> = auction::storage::keys::keys()
+        .collection_royalties(|e| e.entry(collection, |e| e.entry(address_words(payment_token))))
+        .into();
+    royalties.into_iter().next().expect("Must be a key")
+}
+
+/// Extracts the collection of an item from a Query result.
+pub fn item_collection(collection: Query) -> Result<Option<Word>, AuctionError> {
+    collection.as_opt_int()
+}
+
+/// Extracts the creator of a collection from a Query result.
+pub fn collection_creator(creator: Query) -> Result<[Word; 4], AuctionError> {
+    match creator.as_opt_b256()? {
+        Some(creator) => Ok(creator),
+        None => malformed!("Collection does not exist"),
+    }
+}
+
+/// Extracts whether a collection is open to all sellers from a Query result.
+pub fn collection_open(open: Query) -> Result<bool, AuctionError> {
+    Ok(open.as_opt_bool()?.unwrap_or_default())
+}
+
+/// Looks up the items registered in a collection, in the order they were added.
+pub async fn collection_items(
+    node: &EssentialNodeClient,
+    contract: &ContentAddress,
+    collection: Word,
+) -> Result<Vec<Word>, AuctionError> {
+    let count = node
+        .query_state(contract.clone(), collection_item_count_key(collection))
+        .await
+        .map_err(AuctionError::node)?;
+    let count = Query(count).as_opt_int()?.unwrap_or_default();
+    let mut items = Vec::with_capacity(count.max(0) as usize);
+    for index in 0..count {
+        let item = node
+            .query_state(contract.clone(), collection_item_key(collection, index))
+            .await
+            .map_err(AuctionError::node)?;
+        items.push(Query(item).as_int()?);
+    }
+    Ok(items)
+}
+
+/// The stored status of an auction waiting on a curator.
+pub const LISTING_PENDING: Word = 1;
+/// The stored status of an approved auction.
+pub const LISTING_APPROVED: Word = 2;
+/// The stored status of a rejected auction.
+pub const LISTING_REJECTED: Word = 3;
+
+/// The hashed key the auction contract is deployed with as its curator admin.
+///
+/// This is a local development key whose secret is public, matching
+/// `CURATOR_ADMIN` in the auction contract.
+pub const CURATOR_ADMIN: [Word; 4] = [
+    0x1AA3_92C7_09CE_742F,
+    0xB65E_CF60_DE6F_096B_u64 as Word,
+    0x40E8_4476_C773_4ECD,
+    0xAD9E_F585_5E08_FB69_u64 as Word,
+];
+
+/// Where an auction stands with the curators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListingStatus {
+    /// Created before curation was turned on, so it never needed approval.
+    Uncurated,
+    /// Waiting on a curator and not yet taking bids.
+    Pending,
+    /// Approved by a curator and taking bids.
+    Approved,
+    /// Rejected by a curator.
+    Rejected,
+}
+
+impl ListingStatus {
+    /// Whether the auction can be bid on.
+    pub fn accepts_bids(&self) -> bool {
+        matches!(self, ListingStatus::Uncurated | ListingStatus::Approved)
+    }
+}
+
+impl std::fmt::Display for ListingStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match self {
+            ListingStatus::Uncurated => "uncurated",
+            ListingStatus::Pending => "pending",
+            ListingStatus::Approved => "approved",
+            ListingStatus::Rejected => "rejected",
+        };
+        write!(f, "{}", status)
+    }
+}
+
+/// Generates the key for the curator admin.
+pub fn curator_admin_key() -> Key {
+    let admin: Vec<_> = auction::storage::keys::keys().curator_admin().into();
+    admin.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for whether a key is a curator.
+pub fn curator_key(curator: [Word; 4]) -> Key {
+    let curator: Vec<_> = auction::storage::keys::keys()
+        .curators(|e| e.entry(curator))
+        .into();
+    curator.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the listing status of an auction.
+pub fn listing_status_key(item_id: Word) -> Key {
+    let status: Vec<_> = auction::storage::keys::keys()
+        .listing_status(|e| e.entry(item_id))
+        .into();
+    status.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the auction created at an index under curation.
+pub fn curated_item_key(index: Word) -> Key {
+    let item: Vec<_> = auction::storage::keys::keys()
+        .curated_items(|e| e.entry(index))
+        .into();
+    item.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the number of auctions created under curation.
+pub fn curated_item_count_key() -> Key {
+    let count: Vec<_> = auction::storage::keys::keys().curated_item_count().into();
+    count.into_iter().next().expect("Must be a key")
+}
+
+/// Extracts the curator admin from a Query result.
+pub fn curator_admin(admin: Query) -> Result<Option<[Word; 4]>, AuctionError> {
+    admin.as_opt_b256()
+}
+
+/// Extracts whether a key is a curator from a Query result.
+pub fn is_curator(curator: Query) -> Result<bool, AuctionError> {
+    Ok(curator.as_opt_bool()?.unwrap_or_default())
+}
+
+/// Extracts the listing status of an auction from a Query result.
+pub fn listing_status(status: Query) -> Result<ListingStatus, AuctionError> {
+    match status.as_opt_int()? {
+        None => Ok(ListingStatus::Uncurated),
+        Some(LISTING_PENDING) => Ok(ListingStatus::Pending),
+        Some(LISTING_APPROVED) => Ok(ListingStatus::Approved),
+        Some(LISTING_REJECTED) => Ok(ListingStatus::Rejected),
+        Some(status) => malformed!("Unknown listing status: {}", status),
+    }
+}
+
+/// Looks up the listings still waiting on a curator, oldest first.
+pub async fn pending_listings(
+    node: &EssentialNodeClient,
+    contract: &ContentAddress,
+) -> Result<Vec<Word>, AuctionError> {
+    let count = node
+        .query_state(contract.clone(), curated_item_count_key())
+        .await
+        .map_err(AuctionError::node)?;
+    let count = Query(count).as_opt_int()?.unwrap_or_default();
+    let mut pending = Vec::new();
+    for index in 0..count {
+        let item = node
+            .query_state(contract.clone(), curated_item_key(index))
+            .await
+            .map_err(AuctionError::node)?;
+        let item_id = Query(item).as_int()?;
+        // A relisted item is curated again under a new slot.
+        if pending.contains(&item_id) {
+            continue;
+        }
+        let status = node
+            .query_state(contract.clone(), listing_status_key(item_id))
+            .await
+            .map_err(AuctionError::node)?;
+        if listing_status(Query(status))? == ListingStatus::Pending {
+            pending.push(item_id);
+        }
+    }
+    Ok(pending)
+}
+
+/// An item in the auction contract, decoded from its state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuctionItem {
+    /// The id of the item.
+    pub id: Word,
+    /// The current price of the item.
+    pub cost: Word,
+    /// The hashed key of the owner.
+    pub owner: [Word; 4],
+    /// Whether the item is still for sale.
+    pub active: bool,
+    /// The token contract the item is priced in.
+    pub payment_token: ContentAddress,
+    /// Whether the item is listed at a fixed price rather than auctioned.
+    pub fixed_price: bool,
+    /// Whether the item is in an ascending auction.
+    pub ascending: bool,
+    /// Whether the item is being raffled.
+    pub raffle: bool,
+    /// Where the item stands with the curators.
+    pub status: ListingStatus,
+}
+
+/// Reads and decodes an item from the auction contract.
+///
+/// Returns `None` if the item has never been listed.
+pub async fn fetch_item(
+    node: &impl StateSource,
+    contract: &ContentAddress,
+    item_id: Word,
+) -> Result<Option<AuctionItem>, AuctionError> {
+    let owner = node.read(contract, &get_item_owner_key(item_id)).await?;
+    if owner.is_none() {
+        return Ok(None);
+    }
+    let cost = node.read(contract, &cost_key(item_id)).await?;
+    let ended = node.read(contract, &auction_status_key(item_id)).await?;
+    let token = node.read(contract, &payment_token_key(item_id)).await?;
+    let fixed = node.read(contract, &fixed_price_key(item_id)).await?;
+    let ascending = node.read(contract, &ascending_key(item_id)).await?;
+    let raffle = node.read(contract, &raffle_key(item_id)).await?;
+    let status = node.read(contract, &listing_status_key(item_id)).await?;
+
+    // A listed item always has these set, so a missing one is a broken item.
+    let required = |value: Option<Value>, field: &str| match value {
+        Some(value) => Ok(Query(Some(value))),
+        None => Err(AuctionError::MalformedState(format!(
+            "Item {} has no {}",
+            item_id, field
+        ))),
     };
-    Ok(r)
+    Ok(Some(AuctionItem {
+        id: item_id,
+        cost: self::cost(required(cost, "cost")?)?,
+        owner: item_owner(Query(owner))?,
+        active: !flag(required(ended, "auction status")?)?,
+        payment_token: payment_token(required(token, "payment token")?)?,
+        fixed_price: fixed_price(Query(fixed))?,
+        ascending: flag(Query(ascending))?,
+        raffle: flag(Query(raffle))?,
+        status: listing_status(Query(status))?,
+    }))
+}
+
+/// Converts a contract address into the words used by the contracts.
+pub fn address_words(address: &ContentAddress) -> [Word; 4] {
+    word_4_from_u8_32(address.0)
+}
+
+/// Extracts the balance from a Query result.
+pub fn cost(cost: Query) -> Result<Word, AuctionError> {
+    Ok(cost.as_opt_int()?.unwrap_or_default())
+}
+
+/// Generates the key for a buyer's offer on an item.
+pub fn offer_key(item_id: Word, buyer: [Word; 4]) -> Key {
+    let offer: Vec<_> = auction::storage::keys::keys()
+        .offers(|e| e.entry(item_id, |e| e.entry(buyer)))
+        .into();
+    offer.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the token contract a buyer's offer is escrowed in.
+pub fn offer_token_key(item_id: Word, buyer: [Word; 4]) -> Key {
+    let token: Vec<_> = auction::storage::keys::keys()
+        .offer_token(|e| e.entry(item_id, |e| e.entry(buyer)))
+        .into();
+    token.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the number of offers made on an item.
+pub fn offer_count_key(item_id: Word) -> Key {
+    let count: Vec<_> = auction::storage::keys::keys()
+        .offer_count(|e| e.entry(item_id))
+        .into();
+    count.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the buyer of the offer at `index` on an item.
+pub fn offer_buyer_key(item_id: Word, index: Word) -> Key {
+    let buyer: Vec<_> = auction::storage::keys::keys()
+        .offer_buyers(|e| e.entry(item_id, |e| e.entry(index)))
+        .into();
+    buyer.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the number of offer actions a key has signed.
+pub fn offer_nonce_key(hashed_key: [Word; 4]) -> Key {
+    let nonce: Vec<_> = auction::storage::keys::keys()
+        .offer_nonces(|e| e.entry(hashed_key))
+        .into();
+    nonce.into_iter().next().expect("Must be a key")
+}
+
+/// Generates the key for the number of owner actions a key has signed.
+pub fn nonce_key(hashed_key: [Word; 4]) -> Key {
+    let nonce: Vec<_> = auction::storage::keys::keys()
+        .nonces(|e| e.entry(hashed_key))
+        .into();
+    nonce.into_iter().next().expect("Must be a key")
+}
+
+/// Extracts an offer amount, offer count or nonce from a Query result.
+pub fn offer(offer: Query) -> Result<Word, AuctionError> {
+    Ok(offer.as_opt_int()?.unwrap_or_default())
+}
+
+/// Extracts the buyer's hashed key of an offer from a Query result.
+pub fn offer_buyer(buyer: Query) -> Result<[Word; 4], AuctionError> {
+    match buyer.as_opt_b256()? {
+        Some(buyer) => Ok(buyer),
+        None => malformed!("Offer has no buyer"),
+    }
+}
+
+/// The key that holds escrowed tokens in the payment token contracts.
+///
+/// The escrow is held by the auction contract deployed at `contract`.
+pub fn escrow_key(contract: &ContentAddress) -> [Word; 4] {
+    address_words(contract)
+}
+
+/// The address of a generated predicate in the contract deployed at `contract`.
+///
+/// Predicates are addressed by their content, so only the contract part of
+/// the address changes, as with [`ContractAddresses::at`].
+pub fn deployed_at(predicate: PredicateAddress, contract: &ContentAddress) -> PredicateAddress {
+    PredicateAddress {
+        contract: contract.clone(),
+        predicate: predicate.predicate,
+    }
+}
+
+/// Encodes a signature as the `Secp256k1Signature` the contracts expect.
+pub fn signature_words(signature: &RecoverableSignature) -> ([Word; 4], [Word; 4], Word) {
+    let w = essential_sign::encode::signature(signature);
+    ([w[0], w[1], w[2], w[3]], [w[4], w[5], w[6], w[7]], w[8])
 }
@@ -0,0 +1,118 @@
+//! # List
+//! Contains functionality for listing an item for sale at a fixed price.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    address_words, cost, curator_admin, deployed_at, offer, signature_words, AuctionError, Query,
+    LISTING_PENDING,
+};
+
+/// Contains all necessary information to build a list solution.
+pub struct BuildSolution {
+    /// The item being listed.
+    pub item_id: Word,
+    /// The hashed key of the owner.
+    pub hashed_key: [Word; 4],
+    /// The fixed price of the item.
+    pub price: Word,
+    /// The token contract the item is priced in.
+    pub payment_token: ContentAddress,
+    /// The block state contract the auction contract depends on.
+    /// Only needed once the item has been rented.
+    pub block_state: Option<ContentAddress>,
+    /// The collection the item is registered in, if any.
+    pub collection: Option<Word>,
+    /// The current curator admin.
+    /// Once set, the listing stays pending until a curator approves it.
+    pub current_curator_admin: Query,
+    /// The current number of listings created under curation.
+    pub current_curated_item_count: Query,
+    /// The owner's current nonce.
+    pub current_nonce: Query,
+    /// The owner's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The words the owner signs to list an item.
+///
+/// The nonce is the owner's current nonce.
+pub fn data_to_sign(
+    item_id: Word,
+    price: Word,
+    payment_token: &ContentAddress,
+    nonce: Word,
+) -> Vec<Word> {
+    let mut words = vec![item_id, price];
+    words.extend(address_words(payment_token));
+    words.extend([nonce, LIST]);
+    words
+}
+
+/// Builds a list solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        price,
+        payment_token,
+        block_state,
+        collection,
+        current_curator_admin,
+        current_curated_item_count,
+        current_nonce,
+        signature,
+        contract,
+    } = build;
+
+    let curated = curator_admin(current_curator_admin)?.is_some();
+    let curated_index = cost(current_curated_item_count)?;
+    let nonce = offer(current_nonce)?;
+    let next_nonce = nonce.checked_add(1).ok_or(AuctionError::Overflow)?;
+
+    let payment_token = address_words(&payment_token);
+    let pub_vars = super::auction::List::PubVars {
+        item_id,
+        price,
+        key: hashed_key,
+        payment_token,
+        block_state: block_state.as_ref().map(address_words).unwrap_or_default(),
+        collection: collection.unwrap_or_default(),
+        curated_index,
+        nonce,
+    };
+    let vars = super::auction::List::Vars {
+        sig: signature_words(&signature),
+    };
+    let mut mutations = super::auction::storage::mutations()
+        .item_owner(|map| map.entry(item_id, hashed_key))
+        .cost(|map| map.entry(item_id, price))
+        .auction_ended(|map| map.entry(item_id, false))
+        .fixed_price(|map| map.entry(item_id, true))
+        .payment_token(|map| map.entry(item_id, payment_token))
+        .nonces(|map| map.entry(hashed_key, next_nonce));
+    if curated {
+        // The listing is pending until a curator reviews it.
+        mutations = mutations
+            .listing_status(|map| map.entry(item_id, LISTING_PENDING))
+            .curated_items(|map| map.entry(curated_index, item_id))
+            .curated_item_count(curated_index + 1);
+    }
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::List::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
+
+/// Tags a signature as a listing.
+const LIST: Word = 6;
@@ -2,14 +2,21 @@
  #![allow(unused)]  
 use anyhow::bail;
 use clap::{Args, Parser, Subcommand};
-use essential_app_utils::compile::compile_pint_project;
 use essential_rest_client::{
     builder_client::EssentialBuilderClient, node_client::EssentialNodeClient,
 };
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
 use essential_signer::Signature;
-use essential_types::{convert::word_4_from_u8_32, ContentAddress, PredicateAddress, Word};
+use essential_types::{
+    convert::{u8_32_from_word_4, word_4_from_u8_32},
+    solution::Solution,
+    ContentAddress, Word,
+};
 use essential_wallet::Wallet;
-use std::path::PathBuf;
+use std::{
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
 use token::Query;
 
 #[derive(Parser)]
@@ -19,6 +26,18 @@ struct Cli {
     /// If not set then a sensible default will be used (like ~/.essential-wallet).
     #[arg(short, long)]
     wallet: Option<PathBuf>,
+    /// Check solutions against the node's current state instead of sending them to the builder.
+    #[arg(long, global = true)]
+    dry_run: bool,
+    /// The address of the deployed contract, instead of the one built from the pint directory.
+    #[arg(long, global = true)]
+    contract_address: Option<String>,
+    /// A compiled contract json to act on, instead of the one built in the pint directory.
+    #[arg(long, global = true)]
+    contract_json: Option<PathBuf>,
+    /// Act on the contracts embedded in the app, instead of the ones built in the pint directory.
+    #[arg(long, global = true)]
+    embedded: bool,
     #[command(subcommand)]
     command: Command,
 }
@@ -58,15 +77,46 @@ struct Transfer {
 struct PlaceBid {
     /// The account to transfer from.
     bidder_account: String,
-
+    /// The item to bid on.
     item_id: Word,
-    
+    /// The amount to bid.
     amount: Word,
     /// The address of the node to connect to.
     node_api: String,
     /// The address of the builder to connect to.
     builder_api: String,
-    /// The directory of the pint token contract.
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct BundleBid {
+    /// The account to transfer from.
+    bidder_account: String,
+    /// The items to bid on, comma separated.
+    /// Bids on several items are won or lost together.
+    #[arg(long, value_delimiter = ',', required = true)]
+    items: Vec<Word>,
+    /// The amount to bid on each item, comma separated.
+    #[arg(long, value_delimiter = ',', required = true)]
+    amounts: Vec<Word>,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct ClaimProceeds {
+    /// The item sold in a bundle.
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
     pint_directory: PathBuf,
 }
 
@@ -82,8 +132,12 @@ struct CreateAuction {
     node_api: String,
     /// The address of the builder to connect to.
     builder_api: String,
-    /// The directory of the pint token contract.
+    /// The directory of the pint auction contract.
     pint_directory: PathBuf,
+    /// The token contract the item is priced in.
+    /// Contract address as hex. Defaults to the bundled token contract.
+    #[arg(long)]
+    payment_token: Option<String>,
 }
 
 #[derive(Args)]
@@ -98,7 +152,7 @@ struct StepAuction {
     node_api: String,
     /// The address of the builder to connect to.
     builder_api: String,
-    /// The directory of the pint token contract.
+    /// The directory of the pint auction contract.
     pint_directory: PathBuf,
 }
 
@@ -132,352 +186,5116 @@ struct ViewItemDetails {
     node_api: String,
     /// The directory of the pint token contract.
     builder_api: String,
+    /// The directory of the pint auction contract.
     pint_directory: PathBuf,
 }
 
-#[derive(Subcommand)]
-enum Command {
-    Mint(Mint),
-    Transfer(Transfer),
-    Balance(Balance),
-    ExternalBalance(ExternalBalance),
-    CreateAuction(CreateAuction),
-    StepAuction(StepAuction),
-    PlaceBid(PlaceBid),
-    ViewItemDetails(ViewItemDetails),
-}
-
-#[tokio::main]
-async fn main() {
-    let args = Cli::parse();
-    if let Err(err) = run(args).await {
-        eprintln!("Command failed because: {}", err);
-    }
-}
+#[derive(Args)]
+struct List {
+    /// The account that owns the item.
+    owner_account: String,
 
-async fn run(cli: Cli) -> anyhow::Result<()> {
-    let Cli { wallet, command } = cli;
-    let wallet = match &command {
-        Command::ExternalBalance(_) => None,
-        Command::ViewItemDetails(_) => None,
-        _ => {
-            let pass = rpassword::prompt_password("Enter password to unlock wallet: ")?;
-            let wallet = match wallet {
-                Some(path) => essential_wallet::Wallet::new(&pass, path)?,
-                None => essential_wallet::Wallet::with_default_path(&pass)?,
-            };
-            Some(wallet)
-        }
-    };
-    match command {
-        Command::Mint(args) => {
-            println!(
-                "minting {} for account: {}",
-                args.amount, args.account
-            );
-            let wallet = wallet.unwrap();
-            let addr = mint(wallet, args).await?;
-            println!("sent mint solution: {}", addr);
-        }
-        Command::Transfer(args) => {
-            println!(
-                "transferring {} from account: {} to account: {}",
-                args.amount, args.from_account, args.to_account
-            );
-            let wallet = wallet.unwrap();
-            let addr = transfer(wallet, args).await?;
-            println!("sent transfer solution: {}", addr);
-        }
-        Command::Balance(args) => {
-            let Balance {
-                account,
-                node_api,
-                pint_directory,
-            } = args;
-            println!("getting balance for account: {}", account);
-            let mut wallet = wallet.unwrap();
-            let hashed_key = hash_key(&mut wallet, &account);
-            let balance = get_balance(hashed_key, node_api, pint_directory).await?;
-            println!("balance is {}", balance);
-        }
-        Command::ExternalBalance(args) => {
-            let ExternalBalance {
-                account,
-                node_api,
-                pint_directory,
-            } = args;
-            println!("getting balance for account: {}", account);
-            let hashed_key = word_4_from_u8_32(
-                hex::decode(account)?
-                    .try_into()
-                    .map_err(|_| anyhow::anyhow!("To key too large"))?,
-            );
-            let balance = get_balance(hashed_key, node_api, pint_directory).await?;
-            println!("balance is {}", balance);
-        }
-        Command::CreateAuction(args) => {
-            println!(
-                "creation new auction item {} from account with initial cost {}",
-                args.item_id, args.initial_cost
-            );
-            let wallet = wallet.unwrap();
-            let addr = create_auction(wallet, args).await?;
-            println!("sent Create Auction Solution: {}", addr);
-        }
-        Command::StepAuction(args) => {
-            println!(
-                "Decreasing item_id: {} by amount: {}",
-                args.item_id, args.decrease_amt
-            );
-            let wallet = wallet.unwrap();
-            let addr = step_auction(wallet, args).await?;
-            println!("sent Step Auction Solution: {}", addr);
-        }
-        Command::PlaceBid(args) => {
-            println!(
-                "Placing Bid for item_id: {} for amount: {} from account: {}",
-                args.item_id, args.amount, args.bidder_account
-            );
-            let wallet = wallet.unwrap();
-            let addr = place_bid(wallet, args).await?;
-            println!("sent Place bid Solution: {}", addr);
-        }
-        Command::ViewItemDetails(args) => {
-            let ViewItemDetails {
-                item_id,
-                node_api,
-                builder_api,
-                pint_directory,
-            } = args;
-            println!("getting balance for item_id: {:?}", item_id);
+    item_id: Word,
 
-            
-            let node = EssentialNodeClient::new(node_api)?;
-            let builder = EssentialBuilderClient::new(builder_api)?;
-            
-
-            let address = compile_address(pint_directory).await?;
-            
-            let cost_key = token::cost_key(item_id);
-            let current_cost = node
-                .query_state(address.contract.clone(), cost_key)
-                .await?;
-            
-            let item_owner_key = token::get_item_owner_key(item_id);
-            
-            let status_key = token::auction_status_key(item_id);
-            
-            let status_bool = node
-                .query_state(address.contract.clone(), status_key)
-                .await?;
-            
-            let hashed_key = node
-                .query_state(address.contract.clone(), item_owner_key)
-                .await?;
-            
-                if let (Some(cost), Some(hashed_key), Some(status_bool)) = (current_cost.as_ref(), hashed_key.as_ref(), status_bool.as_ref()) {
-                    if (status_bool[0] == 0) {
-                        println!("Cost: {:?}\nOwner: {:?}\nActive: 'true'\n", cost[0], to_hex_string(hashed_key.to_vec()));
-                    }else{
-                        println!("Cost: {:?}\nOwner: {:?}\nActive: 'false'\n", cost[0], to_hex_string(hashed_key.to_vec()));
-                    }
-                } else {
-                    println!("Error: current_cost or hashed_key is None");
-                }
-        }
-    }
-    Ok(())
+    price: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+    /// The token contract the item is priced in.
+    /// Contract address as hex. Defaults to the bundled token contract.
+    #[arg(long)]
+    payment_token: Option<String>,
 }
 
-/// Hashes the public key for an account.
-fn hash_key(wallet: &mut Wallet, account_name: &str) -> [Word; 4] {
-    let public_key = wallet.get_public_key(account_name).unwrap();
-    let essential_signer::PublicKey::Secp256k1(public_key) = public_key else {
-        panic!("Invalid public key")
-    };
-    let encoded = essential_sign::encode::public_key(&public_key);
-    word_4_from_u8_32(essential_hash::hash_words(&encoded))
+#[derive(Args)]
+struct Delist {
+    /// The account that owns the item.
+    owner_account: String,
+
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
 }
 
-async fn mint(mut wallet: Wallet, args: Mint) -> anyhow::Result<ContentAddress> {
-    let Mint {
-        account,
-        amount,
-        node_api,
-        builder_api,
-        pint_directory,
-    } = args;
-    let address = compile_address(pint_directory).await?;
-    let hashed_key = hash_key(&mut wallet, &account);
+#[derive(Args)]
+struct Buy {
+    /// The account buying the item.
+    buyer_account: String,
 
-    let node = EssentialNodeClient::new(node_api)?;
-    let builder = EssentialBuilderClient::new(builder_api)?;
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
 
-    let balance_key = token::balance_key(hashed_key);
-    let balance = node
-        .query_state(address.contract.clone(), balance_key)
-        .await?;
-    let build_solution = token::mint::BuildSolution {
-        current_balance: Query(balance),
-        hashed_key,
-        amount: amount
-    };
-    let solution = token::mint::build_solution(build_solution)?;
-    let ca = builder.submit_solution(&solution).await?;
-    Ok(ca)
+#[derive(Args)]
+struct HoldEscrow {
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+    /// The token contract to hold the escrow in.
+    /// Contract address as hex. Defaults to the bundled token contract.
+    #[arg(long)]
+    payment_token: Option<String>,
 }
 
-async fn transfer(mut wallet: Wallet, args: Transfer) -> anyhow::Result<ContentAddress> {
-    let Transfer {
-        amount,
-        node_api,
-        builder_api,
-        pint_directory,
-        from_account,
-        to_account,
-    } = args;
-    let address = compile_address(pint_directory).await?;
-    let hashed_from_key = hash_key(&mut wallet, &from_account);
-    let hashed_to_key = word_4_from_u8_32(
-        hex::decode(to_account)?
-            .try_into()
-            .map_err(|_| anyhow::anyhow!("To key too large"))?,
-    );
-    let node = EssentialNodeClient::new(node_api)?;
-    let builder = EssentialBuilderClient::new(builder_api)?;
+#[derive(Args)]
+struct Rfq {
+    #[command(subcommand)]
+    command: RfqCommand,
+}
 
-    let balance_key = token::balance_key(hashed_from_key);
-    let from_balance = node
-        .query_state(address.contract.clone(), balance_key)
-        .await?;
-    let balance_key = token::balance_key(hashed_to_key);
-    let to_balance = node
-        .query_state(address.contract.clone(), balance_key)
-        .await?;
-    let build_solution = token::transfer::BuildSolution {
-        current_from_balance: Query(from_balance),
-        current_to_balance: Query(to_balance),
-        hashed_from_key,
-        hashed_to_key,
-        amount: amount,
-    };
-    let solution = token::transfer::build_solution(build_solution)?;
-    let ca = builder.submit_solution(&solution).await?;
-    Ok(ca)
+#[derive(Subcommand)]
+enum RfqCommand {
+    /// Escrow a budget and request providers to bid down.
+    Create(CreateRequest),
+    /// Undercut the best ask on a request.
+    Ask(SubmitAsk),
+    /// Pay the lowest ask once the deadline has passed.
+    Settle(SettleRequest),
+    /// Return the unspent budget to the buyer.
+    Refund(RefundRequest),
 }
 
-async fn create_auction(mut wallet: Wallet, args: CreateAuction) -> anyhow::Result<ContentAddress> {
-    let CreateAuction {
-        owner_account,
-        item_id,
-        initial_cost,
-        node_api,
-        builder_api,
-        pint_directory,
-    } = args;
-    let node = EssentialNodeClient::new(node_api)?;
-    let builder = EssentialBuilderClient::new(builder_api)?;
-    let address = compile_address(pint_directory).await?;
-    let hashed_from_key = hash_key(&mut wallet, &owner_account);
+#[derive(Args)]
+struct CreateRequest {
+    /// The account escrowing the budget.
+    buyer_account: String,
 
+    item_id: Word,
 
-    let build_solution = token::create_auction::BuildSolution {
-        item_id: item_id,
-        hashed_key: hashed_from_key,
-        amount: initial_cost,
-    };
-    let solution = token::create_auction::build_solution(build_solution)?;
-    let ca = builder.submit_solution(&solution).await?;
-    Ok(ca)
+    budget: Word,
+    /// The block number at which asks close.
+    deadline: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+    /// The token contract the budget is escrowed in.
+    /// Contract address as hex. Defaults to the bundled token contract.
+    #[arg(long)]
+    payment_token: Option<String>,
 }
 
-async fn step_auction(mut wallet: Wallet, args: StepAuction) -> anyhow::Result<ContentAddress> {
-    let StepAuction {
-        owner_account,
-        item_id,
-        decrease_amt,
-        node_api,
-        builder_api,
-        pint_directory,
-    } = args;
-    let node = EssentialNodeClient::new(node_api)?;
-    let builder = EssentialBuilderClient::new(builder_api)?;
-    let address = compile_address(pint_directory).await?;
-    let hashed_from_key = hash_key(&mut wallet, &owner_account);
+#[derive(Args)]
+struct SubmitAsk {
+    /// The account offering to fill the request.
+    provider_account: String,
 
-    let cost_key = token::cost_key(item_id);
-    let current_cost = node
-        .query_state(address.contract.clone(), cost_key)
-        .await?;
+    item_id: Word,
 
+    ask: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
 
-    let build_solution = token::step_auction::BuildSolution {
-        item_id: item_id,
-        hashed_key: hashed_from_key,
-        amount: decrease_amt,
-        current_cost: Query(current_cost),
-    };
-    let solution = token::step_auction::build_solution(build_solution)?;
-    let ca = builder.submit_solution(&solution).await?;
-    Ok(ca)
+#[derive(Args)]
+struct SettleRequest {
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
 }
 
-async fn place_bid(mut wallet: Wallet, args: PlaceBid) -> anyhow::Result<ContentAddress> {
-    let PlaceBid {
-        bidder_account,
-        item_id,
+#[derive(Args)]
+struct RefundRequest {
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct CreateAscendingAuction {
+    /// The account that owns the item.
+    owner_account: String,
+
+    item_id: Word,
+    /// The reserve price bidding starts from.
+    reserve: Word,
+    /// The amount the leader's price rises above a losing maximum.
+    increment: Word,
+    /// The block number at which bidding closes and sealed maximums are revealed.
+    deadline: Word,
+    /// The block number at which reveals close and the auction can be settled.
+    reveals_close: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+    /// The token contract the item is priced in.
+    /// Contract address as hex. Defaults to the bundled token contract.
+    #[arg(long)]
+    payment_token: Option<String>,
+}
+
+#[derive(Args)]
+struct ProxyBid {
+    /// The account bidding.
+    bidder_account: String,
+
+    item_id: Word,
+    /// The most the bidder is willing to pay, sealed until bidding closes.
+    max: Word,
+    /// The amount escrowed to cover the maximum.
+    /// Anything at or above the maximum, so it does not give the maximum away.
+    deposit: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct RevealBid {
+    /// The account that placed the proxy bid.
+    bidder_account: String,
+
+    item_id: Word,
+    /// The maximum the proxy bid was sealed with.
+    max: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct CancelAuction {
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct SettleAuction {
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct ClaimRefund {
+    item_id: Word,
+    /// The bidder reclaiming their escrow.
+    /// Hashed key as hex.
+    bidder: String,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct Raffle {
+    #[command(subcommand)]
+    command: RaffleCommand,
+}
+
+#[derive(Subcommand)]
+enum RaffleCommand {
+    /// Offer an item in a raffle.
+    Create(CreateRaffle),
+    /// Escrow a ticket in a raffle.
+    Enter(EnterRaffle),
+    /// Reveal your secret once entries close.
+    Reveal(RevealRaffle),
+    /// Hand the item to the drawn entrant once reveals close.
+    Draw(DrawRaffle),
+    /// Take the item out of a raffle that can't be drawn once reveals close.
+    Close(CloseRaffle),
+    /// Reclaim a losing ticket.
+    Refund(RefundTicket),
+}
+
+#[derive(Args)]
+struct CreateRaffle {
+    /// The account that owns the item.
+    owner_account: String,
+
+    item_id: Word,
+
+    ticket_price: Word,
+    /// The block number at which entries close and reveals open.
+    entries_close: Word,
+    /// The block number at which reveals close.
+    reveals_close: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+    /// The token contract tickets are paid in.
+    /// Contract address as hex. Defaults to the bundled token contract.
+    #[arg(long)]
+    payment_token: Option<String>,
+}
+
+#[derive(Args)]
+struct EnterRaffle {
+    /// The account buying a ticket.
+    entrant_account: String,
+
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct RevealRaffle {
+    /// The seller or entrant account revealing.
+    account: String,
+
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct DrawRaffle {
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct CloseRaffle {
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct RefundTicket {
+    item_id: Word,
+    /// The entrant reclaiming their ticket.
+    /// Hashed key as hex.
+    entrant: String,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct Fractionalize {
+    /// The account that owns the item.
+    owner_account: String,
+
+    item_id: Word,
+    /// The number of shares to mint.
+    supply: Word,
+    /// The price anyone can pay to buy the whole item.
+    reserve: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+    /// The token contract the buyout is paid in.
+    /// Contract address as hex. Defaults to the bundled token contract.
+    #[arg(long)]
+    payment_token: Option<String>,
+}
+
+#[derive(Args)]
+struct ShareTransfer {
+    /// The account to transfer shares from.
+    from_account: String,
+    /// The account to transfer shares to.
+    /// Hashed key as hex.
+    to_account: String,
+
+    item_id: Word,
+
+    amount: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct Buyout {
+    /// The account buying the item.
+    buyer_account: String,
+
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct RedeemShares {
+    item_id: Word,
+    /// The shareholder redeeming their shares.
+    /// Hashed key as hex.
+    holder: String,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct OfferRental {
+    /// The account that owns the item.
+    owner_account: String,
+
+    item_id: Word,
+    /// The price of one lease.
+    price: Word,
+    /// The number of blocks a lease lasts.
+    blocks: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct Rent {
+    /// The account renting the item.
+    renter_account: String,
+
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct RentalStatus {
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct Dispute {
+    #[command(subcommand)]
+    command: DisputeCommand,
+}
+
+#[derive(Subcommand)]
+enum DisputeCommand {
+    /// Hold the next sale of an item in escrow under an arbiter.
+    SetArbiter(SetArbiter),
+    /// Confirm delivery, releasing the sale to the seller.
+    Confirm(ConfirmDelivery),
+    /// Dispute a held sale, leaving it to the arbiter.
+    Open(OpenDispute),
+    /// Rule on a dispute as the arbiter.
+    Resolve(Resolve),
+}
+
+#[derive(Args)]
+struct SetArbiter {
+    /// The account that owns the item.
+    owner_account: String,
+
+    item_id: Word,
+    /// The arbiter of the sale.
+    /// Hashed key as hex.
+    arbiter: String,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct ConfirmDelivery {
+    /// The account that bought the item.
+    buyer_account: String,
+
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct OpenDispute {
+    /// The account that bought the item.
+    buyer_account: String,
+
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct Resolve {
+    /// The arbiter account.
+    arbiter_account: String,
+
+    item_id: Word,
+    /// The part of the sale returned to the buyer.
+    /// The rest is released to the seller.
+    refund: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct Collection {
+    #[command(subcommand)]
+    command: CollectionCommand,
+}
+
+#[derive(Subcommand)]
+enum CollectionCommand {
+    /// Create a collection with a royalty on every sale of its items.
+    Create(CreateCollection),
+    /// Register an item you own in your collection.
+    AddItem(AddCollectionItem),
+    /// Allow or disallow a seller in your closed collection.
+    AllowSeller(AllowSeller),
+    /// Release the royalties owed to a collection to its creator.
+    ClaimRoyalties(ClaimRoyalties),
+    /// Show a collection and its items.
+    Show(ShowCollection),
+}
+
+#[derive(Args)]
+struct CreateCollection {
+    /// The account creating the collection.
+    creator_account: String,
+
+    collection: Word,
+    /// The royalty on every sale in basis points.
+    royalty: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+    /// Allow anyone to sell the items of the collection.
+    #[arg(long)]
+    open: bool,
+}
+
+#[derive(Args)]
+struct AddCollectionItem {
+    /// The account that created the collection.
+    creator_account: String,
+
+    collection: Word,
+
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct AllowSeller {
+    /// The account that created the collection.
+    creator_account: String,
+
+    collection: Word,
+    /// The seller being allowed.
+    /// Hashed key as hex.
+    seller: String,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+    /// Disallow the seller instead.
+    #[arg(long)]
+    revoke: bool,
+}
+
+#[derive(Args)]
+struct ClaimRoyalties {
+    collection: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+    /// The token contract the royalties were paid in.
+    /// Contract address as hex. Defaults to the bundled token contract.
+    #[arg(long)]
+    payment_token: Option<String>,
+}
+
+#[derive(Args)]
+struct ShowCollection {
+    collection: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct Curator {
+    #[command(subcommand)]
+    command: CuratorCommand,
+}
+
+#[derive(Subcommand)]
+enum CuratorCommand {
+    /// Turn on curation, making your account the curator admin.
+    /// Listings created from then on need a curator's approval.
+    SetAdmin(SetCuratorAdmin),
+    /// Appoint or remove a curator as the curator admin.
+    Appoint(AppointCurator),
+    /// Approve a pending auction so it can take bids.
+    Approve(ReviewListing),
+    /// Reject a pending auction.
+    Reject(ReviewListing),
+    /// Take your rejected listing off sale so the item can be listed again.
+    Release(ReleaseListing),
+    /// List the auctions waiting on a curator.
+    ListPending(ListPending),
+}
+
+#[derive(Args)]
+struct SetCuratorAdmin {
+    /// The account that will appoint curators.
+    /// Its key must be the curator admin the contract was deployed with.
+    admin_account: String,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct AppointCurator {
+    /// The curator admin account.
+    admin_account: String,
+    /// The curator being appointed.
+    /// Hashed key as hex.
+    curator: String,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+    /// Remove the curator instead.
+    #[arg(long)]
+    remove: bool,
+}
+
+#[derive(Args)]
+struct ReviewListing {
+    /// The curator account.
+    curator_account: String,
+
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct ReleaseListing {
+    /// The owner account.
+    owner_account: String,
+
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct ListPending {
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct Intent {
+    #[command(subcommand)]
+    command: IntentCommand,
+}
+
+#[derive(Subcommand)]
+enum IntentCommand {
+    /// Sign an intent to pay up to a limit for an item and add it to a pool.
+    Bid(BidIntent),
+}
+
+#[derive(Args)]
+struct BidIntent {
+    /// The account bidding.
+    bidder_account: String,
+
+    item_id: Word,
+    /// The most the bidder will pay.
+    limit: Word,
+    /// The last block the intent can be filled in.
+    expiry: Word,
+    /// The intent pool file the intent is added to.
+    pool: PathBuf,
+    /// The token contract the bidder pays in.
+    /// Contract address as hex. Defaults to the bundled token contract.
+    #[arg(long)]
+    payment_token: Option<String>,
+}
+
+#[derive(Args)]
+struct Solver {
+    #[command(subcommand)]
+    command: SolverCommand,
+}
+
+#[derive(Subcommand)]
+enum SolverCommand {
+    /// Fill the intents in a pool as Dutch auctions come within their limits.
+    Run(RunSolver),
+}
+
+#[derive(Args)]
+struct RunSolver {
+    /// The intent pool file to fill.
+    pool: PathBuf,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+    /// Seconds to wait between passes over the pool.
+    #[arg(long, default_value_t = 5)]
+    interval: u64,
+    /// Make a single pass over the pool and exit.
+    #[arg(long)]
+    once: bool,
+}
+
+#[derive(Args)]
+struct Batch {
+    /// The account that takes every action.
+    account: String,
+    /// An action to take, in order, such as `mint:<amount>`, `transfer:<to>:<amount>`,
+    /// `create-auction:<item_id>:<initial_cost>`, `step-auction:<item_id>:<decrease_amt>`
+    /// or `place-bid:<item_id>:<amount>`.
+    /// The actions are submitted as one solution, so they all happen or none do.
+    #[arg(long = "action", required = true)]
+    actions: Vec<String>,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct Build {
+    /// The file to write the unsigned solution to.
+    #[arg(long)]
+    out: PathBuf,
+    #[command(subcommand)]
+    command: BuildCommand,
+}
+
+#[derive(Subcommand)]
+enum BuildCommand {
+    /// Build an offer on an item that is not for sale.
+    MakeOffer(BuildMakeOffer),
+    /// Build the acceptance of an offer on an item.
+    AcceptOffer(BuildAcceptOffer),
+    /// Build the withdrawal of an offer.
+    WithdrawOffer(BuildWithdrawOffer),
+    /// Build the buyer's confirmation that an item held in escrow arrived.
+    ConfirmDelivery(BuildConfirmDelivery),
+    /// Build a dispute over an item held in escrow.
+    OpenDispute(BuildOpenDispute),
+    /// Build an arbiter's ruling on a dispute.
+    Resolve(BuildResolve),
+    /// Build the fill of a bid intent, signed by the bidder.
+    FillBidIntent(BuildFillBidIntent),
+    /// Build a transfer of tokens signed by the sender, such as out of a treasury account.
+    Transfer(BuildTransfer),
+}
+
+#[derive(Args)]
+struct BuildMakeOffer {
+    /// The account making the offer.
+    /// Hashed key as hex.
+    buyer: String,
+
+    item_id: Word,
+
+    amount: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+    /// The token contract the offer is escrowed in.
+    /// Contract address as hex. Defaults to the bundled token contract.
+    #[arg(long)]
+    payment_token: Option<String>,
+}
+
+#[derive(Args)]
+struct BuildAcceptOffer {
+    /// The account that owns the item.
+    /// Hashed key as hex.
+    owner: String,
+
+    item_id: Word,
+    /// The buyer whose offer is accepted.
+    /// Hashed key as hex.
+    buyer: String,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct BuildWithdrawOffer {
+    /// The account that made the offer.
+    /// Hashed key as hex.
+    buyer: String,
+
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct BuildConfirmDelivery {
+    /// The account that bought the item.
+    /// Hashed key as hex.
+    buyer: String,
+
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct BuildOpenDispute {
+    /// The account that bought the item.
+    /// Hashed key as hex.
+    buyer: String,
+
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct BuildResolve {
+    /// The arbiter account.
+    /// Hashed key as hex.
+    arbiter: String,
+
+    item_id: Word,
+    /// The part of the sale returned to the buyer.
+    /// The rest is released to the seller.
+    refund: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct BuildFillBidIntent {
+    /// The account that bids.
+    /// Hashed key as hex.
+    bidder: String,
+    /// The item to win.
+    item_id: Word,
+    /// The most the bidder will pay.
+    limit: Word,
+    /// The last block the intent can be filled in.
+    expiry: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+    /// The token contract the bidder pays in.
+    /// Contract address as hex. Defaults to the bundled token contract.
+    #[arg(long)]
+    payment_token: Option<String>,
+}
+
+#[derive(Args)]
+struct BuildTransfer {
+    /// The account to transfer from.
+    /// Hashed key as hex.
+    from: String,
+    /// The account to transfer to.
+    /// Hashed key as hex.
+    to: String,
+    /// The amount of token to transfer.
+    amount: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The directory of the pint token contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct SignSolution {
+    /// The account to sign with.
+    account: String,
+    /// The solution file written by `build`, which is signed in place.
+    solution: PathBuf,
+}
+
+#[derive(Args)]
+struct SubmitSolution {
+    /// The signed solution file.
+    solution: PathBuf,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct Offer {
+    #[command(subcommand)]
+    command: OfferCommand,
+}
+
+#[derive(Subcommand)]
+enum OfferCommand {
+    /// Escrow an offer on an item that is not for sale.
+    Make(MakeOffer),
+    /// Accept an offer on an item you own.
+    Accept(AcceptOffer),
+    /// Withdraw your escrowed offer.
+    Withdraw(WithdrawOffer),
+    /// List the open offers on an item.
+    List(ListOffers),
+}
+
+#[derive(Args)]
+struct MakeOffer {
+    /// The account making the offer.
+    buyer_account: String,
+
+    item_id: Word,
+
+    amount: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+    /// The token contract the offer is escrowed in.
+    /// Contract address as hex. Defaults to the bundled token contract.
+    #[arg(long)]
+    payment_token: Option<String>,
+}
+
+#[derive(Args)]
+struct AcceptOffer {
+    /// The account that owns the item.
+    owner_account: String,
+
+    item_id: Word,
+    /// The buyer whose offer is accepted.
+    /// Hashed key as hex.
+    buyer: String,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct WithdrawOffer {
+    /// The account that made the offer.
+    buyer_account: String,
+
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The address of the builder to connect to.
+    builder_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Args)]
+struct ListOffers {
+    item_id: Word,
+    /// The address of the node to connect to.
+    node_api: String,
+    /// The directory of the pint auction contract.
+    pint_directory: PathBuf,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    Mint(Mint),
+    Transfer(Transfer),
+    Balance(Balance),
+    ExternalBalance(ExternalBalance),
+    CreateAuction(CreateAuction),
+    StepAuction(StepAuction),
+    PlaceBid(PlaceBid),
+    BundleBid(BundleBid),
+    ClaimProceeds(ClaimProceeds),
+    ViewItemDetails(ViewItemDetails),
+    Offer(Offer),
+    List(List),
+    Delist(Delist),
+    Buy(Buy),
+    HoldEscrow(HoldEscrow),
+    Rfq(Rfq),
+    CreateAscendingAuction(CreateAscendingAuction),
+    ProxyBid(ProxyBid),
+    RevealBid(RevealBid),
+    SettleAuction(SettleAuction),
+    CancelAuction(CancelAuction),
+    ClaimRefund(ClaimRefund),
+    Raffle(Raffle),
+    Fractionalize(Fractionalize),
+    ShareTransfer(ShareTransfer),
+    Buyout(Buyout),
+    RedeemShares(RedeemShares),
+    OfferRental(OfferRental),
+    Rent(Rent),
+    RentalStatus(RentalStatus),
+    Dispute(Dispute),
+    Collection(Collection),
+    Curator(Curator),
+    Intent(Intent),
+    Solver(Solver),
+    Batch(Batch),
+    Build(Build),
+    Sign(SignSolution),
+    Submit(SubmitSolution),
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Cli::parse();
+    if let Err(err) = run(args).await {
+        eprintln!("Command failed because: {}", err);
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> anyhow::Result<()> {
+    let Cli {
+        wallet,
+        dry_run,
+        contract_address,
+        contract_json,
+        embedded,
+        command,
+    } = cli;
+    let contract_address = match contract_address {
+        Some(address) => Some(parse_address(&address)?),
+        None => None,
+    };
+    let _ = CONTRACT_OVERRIDE.set(ContractOverride {
+        address: contract_address,
+        json: contract_json,
+        embedded,
+    });
+    let sent = if dry_run { "checked" } else { "sent" };
+    match command {
+        Command::Mint(args) => {
+            println!(
+                "minting {} for account: {}",
+                args.amount, args.account
+            );
+            let wallet = open_wallet(wallet)?;
+            let addr = mint(wallet, args, dry_run).await?;
+            println!("{} mint solution: {}", sent, addr);
+        }
+        Command::Transfer(args) => {
+            println!(
+                "transferring {} from account: {} to account: {}",
+                args.amount, args.from_account, args.to_account
+            );
+            let wallet = open_wallet(wallet)?;
+            let addr = transfer(wallet, args, dry_run).await?;
+            println!("{} transfer solution: {}", sent, addr);
+        }
+        Command::Balance(args) => {
+            let Balance {
+                account,
+                node_api,
+                pint_directory,
+            } = args;
+            println!("getting balance for account: {}", account);
+            let mut wallet = open_wallet(wallet)?;
+            let hashed_key = hash_key(&mut wallet, &account)?;
+            let balance = get_balance(hashed_key, node_api, pint_directory).await?;
+            println!("balance is {}", balance);
+        }
+        Command::ExternalBalance(args) => {
+            let ExternalBalance {
+                account,
+                node_api,
+                pint_directory,
+            } = args;
+            println!("getting balance for account: {}", account);
+            let hashed_key = word_4_from_u8_32(
+                hex::decode(account)?
+                    .try_into()
+                    .map_err(|_| anyhow::anyhow!("To key too large"))?,
+            );
+            let balance = get_balance(hashed_key, node_api, pint_directory).await?;
+            println!("balance is {}", balance);
+        }
+        Command::CreateAuction(args) => {
+            println!(
+                "creation new auction item {} from account with initial cost {}",
+                args.item_id, args.initial_cost
+            );
+            let wallet = open_wallet(wallet)?;
+            let addr = create_auction(wallet, args, dry_run).await?;
+            println!("{} Create Auction Solution: {}", sent, addr);
+        }
+        Command::StepAuction(args) => {
+            println!(
+                "Decreasing item_id: {} by amount: {}",
+                args.item_id, args.decrease_amt
+            );
+            let wallet = open_wallet(wallet)?;
+            let addr = step_auction(wallet, args, dry_run).await?;
+            println!("{} Step Auction Solution: {}", sent, addr);
+        }
+        Command::PlaceBid(args) => {
+            println!(
+                "Placing Bid for item_id: {} for amount: {} from account: {}",
+                args.item_id, args.amount, args.bidder_account
+            );
+            let wallet = open_wallet(wallet)?;
+            let addr = place_bid(wallet, args, dry_run).await?;
+            println!("{} Place bid Solution: {}", sent, addr);
+        }
+        Command::BundleBid(args) => {
+            println!(
+                "Placing Bundle Bid for item_ids: {:?} for amounts: {:?} from account: {}",
+                args.items, args.amounts, args.bidder_account
+            );
+            let wallet = open_wallet(wallet)?;
+            let addr = bundle_bid(wallet, args, dry_run).await?;
+            println!("{} Bundle bid Solution: {}", sent, addr);
+        }
+        Command::ClaimProceeds(args) => {
+            println!("Claiming proceeds for item_id: {}", args.item_id);
+            let addr = claim_proceeds(args, dry_run).await?;
+            println!("{} Claim Proceeds Solution: {}", sent, addr);
+        }
+        Command::ViewItemDetails(args) => {
+            let ViewItemDetails {
+                item_id,
+                node_api,
+                builder_api: _,
+                pint_directory,
+            } = args;
+            println!("getting balance for item_id: {:?}", item_id);
+
+            let node = EssentialNodeClient::new(node_api)?;
+            let address = contract_addresses(&pint_directory)?;
+
+            match token::fetch_item(&node, &address.contract, item_id).await? {
+                Some(item) => {
+                    let listing = if item.fixed_price { "fixed" } else { "auction" };
+                    println!(
+                        "Cost: {:?}\nOwner: {:?}\nActive: '{}'\nPayment token: {:?}\nListing: '{}'\nStatus: '{}'\n",
+                        item.cost,
+                        to_hex_string(item.owner.to_vec()),
+                        item.active,
+                        to_hex_string(token::address_words(&item.payment_token).to_vec()),
+                        listing,
+                        item.status
+                    );
+                }
+                None => println!("Item {} has not been listed", item_id),
+            }
+        }
+        Command::List(args) => {
+            println!(
+                "Listing item_id: {} at fixed price: {}",
+                args.item_id, args.price
+            );
+            let wallet = open_wallet(wallet)?;
+            let addr = list(wallet, args, dry_run).await?;
+            println!("{} List Solution: {}", sent, addr);
+        }
+        Command::Delist(args) => {
+            println!("Delisting item_id: {}", args.item_id);
+            let wallet = open_wallet(wallet)?;
+            let addr = delist(wallet, args, dry_run).await?;
+            println!("{} Delist Solution: {}", sent, addr);
+        }
+        Command::Buy(args) => {
+            println!(
+                "Buying item_id: {} from account: {}",
+                args.item_id, args.buyer_account
+            );
+            let wallet = open_wallet(wallet)?;
+            let addr = buy(wallet, args, dry_run).await?;
+            println!("{} Buy Solution: {}", sent, addr);
+        }
+        Command::HoldEscrow(args) => {
+            println!("Holding escrow");
+            let addr = hold_escrow(args, dry_run).await?;
+            println!("{} Hold Escrow Solution: {}", sent, addr);
+        }
+        Command::Rfq(Rfq { command }) => match command {
+            RfqCommand::Create(args) => {
+                println!(
+                    "Requesting item_id: {} with budget: {} until block: {}",
+                    args.item_id, args.budget, args.deadline
+                );
+                let wallet = open_wallet(wallet)?;
+                let addr = create_request(wallet, args, dry_run).await?;
+                println!("{} Create Request Solution: {}", sent, addr);
+            }
+            RfqCommand::Ask(args) => {
+                println!(
+                    "Asking {} for item_id: {} from account: {}",
+                    args.ask, args.item_id, args.provider_account
+                );
+                let wallet = open_wallet(wallet)?;
+                let addr = submit_ask(wallet, args, dry_run).await?;
+                println!("{} Submit Ask Solution: {}", sent, addr);
+            }
+            RfqCommand::Settle(args) => {
+                println!("Settling request for item_id: {}", args.item_id);
+                let addr = settle_request(args, dry_run).await?;
+                println!("{} Settle Request Solution: {}", sent, addr);
+            }
+            RfqCommand::Refund(args) => {
+                println!("Refunding request for item_id: {}", args.item_id);
+                let addr = refund_request(args, dry_run).await?;
+                println!("{} Refund Request Solution: {}", sent, addr);
+            }
+        },
+        Command::CreateAscendingAuction(args) => {
+            println!(
+                "Creating ascending auction for item_id: {} with reserve: {} until block: {}",
+                args.item_id, args.reserve, args.deadline
+            );
+            let wallet = open_wallet(wallet)?;
+            let addr = create_ascending_auction(wallet, args, dry_run).await?;
+            println!("{} Create Ascending Auction Solution: {}", sent, addr);
+        }
+        Command::ProxyBid(args) => {
+            println!(
+                "Sealing proxy bid for item_id: {} with deposit: {} from account: {}",
+                args.item_id, args.deposit, args.bidder_account
+            );
+            let wallet = open_wallet(wallet)?;
+            let addr = proxy_bid(wallet, args, dry_run).await?;
+            println!("{} Proxy Bid Solution: {}", sent, addr);
+            println!("reveal your maximum with reveal-bid once bidding closes");
+        }
+        Command::RevealBid(args) => {
+            println!(
+                "Revealing proxy bid for item_id: {} with maximum: {} from account: {}",
+                args.item_id, args.max, args.bidder_account
+            );
+            let wallet = open_wallet(wallet)?;
+            let (addr, standing) = reveal_bid(wallet, args, dry_run).await?;
+            println!("{} Reveal Bid Solution: {}", sent, addr);
+            match standing {
+                token::proxy_bid::Standing::Winning(price) => {
+                    println!("you are winning at {}", price)
+                }
+                token::proxy_bid::Standing::Outbid(price) => {
+                    println!(
+                        "you are outbid at {}, reclaim your deposit with claim-refund",
+                        price
+                    )
+                }
+            }
+        }
+        Command::SettleAuction(args) => {
+            println!("Settling ascending auction for item_id: {}", args.item_id);
+            let addr = settle_auction(args, dry_run).await?;
+            println!("{} Settle Auction Solution: {}", sent, addr);
+        }
+        Command::CancelAuction(args) => {
+            println!("Cancelling ascending auction for item_id: {}", args.item_id);
+            let addr = cancel_auction(args, dry_run).await?;
+            println!("{} Cancel Auction Solution: {}", sent, addr);
+        }
+        Command::ClaimRefund(args) => {
+            println!(
+                "Claiming refund for item_id: {} to: {}",
+                args.item_id, args.bidder
+            );
+            let addr = claim_refund(args, dry_run).await?;
+            println!("{} Claim Refund Solution: {}", sent, addr);
+        }
+        Command::Raffle(Raffle { command }) => match command {
+            RaffleCommand::Create(args) => {
+                println!(
+                    "Raffling item_id: {} at ticket price: {} until block: {}",
+                    args.item_id, args.ticket_price, args.entries_close
+                );
+                let wallet = open_wallet(wallet)?;
+                let addr = create_raffle(wallet, args, dry_run).await?;
+                println!("{} Create Raffle Solution: {}", sent, addr);
+            }
+            RaffleCommand::Enter(args) => {
+                println!(
+                    "Entering raffle for item_id: {} from account: {}",
+                    args.item_id, args.entrant_account
+                );
+                let wallet = open_wallet(wallet)?;
+                let addr = enter_raffle(wallet, args, dry_run).await?;
+                println!("{} Enter Raffle Solution: {}", sent, addr);
+            }
+            RaffleCommand::Reveal(args) => {
+                println!(
+                    "Revealing secret for item_id: {} from account: {}",
+                    args.item_id, args.account
+                );
+                let wallet = open_wallet(wallet)?;
+                let addr = reveal_raffle(wallet, args, dry_run).await?;
+                println!("{} Reveal Raffle Solution: {}", sent, addr);
+            }
+            RaffleCommand::Draw(args) => {
+                println!("Drawing raffle for item_id: {}", args.item_id);
+                let addr = draw_raffle(args, dry_run).await?;
+                println!("{} Draw Raffle Solution: {}", sent, addr);
+            }
+            RaffleCommand::Close(args) => {
+                println!("Closing raffle for item_id: {}", args.item_id);
+                let addr = close_raffle(args, dry_run).await?;
+                println!("{} Close Raffle Solution: {}", sent, addr);
+            }
+            RaffleCommand::Refund(args) => {
+                println!(
+                    "Refunding ticket for item_id: {} to: {}",
+                    args.item_id, args.entrant
+                );
+                let addr = refund_ticket(args, dry_run).await?;
+                println!("{} Refund Ticket Solution: {}", sent, addr);
+            }
+        },
+        Command::Fractionalize(args) => {
+            println!(
+                "Splitting item_id: {} into {} shares with reserve: {}",
+                args.item_id, args.supply, args.reserve
+            );
+            let wallet = open_wallet(wallet)?;
+            let addr = fractionalize(wallet, args, dry_run).await?;
+            println!("{} Fractionalize Solution: {}", sent, addr);
+        }
+        Command::ShareTransfer(args) => {
+            println!(
+                "transferring {} shares of item_id: {} from account: {} to account: {}",
+                args.amount, args.item_id, args.from_account, args.to_account
+            );
+            let wallet = open_wallet(wallet)?;
+            let addr = share_transfer(wallet, args, dry_run).await?;
+            println!("{} Share Transfer Solution: {}", sent, addr);
+        }
+        Command::Buyout(args) => {
+            println!(
+                "Buying out item_id: {} from account: {}",
+                args.item_id, args.buyer_account
+            );
+            let wallet = open_wallet(wallet)?;
+            let addr = buyout(wallet, args, dry_run).await?;
+            println!("{} Buyout Solution: {}", sent, addr);
+        }
+        Command::RedeemShares(args) => {
+            println!(
+                "Redeeming shares of item_id: {} for: {}",
+                args.item_id, args.holder
+            );
+            let addr = redeem_shares(args, dry_run).await?;
+            println!("{} Redeem Shares Solution: {}", sent, addr);
+        }
+        Command::OfferRental(args) => {
+            println!(
+                "Offering item_id: {} for rent at: {} for {} blocks",
+                args.item_id, args.price, args.blocks
+            );
+            let wallet = open_wallet(wallet)?;
+            let addr = offer_rental(wallet, args, dry_run).await?;
+            println!("{} Offer Rental Solution: {}", sent, addr);
+        }
+        Command::Rent(args) => {
+            println!(
+                "Renting item_id: {} from account: {}",
+                args.item_id, args.renter_account
+            );
+            let wallet = open_wallet(wallet)?;
+            let addr = rent(wallet, args, dry_run).await?;
+            println!("{} Rent Solution: {}", sent, addr);
+        }
+        Command::RentalStatus(args) => {
+            println!("getting rental status for item_id: {}", args.item_id);
+            rental_status(args).await?;
+        }
+        Command::Dispute(Dispute { command }) => match command {
+            DisputeCommand::SetArbiter(args) => {
+                println!(
+                    "Setting arbiter: {} for item_id: {}",
+                    args.arbiter, args.item_id
+                );
+                let wallet = open_wallet(wallet)?;
+                let addr = set_arbiter(wallet, args, dry_run).await?;
+                println!("{} Set Arbiter Solution: {}", sent, addr);
+            }
+            DisputeCommand::Confirm(args) => {
+                println!(
+                    "Confirming delivery of item_id: {} from account: {}",
+                    args.item_id, args.buyer_account
+                );
+                let wallet = open_wallet(wallet)?;
+                let addr = confirm_delivery(wallet, args, dry_run).await?;
+                println!("{} Confirm Delivery Solution: {}", sent, addr);
+            }
+            DisputeCommand::Open(args) => {
+                println!(
+                    "Disputing sale of item_id: {} from account: {}",
+                    args.item_id, args.buyer_account
+                );
+                let wallet = open_wallet(wallet)?;
+                let addr = open_dispute(wallet, args, dry_run).await?;
+                println!("{} Open Dispute Solution: {}", sent, addr);
+            }
+            DisputeCommand::Resolve(args) => {
+                println!(
+                    "Resolving dispute over item_id: {} with refund: {}",
+                    args.item_id, args.refund
+                );
+                let wallet = open_wallet(wallet)?;
+                let addr = resolve(wallet, args, dry_run).await?;
+                println!("{} Resolve Solution: {}", sent, addr);
+            }
+        },
+        Command::Collection(Collection { command }) => match command {
+            CollectionCommand::Create(args) => {
+                println!(
+                    "Creating collection: {} with royalty: {}",
+                    args.collection, args.royalty
+                );
+                let wallet = open_wallet(wallet)?;
+                let addr = create_collection(wallet, args, dry_run).await?;
+                println!("{} Create Collection Solution: {}", sent, addr);
+            }
+            CollectionCommand::AddItem(args) => {
+                println!(
+                    "Adding item_id: {} to collection: {}",
+                    args.item_id, args.collection
+                );
+                let wallet = open_wallet(wallet)?;
+                let addr = add_collection_item(wallet, args, dry_run).await?;
+                println!("{} Add Collection Item Solution: {}", sent, addr);
+            }
+            CollectionCommand::AllowSeller(args) => {
+                println!(
+                    "Setting seller: {} of collection: {}",
+                    args.seller, args.collection
+                );
+                let wallet = open_wallet(wallet)?;
+                let addr = allow_seller(wallet, args, dry_run).await?;
+                println!("{} Allow Seller Solution: {}", sent, addr);
+            }
+            CollectionCommand::ClaimRoyalties(args) => {
+                println!("Claiming royalties of collection: {}", args.collection);
+                let addr = claim_royalties(args, dry_run).await?;
+                println!("{} Claim Royalties Solution: {}", sent, addr);
+            }
+            CollectionCommand::Show(args) => {
+                println!("getting collection: {}", args.collection);
+                show_collection(args).await?;
+            }
+        },
+        Command::Curator(Curator { command }) => match command {
+            CuratorCommand::SetAdmin(args) => {
+                println!("Setting curator admin: {}", args.admin_account);
+                let wallet = open_wallet(wallet)?;
+                let addr = set_curator_admin(wallet, args, dry_run).await?;
+                println!("{} Set Curator Admin Solution: {}", sent, addr);
+            }
+            CuratorCommand::Appoint(args) => {
+                println!("Setting curator: {}", args.curator);
+                let wallet = open_wallet(wallet)?;
+                let addr = appoint_curator(wallet, args, dry_run).await?;
+                println!("{} Set Curator Solution: {}", sent, addr);
+            }
+            CuratorCommand::Approve(args) => {
+                println!("Approving item_id: {}", args.item_id);
+                let wallet = open_wallet(wallet)?;
+                let addr = review_listing(wallet, args, true, dry_run).await?;
+                println!("{} Review Listing Solution: {}", sent, addr);
+            }
+            CuratorCommand::Reject(args) => {
+                println!("Rejecting item_id: {}", args.item_id);
+                let wallet = open_wallet(wallet)?;
+                let addr = review_listing(wallet, args, false, dry_run).await?;
+                println!("{} Review Listing Solution: {}", sent, addr);
+            }
+            CuratorCommand::Release(args) => {
+                println!("Releasing item_id: {}", args.item_id);
+                let wallet = open_wallet(wallet)?;
+                let addr = release_listing(wallet, args, dry_run).await?;
+                println!("{} Release Listing Solution: {}", sent, addr);
+            }
+            CuratorCommand::ListPending(args) => {
+                println!("getting pending listings");
+                list_pending(args).await?;
+            }
+        },
+        Command::Intent(Intent { command }) => match command {
+            IntentCommand::Bid(args) => {
+                println!(
+                    "Publishing intent to pay up to {} for item_id: {} until block: {}",
+                    args.limit, args.item_id, args.expiry
+                );
+                let wallet = open_wallet(wallet)?;
+                publish_bid_intent(wallet, args)?;
+                println!("added Bid Intent to pool");
+            }
+        },
+        Command::Solver(Solver { command }) => match command {
+            SolverCommand::Run(args) => {
+                println!("running solver over pool: {}", args.pool.display());
+                run_solver(args, dry_run).await?;
+            }
+        },
+        Command::Batch(args) => {
+            println!(
+                "batching {} actions for account: {}",
+                args.actions.len(),
+                args.account
+            );
+            let wallet = open_wallet(wallet)?;
+            let addr = batch(wallet, args, dry_run).await?;
+            println!("{} Batch Solution: {}", sent, addr);
+        }
+        Command::Build(args) => {
+            let out = args.out.clone();
+            let envelope = build(args).await?;
+            println!(
+                "wrote unsigned {} solution to: {}",
+                envelope.action,
+                out.display()
+            );
+            println!("words to sign: {:?}", envelope.to_sign);
+        }
+        Command::Sign(args) => {
+            println!(
+                "signing {} with account: {}",
+                args.solution.display(),
+                args.account
+            );
+            let envelope = sign_solution(wallet, args)?;
+            println!("signed {} solution", envelope.action);
+        }
+        Command::Submit(args) => {
+            println!("submitting solution: {}", args.solution.display());
+            let addr = submit_solution(args, dry_run).await?;
+            println!("{} Solution: {}", sent, addr);
+        }
+        Command::Offer(Offer { command }) => match command {
+            OfferCommand::Make(args) => {
+                println!(
+                    "Making offer of {} for item_id: {} from account: {}",
+                    args.amount, args.item_id, args.buyer_account
+                );
+                let wallet = open_wallet(wallet)?;
+                let addr = make_offer(wallet, args, dry_run).await?;
+                println!("{} Make Offer Solution: {}", sent, addr);
+            }
+            OfferCommand::Accept(args) => {
+                println!(
+                    "Accepting offer from: {} for item_id: {}",
+                    args.buyer, args.item_id
+                );
+                let wallet = open_wallet(wallet)?;
+                let addr = accept_offer(wallet, args, dry_run).await?;
+                println!("{} Accept Offer Solution: {}", sent, addr);
+            }
+            OfferCommand::Withdraw(args) => {
+                println!(
+                    "Withdrawing offer for item_id: {} from account: {}",
+                    args.item_id, args.buyer_account
+                );
+                let wallet = open_wallet(wallet)?;
+                let addr = withdraw_offer(wallet, args, dry_run).await?;
+                println!("{} Withdraw Offer Solution: {}", sent, addr);
+            }
+            OfferCommand::List(args) => {
+                println!("getting offers for item_id: {}", args.item_id);
+                list_offers(args).await?;
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Unlocks the wallet at `path`, or at the default path, with a password
+/// read from the terminal.
+fn open_wallet(path: Option<PathBuf>) -> anyhow::Result<Wallet> {
+    let pass = rpassword::prompt_password("Enter password to unlock wallet: ")?;
+    Ok(match path {
+        Some(path) => essential_wallet::Wallet::new(&pass, path)?,
+        None => essential_wallet::Wallet::with_default_path(&pass)?,
+    })
+}
+
+/// Hashes the public key for an account.
+fn hash_key(wallet: &mut Wallet, account_name: &str) -> anyhow::Result<[Word; 4]> {
+    token::client::hash_key(wallet, account_name)
+}
+
+async fn mint(wallet: Wallet, args: Mint, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let Mint {
+        account,
+        amount,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let address = token_addresses(&pint_directory)?;
+    let client = connect(
+        wallet,
+        &account,
+        node_api,
+        builder_api,
+        token::auction::ADDRESS,
+        &address,
+        dry_run,
+    )?;
+    client.mint(amount).await
+}
+
+async fn transfer(
+    wallet: Wallet,
+    args: Transfer,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let Transfer {
+        amount,
+        node_api,
+        builder_api,
+        pint_directory,
+        from_account,
+        to_account,
+    } = args;
+    let address = token_addresses(&pint_directory)?;
+    let client = connect(
+        wallet,
+        &from_account,
+        node_api,
+        builder_api,
+        token::auction::ADDRESS,
+        &address,
+        dry_run,
+    )?;
+    client.transfer(parse_key(&to_account)?, amount).await
+}
+
+async fn create_auction(
+    wallet: Wallet,
+    args: CreateAuction,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let CreateAuction {
+        owner_account,
+        item_id,
+        initial_cost,
+        node_api,
+        builder_api,
+        pint_directory,
+        payment_token,
+    } = args;
+    let address = contract_addresses(&pint_directory)?;
+    let payment_token = match payment_token {
+        Some(payment_token) => parse_address(&payment_token)?,
+        None => token::token::ADDRESS,
+    };
+    let client = connect(
+        wallet,
+        &owner_account,
+        node_api,
+        builder_api,
+        address.contract.clone(),
+        &address,
+        dry_run,
+    )?;
+    client
+        .create_auction(item_id, initial_cost, payment_token)
+        .await
+}
+
+async fn step_auction(
+    wallet: Wallet,
+    args: StepAuction,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let StepAuction {
+        owner_account,
+        item_id,
+        decrease_amt,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let address = contract_addresses(&pint_directory)?;
+    let client = connect(
+        wallet,
+        &owner_account,
+        node_api,
+        builder_api,
+        address.contract.clone(),
+        &address,
+        dry_run,
+    )?;
+    client.step_auction(item_id, decrease_amt).await
+}
+
+async fn place_bid(
+    wallet: Wallet,
+    args: PlaceBid,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let PlaceBid {
+        bidder_account,
+        item_id,
+        amount,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let address = contract_addresses(&pint_directory)?;
+    let client = connect(
+        wallet,
+        &bidder_account,
+        node_api,
+        builder_api,
+        address.contract.clone(),
+        &address,
+        dry_run,
+    )?;
+    client.place_bid(item_id, amount).await
+}
+
+/// Connects a client that signs as `account` and acts on the auction
+/// contract at `contract`.
+///
+/// A dry run checks solutions against the contracts in `address`, the
+/// contract the command was given.
+fn connect(
+    wallet: Wallet,
+    account: &str,
+    node_api: String,
+    builder_api: String,
+    contract: ContentAddress,
+    address: &token::ContractAddresses,
+    dry_run: bool,
+) -> anyhow::Result<token::AuctionClient> {
+    let contracts = token::dry_run::Contracts::in_use(address)?;
+    let client = token::AuctionClient::new(node_api, builder_api, contract, wallet, account)?;
+    Ok(client.with_contracts(contracts).with_dry_run(dry_run))
+}
+
+async fn bundle_bid(
+    mut wallet: Wallet,
+    args: BundleBid,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let BundleBid {
+        bidder_account,
+        items,
+        amounts,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    if items.len() != amounts.len() {
+        bail!("Expected one amount for each item");
+    }
+
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_from_key = hash_key(&mut wallet, &bidder_account)?;
+
+    let solution = bundle_solution(&node, &address.contract, hashed_from_key, &items, &amounts).await?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn bundle_solution(
+    node: &EssentialNodeClient,
+    contract: &ContentAddress,
+    hashed_from_key: [Word; 4],
+    items: &[Word],
+    amounts: &[Word],
+) -> anyhow::Result<Solution> {
+    let mut bundle = Vec::with_capacity(items.len());
+    let mut bundle_token = None;
+    for (&item_id, &amount) in items.iter().zip(amounts) {
+        let item_owner_key = token::get_item_owner_key(item_id);
+        let current_owner = node
+            .query_state(contract.clone(), item_owner_key)
+            .await?;
+
+        let cost_key = token::cost_key(item_id);
+        let current_cost = node
+            .query_state(contract.clone(), cost_key)
+            .await?;
+
+        // A bundle is paid with a single transfer so every item must share a token.
+        let payment_token_key = token::payment_token_key(item_id);
+        let payment_token = node
+            .query_state(contract.clone(), payment_token_key)
+            .await?;
+        let payment_token = token::payment_token(Query(payment_token))?;
+        match &bundle_token {
+            None => bundle_token = Some(payment_token.clone()),
+            Some(bundle_token) if *bundle_token == payment_token => (),
+            Some(_) => bail!("Item {} is priced in a different token", item_id),
+        }
+
+        // Sales held in escrow can't be bundled.
+        let arbiter = node
+            .query_state(contract.clone(), token::arbiter_key(item_id))
+            .await?;
+
+        let (collection, royalty_rate, royalties) =
+            collection_royalty(node, contract, item_id, &payment_token).await?;
+
+        bundle.push(token::place_bid::BundleItem {
+            item_id,
+            amount,
+            current_cost: Query(current_cost),
+            current_owner: Query(current_owner),
+            current_arbiter: Query(arbiter),
+            current_collection: collection,
+            current_royalty_rate: royalty_rate,
+            current_royalties: royalties,
+        });
+    }
+    let Some(payment_token) = bundle_token else {
+        bail!("Bundle has no items");
+    };
+
+    let balance_key = token::balance_key(hashed_from_key);
+    let from_balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    // Check the total up front rather than have the whole bundle rejected.
+    let total = amounts
+        .iter()
+        .try_fold(0 as Word, |total, &amount| total.checked_add(amount))
+        .ok_or(token::AuctionError::Overflow)?;
+    let balance = token::balance(Query(from_balance.clone()))?;
+    if balance < total {
+        bail!("Bundle total {} exceeds balance {}", total, balance);
+    }
+
+    let balance_key = token::balance_key(token::escrow_key(contract));
+    let escrow_balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    let build_bundle = token::place_bid::BuildBundle {
+        hashed_from_key,
+        items: bundle,
+        payment_token,
+        current_from_balance: Query(from_balance),
+        current_escrow_balance: Query(escrow_balance),
+        block_state: token::block_state::ADDRESS,
+        contract: contract.clone(),
+    };
+    Ok(token::place_bid::build_bundle(build_bundle)?)
+}
+
+async fn claim_proceeds(args: ClaimProceeds, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let ClaimProceeds {
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+
+    let proceeds_key = token::proceeds_key(item_id);
+    let proceeds = node
+        .query_state(address.contract.clone(), proceeds_key)
+        .await?;
+
+    let proceeds_seller_key = token::proceeds_seller_key(item_id);
+    let seller = node
+        .query_state(address.contract.clone(), proceeds_seller_key)
+        .await?;
+    let hashed_seller_key = token::item_owner(Query(seller))?;
+
+    let payment_token_key = token::payment_token_key(item_id);
+    let payment_token = node
+        .query_state(address.contract.clone(), payment_token_key)
+        .await?;
+    let payment_token = token::payment_token(Query(payment_token))?;
+
+    let balance_key = token::balance_key(hashed_seller_key);
+    let seller_balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    let balance_key = token::balance_key(token::escrow_key(&address.contract));
+    let escrow_balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    let build_solution = token::claim_proceeds::BuildSolution {
+        item_id,
+        hashed_seller_key,
+        payment_token,
+        current_proceeds: Query(proceeds),
+        current_seller_balance: Query(seller_balance),
+        current_escrow_balance: Query(escrow_balance),
+        contract: address.contract.clone(),
+    };
+    let solution = token::claim_proceeds::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn list(mut wallet: Wallet, args: List, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let List {
+        owner_account,
+        item_id,
+        price,
+        node_api,
+        builder_api,
+        pint_directory,
+        payment_token,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &owner_account)?;
+    let payment_token = match payment_token {
+        Some(payment_token) => parse_address(&payment_token)?,
+        None => token::token::ADDRESS,
+    };
+
+    let existing = token::fetch_item(&node, &address.contract, item_id).await?;
+    token::validate::list(existing.as_ref(), hashed_key, price)
+        .map_err(token::AuctionError::from)?;
+
+    // The lease on a rented item is checked against the block state.
+    let expiry = node
+        .query_state(address.contract.clone(), token::rental_expiry_key(item_id))
+        .await?;
+    let block_state = match expiry {
+        Some(_) => Some(token::block_state::ADDRESS),
+        None => None,
+    };
+    let collection = node
+        .query_state(address.contract.clone(), token::item_collection_key(item_id))
+        .await?;
+    let collection = token::item_collection(Query(collection))?;
+    let curator_admin = node
+        .query_state(address.contract.clone(), token::curator_admin_key())
+        .await?;
+    let curated_item_count = node
+        .query_state(address.contract.clone(), token::curated_item_count_key())
+        .await?;
+    let nonce = node
+        .query_state(address.contract.clone(), token::nonce_key(hashed_key))
+        .await?;
+
+    let to_sign = token::list::data_to_sign(
+        item_id,
+        price,
+        &payment_token,
+        token::offer(Query(nonce.clone()))?,
+    );
+    let signature = sign(&mut wallet, &to_sign, &owner_account)?;
+
+    let build_solution = token::list::BuildSolution {
+        item_id,
+        hashed_key,
+        price,
+        payment_token,
+        block_state,
+        collection,
+        current_curator_admin: Query(curator_admin),
+        current_curated_item_count: Query(curated_item_count),
+        current_nonce: Query(nonce),
+        signature,
+        contract: address.contract.clone(),
+    };
+    let solution = token::list::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn delist(mut wallet: Wallet, args: Delist, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let Delist {
+        owner_account,
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &owner_account)?;
+
+    let item = listed_item(&node, &address.contract, item_id).await?;
+    token::validate::delist(&item, hashed_key).map_err(token::AuctionError::from)?;
+    let nonce = node
+        .query_state(address.contract.clone(), token::nonce_key(hashed_key))
+        .await?;
+
+    let to_sign = token::delist::data_to_sign(item_id, token::offer(Query(nonce.clone()))?);
+    let signature = sign(&mut wallet, &to_sign, &owner_account)?;
+
+    let build_solution = token::delist::BuildSolution {
+        item_id,
+        hashed_key,
+        current_nonce: Query(nonce),
+        signature,
+        contract: address.contract.clone(),
+    };
+    let solution = token::delist::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn buy(mut wallet: Wallet, args: Buy, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let Buy {
+        buyer_account,
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_from_key = hash_key(&mut wallet, &buyer_account)?;
+
+    let item_owner_key = token::get_item_owner_key(item_id);
+    let current_owner = node
+        .query_state(address.contract.clone(), item_owner_key)
+        .await?;
+    let hashed_to_key = token::item_owner(Query(current_owner))?;
+
+    let payment_token_key = token::payment_token_key(item_id);
+    let payment_token = node
+        .query_state(address.contract.clone(), payment_token_key)
+        .await?;
+    let payment_token = token::payment_token(Query(payment_token))?;
+
+    let balance_key = token::balance_key(hashed_from_key);
+    let from_balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    let item = listed_item(&node, &address.contract, item_id).await?;
+    let balance = token::balance(Query(from_balance.clone()))?;
+    token::validate::buy(&item, balance).map_err(token::AuctionError::from)?;
+
+    let collection = node
+        .query_state(address.contract.clone(), token::item_collection_key(item_id))
+        .await?;
+    let collection_id = token::item_collection(Query(collection.clone()))?;
+
+    // Sales in a collection are paid into escrow to split off the royalty.
+    let balance_key = match collection_id {
+        Some(_) => token::balance_key(token::escrow_key(&address.contract)),
+        None => token::balance_key(hashed_to_key),
+    };
+    let to_balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    let cost_key = token::cost_key(item_id);
+    let current_cost = node
+        .query_state(address.contract.clone(), cost_key)
+        .await?;
+
+    let collection_id = collection_id.unwrap_or_default();
+    let royalty_rate = node
+        .query_state(
+            address.contract.clone(),
+            token::collection_royalty_key(collection_id),
+        )
+        .await?;
+    let royalties = node
+        .query_state(
+            address.contract.clone(),
+            token::collection_royalties_key(collection_id, &payment_token),
+        )
+        .await?;
+    let proceeds = node
+        .query_state(address.contract.clone(), token::proceeds_key(item_id))
+        .await?;
+
+    let build_solution = token::buy::BuildSolution {
+        item_id,
+        hashed_from_key,
+        hashed_to_key,
+        current_from_balance: Query(from_balance),
+        current_to_balance: Query(to_balance),
+        current_cost: Query(current_cost),
+        payment_token,
+        current_collection: Query(collection),
+        current_royalty_rate: Query(royalty_rate),
+        current_royalties: Query(royalties),
+        current_proceeds: Query(proceeds),
+        contract: address.contract.clone(),
+    };
+    let solution = token::buy::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn hold_escrow(args: HoldEscrow, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let HoldEscrow {
+        builder_api,
+        pint_directory,
+        payment_token,
+    } = args;
+    if dry_run {
+        bail!("hold-escrow takes no node to dry run against");
+    }
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let payment_token = match payment_token {
+        Some(payment_token) => parse_address(&payment_token)?,
+        None => token::token::ADDRESS,
+    };
+
+    let build_solution = token::hold_escrow::BuildSolution {
+        payment_token,
+        contract: address.contract.clone(),
+    };
+    let solution = token::hold_escrow::build_solution(build_solution)?;
+    let ca = builder.submit_solution(&solution).await?;
+    Ok(ca)
+}
+
+async fn create_request(
+    mut wallet: Wallet,
+    args: CreateRequest,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let CreateRequest {
+        buyer_account,
+        item_id,
+        budget,
+        deadline,
+        node_api,
+        builder_api,
+        pint_directory,
+        payment_token,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &buyer_account)?;
+    let payment_token = match payment_token {
+        Some(payment_token) => parse_address(&payment_token)?,
+        None => token::token::ADDRESS,
+    };
+    let block_state = token::block_state::ADDRESS;
+
+    let balance_key = token::balance_key(hashed_key);
+    let balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    let balance_key = token::balance_key(token::escrow_key(&address.contract));
+    let escrow_balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    let build_solution = token::create_request::BuildSolution {
+        item_id,
+        hashed_key,
+        budget,
+        payment_token,
+        deadline,
+        block_state,
+        current_balance: Query(balance),
+        current_escrow_balance: Query(escrow_balance),
+        contract: address.contract.clone(),
+    };
+    let solution = token::create_request::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn submit_ask(
+    mut wallet: Wallet,
+    args: SubmitAsk,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let SubmitAsk {
+        provider_account,
+        item_id,
+        ask,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &provider_account)?;
+    let block_state = token::block_state::ADDRESS;
+
+    let budget_key = token::request_budget_key(item_id);
+    let budget = node
+        .query_state(address.contract.clone(), budget_key)
+        .await?;
+
+    let best_ask_key = token::best_ask_key(item_id);
+    let best_ask = node
+        .query_state(address.contract.clone(), best_ask_key)
+        .await?;
+
+    let build_solution = token::submit_ask::BuildSolution {
+        item_id,
+        hashed_key,
+        ask,
+        block_state,
+        current_budget: Query(budget),
+        current_best_ask: Query(best_ask),
+        contract: address.contract.clone(),
+    };
+    let solution = token::submit_ask::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn settle_request(args: SettleRequest, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let SettleRequest {
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let block_state = token::block_state::ADDRESS;
+
+    let best_provider_key = token::best_provider_key(item_id);
+    let best_provider = node
+        .query_state(address.contract.clone(), best_provider_key)
+        .await?;
+    let Some(hashed_provider_key) = token::best_provider(Query(best_provider))? else {
+        bail!("No asks on request, refund it instead");
+    };
+
+    let best_ask_key = token::best_ask_key(item_id);
+    let best_ask = node
+        .query_state(address.contract.clone(), best_ask_key)
+        .await?;
+
+    let request_token_key = token::request_token_key(item_id);
+    let payment_token = node
+        .query_state(address.contract.clone(), request_token_key)
+        .await?;
+    let payment_token = token::payment_token(Query(payment_token))?;
+
+    let balance_key = token::balance_key(hashed_provider_key);
+    let provider_balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    let balance_key = token::balance_key(token::escrow_key(&address.contract));
+    let escrow_balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    let build_solution = token::settle_request::BuildSolution {
+        item_id,
+        hashed_provider_key,
+        payment_token,
+        block_state,
+        current_best_ask: Query(best_ask),
+        current_provider_balance: Query(provider_balance),
+        current_escrow_balance: Query(escrow_balance),
+        contract: address.contract.clone(),
+    };
+    let solution = token::settle_request::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn refund_request(args: RefundRequest, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let RefundRequest {
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let block_state = token::block_state::ADDRESS;
+
+    let request_buyer_key = token::request_buyer_key(item_id);
+    let buyer = node
+        .query_state(address.contract.clone(), request_buyer_key)
+        .await?;
+    let hashed_buyer_key = token::request_buyer(Query(buyer))?;
+
+    let budget_key = token::request_budget_key(item_id);
+    let budget = node
+        .query_state(address.contract.clone(), budget_key)
+        .await?;
+
+    let best_ask_key = token::best_ask_key(item_id);
+    let best_ask = node
+        .query_state(address.contract.clone(), best_ask_key)
+        .await?;
+
+    let request_token_key = token::request_token_key(item_id);
+    let payment_token = node
+        .query_state(address.contract.clone(), request_token_key)
+        .await?;
+    let payment_token = token::payment_token(Query(payment_token))?;
+
+    let balance_key = token::balance_key(hashed_buyer_key);
+    let buyer_balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    let balance_key = token::balance_key(token::escrow_key(&address.contract));
+    let escrow_balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    let build_solution = token::refund_request::BuildSolution {
+        item_id,
+        hashed_buyer_key,
+        payment_token,
+        block_state,
+        current_budget: Query(budget),
+        current_best_ask: Query(best_ask),
+        current_buyer_balance: Query(buyer_balance),
+        current_escrow_balance: Query(escrow_balance),
+        contract: address.contract.clone(),
+    };
+    let solution = token::refund_request::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn create_ascending_auction(
+    mut wallet: Wallet,
+    args: CreateAscendingAuction,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let CreateAscendingAuction {
+        owner_account,
+        item_id,
+        reserve,
+        increment,
+        deadline,
+        reveals_close,
+        node_api,
+        builder_api,
+        pint_directory,
+        payment_token,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &owner_account)?;
+    let payment_token = match payment_token {
+        Some(payment_token) => parse_address(&payment_token)?,
+        None => token::token::ADDRESS,
+    };
+    let block_state = token::block_state::ADDRESS;
+
+    let curator_admin = node
+        .query_state(address.contract.clone(), token::curator_admin_key())
+        .await?;
+    let curated_item_count = node
+        .query_state(address.contract.clone(), token::curated_item_count_key())
+        .await?;
+    let collection = node
+        .query_state(address.contract.clone(), token::item_collection_key(item_id))
+        .await?;
+
+    let build_solution = token::create_ascending_auction::BuildSolution {
+        item_id,
+        hashed_key,
+        reserve,
+        increment,
+        deadline,
+        reveals_close,
+        payment_token,
+        block_state,
+        collection: token::item_collection(Query(collection))?,
+        current_curator_admin: Query(curator_admin),
+        current_curated_item_count: Query(curated_item_count),
+        contract: address.contract.clone(),
+    };
+    let solution = token::create_ascending_auction::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn proxy_bid(
+    mut wallet: Wallet,
+    args: ProxyBid,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let ProxyBid {
+        bidder_account,
+        item_id,
+        max,
+        deposit,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &bidder_account)?;
+    let block_state = token::block_state::ADDRESS;
+    let salt = bid_salt(&mut wallet, &bidder_account, item_id)?;
+
+    let payment_token = node
+        .query_state(address.contract.clone(), token::payment_token_key(item_id))
+        .await?;
+    let payment_token = token::payment_token(Query(payment_token))?;
+
+    let current_cost = node
+        .query_state(address.contract.clone(), token::cost_key(item_id))
+        .await?;
+    let commitment = node
+        .query_state(
+            address.contract.clone(),
+            token::bid_commitment_key(item_id, hashed_key),
+        )
+        .await?;
+
+    let balance = node
+        .query_state(payment_token.clone(), token::balance_key(hashed_key))
+        .await?;
+    let escrow_balance = node
+        .query_state(
+            payment_token.clone(),
+            token::balance_key(token::escrow_key(&address.contract)),
+        )
+        .await?;
+
+    let build_solution = token::commit_bid::BuildSolution {
+        item_id,
+        hashed_key,
+        max,
+        salt,
+        deposit,
+        payment_token,
+        block_state,
+        current_cost: Query(current_cost),
+        current_commitment: Query(commitment),
+        current_balance: Query(balance),
+        current_escrow_balance: Query(escrow_balance),
+        contract: address.contract.clone(),
+    };
+    let solution = token::commit_bid::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn reveal_bid(
+    mut wallet: Wallet,
+    args: RevealBid,
+    dry_run: bool,
+) -> anyhow::Result<(ContentAddress, token::proxy_bid::Standing)> {
+    let RevealBid {
+        bidder_account,
+        item_id,
+        max,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &bidder_account)?;
+    let block_state = token::block_state::ADDRESS;
+    let salt = bid_salt(&mut wallet, &bidder_account, item_id)?;
+
+    let block_number = node
+        .query_state(block_state.clone(), token::block_number_key())
+        .await?;
+    let reveals_close = node
+        .query_state(
+            address.contract.clone(),
+            token::bid_reveal_deadline_key(item_id),
+        )
+        .await?;
+    let current_cost = node
+        .query_state(address.contract.clone(), token::cost_key(item_id))
+        .await?;
+    let increment = node
+        .query_state(address.contract.clone(), token::bid_increment_key(item_id))
+        .await?;
+    let leader = node
+        .query_state(address.contract.clone(), token::leader_key(item_id))
+        .await?;
+    let leader_escrow = node
+        .query_state(address.contract.clone(), token::leader_escrow_key(item_id))
+        .await?;
+    let leader_refund = match token::leader(Query(leader.clone()))? {
+        Some(leader) => {
+            node.query_state(address.contract.clone(), token::refund_key(item_id, leader))
+                .await?
+        }
+        None => None,
+    };
+    let refund = node
+        .query_state(address.contract.clone(), token::refund_key(item_id, hashed_key))
+        .await?;
+    let commitment = node
+        .query_state(
+            address.contract.clone(),
+            token::bid_commitment_key(item_id, hashed_key),
+        )
+        .await?;
+    let deposit = node
+        .query_state(
+            address.contract.clone(),
+            token::bid_deposit_key(item_id, hashed_key),
+        )
+        .await?;
+    let revealed = node
+        .query_state(
+            address.contract.clone(),
+            token::bid_revealed_key(item_id, hashed_key),
+        )
+        .await?;
+
+    let leader_max = match token::leader(Query(leader.clone()))? {
+        Some(_) => Some(token::cost(Query(leader_escrow.clone()))?),
+        None => None,
+    };
+    let standing = token::proxy_bid::standing(
+        token::cost(Query(current_cost.clone()))?,
+        leader_max,
+        max,
+        token::cost(Query(increment.clone()))?,
+    );
+
+    let build_solution = token::proxy_bid::BuildSolution {
+        item_id,
+        hashed_key,
+        max,
+        salt,
+        block_state,
+        current_block_number: Query(block_number),
+        current_reveals_close: Query(reveals_close),
+        current_cost: Query(current_cost),
+        current_increment: Query(increment),
+        current_leader: Query(leader),
+        current_leader_escrow: Query(leader_escrow),
+        current_leader_refund: Query(leader_refund),
+        current_refund: Query(refund),
+        current_commitment: Query(commitment),
+        current_deposit: Query(deposit),
+        current_revealed: Query(revealed),
+        contract: address.contract.clone(),
+    };
+    let solution = token::proxy_bid::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok((ca, standing))
+}
+
+/// Derives an account's salt for sealing a maximum from its signature,
+/// so nothing has to be kept between bidding and revealing.
+fn bid_salt(wallet: &mut Wallet, account_name: &str, item_id: Word) -> anyhow::Result<[Word; 4]> {
+    let sig = sign(wallet, &[BID_SALT_TAG, item_id], account_name)?;
+    Ok(word_4_from_u8_32(essential_hash::hash_words(
+        &essential_sign::encode::signature(&sig),
+    )))
+}
+
+/// Separates bid salt signatures from any other signed data.
+const BID_SALT_TAG: Word = 0x6269_645f_7361_6c74;
+
+async fn settle_auction(args: SettleAuction, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let SettleAuction {
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let block_state = token::block_state::ADDRESS;
+
+    let owner = node
+        .query_state(address.contract.clone(), token::get_item_owner_key(item_id))
+        .await?;
+    let current_cost = node
+        .query_state(address.contract.clone(), token::cost_key(item_id))
+        .await?;
+    let leader = node
+        .query_state(address.contract.clone(), token::leader_key(item_id))
+        .await?;
+    let Some(winner) = token::leader(Query(leader.clone()))? else {
+        bail!("No bids on the auction");
+    };
+    let leader_escrow = node
+        .query_state(address.contract.clone(), token::leader_escrow_key(item_id))
+        .await?;
+    let leader_refund = node
+        .query_state(address.contract.clone(), token::refund_key(item_id, winner))
+        .await?;
+    let payment_token = node
+        .query_state(address.contract.clone(), token::payment_token_key(item_id))
+        .await?;
+    let payment_token = token::payment_token(Query(payment_token))?;
+    let (collection, royalty_rate, royalties) =
+        collection_royalty(&node, &address.contract, item_id, &payment_token).await?;
+
+    let build_solution = token::settle_ascending::BuildSolution {
+        item_id,
+        payment_token,
+        block_state,
+        current_owner: Query(owner),
+        current_cost: Query(current_cost),
+        current_leader: Query(leader),
+        current_leader_escrow: Query(leader_escrow),
+        current_leader_refund: Query(leader_refund),
+        current_collection: collection,
+        current_royalty_rate: royalty_rate,
+        current_royalties: royalties,
+        contract: address.contract.clone(),
+    };
+    let solution = token::settle_ascending::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn cancel_auction(args: CancelAuction, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let CancelAuction {
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+
+    let leader = node
+        .query_state(address.contract.clone(), token::leader_key(item_id))
+        .await?;
+
+    let build_solution = token::cancel_ascending::BuildSolution {
+        item_id,
+        block_state: token::block_state::ADDRESS,
+        current_leader: Query(leader),
+        contract: address.contract.clone(),
+    };
+    let solution = token::cancel_ascending::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn claim_refund(args: ClaimRefund, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let ClaimRefund {
+        item_id,
+        bidder,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_bidder_key = word_4_from_u8_32(
+        hex::decode(bidder)?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Bidder key too large"))?,
+    );
+
+    let payment_token = node
+        .query_state(address.contract.clone(), token::payment_token_key(item_id))
+        .await?;
+    let payment_token = token::payment_token(Query(payment_token))?;
+
+    let refund = node
+        .query_state(
+            address.contract.clone(),
+            token::refund_key(item_id, hashed_bidder_key),
+        )
+        .await?;
+    let bidder_balance = node
+        .query_state(payment_token.clone(), token::balance_key(hashed_bidder_key))
+        .await?;
+    let escrow_balance = node
+        .query_state(
+            payment_token.clone(),
+            token::balance_key(token::escrow_key(&address.contract)),
+        )
+        .await?;
+
+    let build_solution = token::claim_refund::BuildSolution {
+        item_id,
+        hashed_bidder_key,
+        payment_token,
+        current_refund: Query(refund),
+        current_bidder_balance: Query(bidder_balance),
+        current_escrow_balance: Query(escrow_balance),
+        contract: address.contract.clone(),
+    };
+    let solution = token::claim_refund::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn create_raffle(
+    mut wallet: Wallet,
+    args: CreateRaffle,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let CreateRaffle {
+        owner_account,
+        item_id,
+        ticket_price,
+        entries_close,
+        reveals_close,
+        node_api,
+        builder_api,
+        pint_directory,
+        payment_token,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &owner_account)?;
+    let payment_token = match payment_token {
+        Some(payment_token) => parse_address(&payment_token)?,
+        None => token::token::ADDRESS,
+    };
+    let block_state = token::block_state::ADDRESS;
+    let (secret, salt) = raffle_secret(&mut wallet, &owner_account, item_id)?;
+
+    let curator_admin = node
+        .query_state(address.contract.clone(), token::curator_admin_key())
+        .await?;
+    let curated_item_count = node
+        .query_state(address.contract.clone(), token::curated_item_count_key())
+        .await?;
+    let collection = node
+        .query_state(address.contract.clone(), token::item_collection_key(item_id))
+        .await?;
+
+    let build_solution = token::create_raffle::BuildSolution {
+        item_id,
+        hashed_key,
+        ticket_price,
+        entries_close,
+        reveals_close,
+        secret,
+        salt,
+        payment_token,
+        block_state,
+        collection: token::item_collection(Query(collection))?,
+        current_curator_admin: Query(curator_admin),
+        current_curated_item_count: Query(curated_item_count),
+        contract: address.contract.clone(),
+    };
+    let solution = token::create_raffle::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn enter_raffle(
+    mut wallet: Wallet,
+    args: EnterRaffle,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let EnterRaffle {
+        entrant_account,
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &entrant_account)?;
+    let block_state = token::block_state::ADDRESS;
+    let (secret, salt) = raffle_secret(&mut wallet, &entrant_account, item_id)?;
+
+    let payment_token = node
+        .query_state(address.contract.clone(), token::payment_token_key(item_id))
+        .await?;
+    let payment_token = token::payment_token(Query(payment_token))?;
+
+    let ticket_price = node
+        .query_state(address.contract.clone(), token::ticket_price_key(item_id))
+        .await?;
+    let entry_count = node
+        .query_state(address.contract.clone(), token::raffle_entry_count_key(item_id))
+        .await?;
+    let balance = node
+        .query_state(payment_token.clone(), token::balance_key(hashed_key))
+        .await?;
+    let escrow_balance = node
+        .query_state(
+            payment_token.clone(),
+            token::balance_key(token::escrow_key(&address.contract)),
+        )
+        .await?;
+
+    let build_solution = token::enter_raffle::BuildSolution {
+        item_id,
+        hashed_key,
+        secret,
+        salt,
+        payment_token,
+        block_state,
+        current_ticket_price: Query(ticket_price),
+        current_entry_count: Query(entry_count),
+        current_balance: Query(balance),
+        current_escrow_balance: Query(escrow_balance),
+        contract: address.contract.clone(),
+    };
+    let solution = token::enter_raffle::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn reveal_raffle(
+    mut wallet: Wallet,
+    args: RevealRaffle,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let RevealRaffle {
+        account,
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &account)?;
+    let block_state = token::block_state::ADDRESS;
+    let (secret, salt) = raffle_secret(&mut wallet, &account, item_id)?;
+
+    let seed = node
+        .query_state(address.contract.clone(), token::raffle_seed_key(item_id))
+        .await?;
+
+    let build_solution = token::reveal_raffle::BuildSolution {
+        item_id,
+        hashed_key,
+        secret,
+        salt,
+        block_state,
+        current_seed: Query(seed),
+        contract: address.contract.clone(),
+    };
+    let solution = token::reveal_raffle::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn draw_raffle(args: DrawRaffle, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let DrawRaffle {
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let block_state = token::block_state::ADDRESS;
+
+    let seed = node
+        .query_state(address.contract.clone(), token::raffle_seed_key(item_id))
+        .await?;
+    let entry_count = node
+        .query_state(address.contract.clone(), token::raffle_entry_count_key(item_id))
+        .await?;
+    let index =
+        token::draw_raffle::winning_index(token::cost(Query(seed))?, token::cost(Query(entry_count))?)?;
+
+    let winner = node
+        .query_state(address.contract.clone(), token::raffle_entrant_key(item_id, index))
+        .await?;
+    let owner = node
+        .query_state(address.contract.clone(), token::get_item_owner_key(item_id))
+        .await?;
+    let ticket_price = node
+        .query_state(address.contract.clone(), token::ticket_price_key(item_id))
+        .await?;
+    let ticket_token = node
+        .query_state(address.contract.clone(), token::ticket_token_key(item_id))
+        .await?;
+    let payment_token = token::payment_token(Query(ticket_token))?;
+    let (collection, royalty_rate, royalties) =
+        collection_royalty(&node, &address.contract, item_id, &payment_token).await?;
+
+    let build_solution = token::draw_raffle::BuildSolution {
+        item_id,
+        payment_token,
+        block_state,
+        current_owner: Query(owner),
+        current_ticket_price: Query(ticket_price),
+        current_winner: Query(winner),
+        current_collection: collection,
+        current_royalty_rate: royalty_rate,
+        current_royalties: royalties,
+        index,
+        contract: address.contract.clone(),
+    };
+    let solution = token::draw_raffle::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn close_raffle(args: CloseRaffle, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let CloseRaffle {
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let block_state = token::block_state::ADDRESS;
+
+    let owner = node
+        .query_state(address.contract.clone(), token::get_item_owner_key(item_id))
+        .await?;
+    let owner = token::item_owner(Query(owner))?;
+    let seller_revealed = node
+        .query_state(
+            address.contract.clone(),
+            token::raffle_revealed_key(item_id, owner),
+        )
+        .await?;
+    let entry_count = node
+        .query_state(
+            address.contract.clone(),
+            token::raffle_entry_count_key(item_id),
+        )
+        .await?;
+
+    let build_solution = token::close_raffle::BuildSolution {
+        item_id,
+        block_state,
+        current_seller_revealed: Query(seller_revealed),
+        current_entry_count: Query(entry_count),
+        contract: address.contract.clone(),
+    };
+    let solution = token::close_raffle::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn refund_ticket(args: RefundTicket, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let RefundTicket {
+        item_id,
+        entrant,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let block_state = token::block_state::ADDRESS;
+    let hashed_entrant_key = word_4_from_u8_32(
+        hex::decode(entrant)?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Entrant key too large"))?,
+    );
+
+    let payment_token = node
+        .query_state(address.contract.clone(), token::ticket_token_key(item_id))
+        .await?;
+    let payment_token = token::payment_token(Query(payment_token))?;
+
+    let ticket_held = node
+        .query_state(
+            address.contract.clone(),
+            token::ticket_held_key(item_id, hashed_entrant_key),
+        )
+        .await?;
+    let ticket_price = node
+        .query_state(address.contract.clone(), token::ticket_price_key(item_id))
+        .await?;
+    let entrant_balance = node
+        .query_state(payment_token.clone(), token::balance_key(hashed_entrant_key))
+        .await?;
+    let escrow_balance = node
+        .query_state(
+            payment_token.clone(),
+            token::balance_key(token::escrow_key(&address.contract)),
+        )
+        .await?;
+
+    let build_solution = token::refund_ticket::BuildSolution {
+        item_id,
+        hashed_entrant_key,
+        payment_token,
+        block_state,
+        current_ticket_held: Query(ticket_held),
+        current_ticket_price: Query(ticket_price),
+        current_entrant_balance: Query(entrant_balance),
+        current_escrow_balance: Query(escrow_balance),
+        contract: address.contract.clone(),
+    };
+    let solution = token::refund_ticket::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+/// Derives an account's raffle secret and salt from its signature,
+/// so nothing has to be kept between entering and revealing.
+fn raffle_secret(
+    wallet: &mut Wallet,
+    account_name: &str,
+    item_id: Word,
+) -> anyhow::Result<(Word, [Word; 4])> {
+    let sig = sign(wallet, &[RAFFLE_SECRET_TAG, item_id], account_name)?;
+    let hash = word_4_from_u8_32(essential_hash::hash_words(
+        &essential_sign::encode::signature(&sig),
+    ));
+    let salt = word_4_from_u8_32(essential_hash::hash_words(&hash));
+    Ok((hash[0].rem_euclid(token::reveal_raffle::SEED_MODULUS), salt))
+}
+
+/// Separates raffle secret signatures from any other signed data.
+const RAFFLE_SECRET_TAG: Word = 0x7261_6666_6c65;
+
+async fn fractionalize(
+    mut wallet: Wallet,
+    args: Fractionalize,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let Fractionalize {
+        owner_account,
+        item_id,
+        supply,
+        reserve,
+        node_api,
+        builder_api,
+        pint_directory,
+        payment_token,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &owner_account)?;
+    let payment_token = match payment_token {
+        Some(payment_token) => parse_address(&payment_token)?,
+        None => token::token::ADDRESS,
+    };
+
+    let owner = node
+        .query_state(address.contract.clone(), token::get_item_owner_key(item_id))
+        .await?;
+    let nonce = node
+        .query_state(address.contract.clone(), token::nonce_key(hashed_key))
+        .await?;
+    let to_sign = token::fractionalize::data_to_sign(
+        item_id,
+        supply,
+        reserve,
+        &payment_token,
+        token::offer(Query(nonce.clone()))?,
+    );
+    let signature = sign(&mut wallet, &to_sign, &owner_account)?;
+
+    let build_solution = token::fractionalize::BuildSolution {
+        item_id,
+        hashed_key,
+        supply,
+        reserve,
+        payment_token,
+        block_state: token::block_state::ADDRESS,
+        current_owner: Query(owner),
+        current_nonce: Query(nonce),
+        signature,
+        contract: address.contract.clone(),
+    };
+    let solution = token::fractionalize::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn share_transfer(
+    mut wallet: Wallet,
+    args: ShareTransfer,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let ShareTransfer {
+        from_account,
+        to_account,
+        item_id,
+        amount,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_from_key = hash_key(&mut wallet, &from_account)?;
+    let hashed_to_key = word_4_from_u8_32(
+        hex::decode(to_account)?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("To key too large"))?,
+    );
+
+    let from_shares = node
+        .query_state(
+            address.contract.clone(),
+            token::share_balance_key(item_id, hashed_from_key),
+        )
+        .await?;
+    let to_shares = node
+        .query_state(
+            address.contract.clone(),
+            token::share_balance_key(item_id, hashed_to_key),
+        )
+        .await?;
+    let nonce = node
+        .query_state(address.contract.clone(), token::share_nonce_key(hashed_from_key))
+        .await?;
+
+    let to_sign = token::transfer_shares::data_to_sign(
+        item_id,
+        hashed_to_key,
+        amount,
+        token::offer(Query(nonce.clone()))?,
+    );
+    let signature = sign(&mut wallet, &to_sign, &from_account)?;
+
+    let build_solution = token::transfer_shares::BuildSolution {
+        item_id,
+        hashed_from_key,
+        hashed_to_key,
+        amount,
+        current_from_shares: Query(from_shares),
+        current_to_shares: Query(to_shares),
+        current_nonce: Query(nonce),
+        signature,
+        contract: address.contract.clone(),
+    };
+    let solution = token::transfer_shares::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn buyout(mut wallet: Wallet, args: Buyout, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let Buyout {
+        buyer_account,
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &buyer_account)?;
+
+    let payment_token = node
+        .query_state(address.contract.clone(), token::payment_token_key(item_id))
+        .await?;
+    let payment_token = token::payment_token(Query(payment_token))?;
+
+    let reserve = node
+        .query_state(address.contract.clone(), token::buyout_reserve_key(item_id))
+        .await?;
+    let balance = node
+        .query_state(payment_token.clone(), token::balance_key(hashed_key))
+        .await?;
+    let escrow_balance = node
+        .query_state(
+            payment_token.clone(),
+            token::balance_key(token::escrow_key(&address.contract)),
+        )
+        .await?;
+
+    let build_solution = token::buyout::BuildSolution {
+        item_id,
+        hashed_key,
+        payment_token,
+        current_reserve: Query(reserve),
+        current_balance: Query(balance),
+        current_escrow_balance: Query(escrow_balance),
+        contract: address.contract.clone(),
+    };
+    let solution = token::buyout::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn redeem_shares(args: RedeemShares, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let RedeemShares {
+        item_id,
+        holder,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_holder_key = word_4_from_u8_32(
+        hex::decode(holder)?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Holder key too large"))?,
+    );
+
+    let payment_token = node
+        .query_state(address.contract.clone(), token::payment_token_key(item_id))
+        .await?;
+    let payment_token = token::payment_token(Query(payment_token))?;
+
+    let supply = node
+        .query_state(address.contract.clone(), token::share_supply_key(item_id))
+        .await?;
+    let reserve = node
+        .query_state(address.contract.clone(), token::buyout_reserve_key(item_id))
+        .await?;
+    let shares = node
+        .query_state(
+            address.contract.clone(),
+            token::share_balance_key(item_id, hashed_holder_key),
+        )
+        .await?;
+    let redeemed = node
+        .query_state(
+            address.contract.clone(),
+            token::shares_redeemed_key(item_id),
+        )
+        .await?;
+    let holder_balance = node
+        .query_state(payment_token.clone(), token::balance_key(hashed_holder_key))
+        .await?;
+    let escrow_balance = node
+        .query_state(
+            payment_token.clone(),
+            token::balance_key(token::escrow_key(&address.contract)),
+        )
+        .await?;
+
+    let build_solution = token::redeem_shares::BuildSolution {
+        item_id,
+        hashed_holder_key,
+        payment_token,
+        current_supply: Query(supply),
+        current_reserve: Query(reserve),
+        current_shares: Query(shares),
+        current_redeemed: Query(redeemed),
+        current_holder_balance: Query(holder_balance),
+        current_escrow_balance: Query(escrow_balance),
+        contract: address.contract.clone(),
+    };
+    let solution = token::redeem_shares::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn offer_rental(
+    mut wallet: Wallet,
+    args: OfferRental,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let OfferRental {
+        owner_account,
+        item_id,
+        price,
+        blocks,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &owner_account)?;
+
+    let owner = node
+        .query_state(address.contract.clone(), token::get_item_owner_key(item_id))
+        .await?;
+    let nonce = node
+        .query_state(address.contract.clone(), token::nonce_key(hashed_key))
+        .await?;
+    let to_sign = token::offer_rental::data_to_sign(
+        item_id,
+        price,
+        blocks,
+        token::offer(Query(nonce.clone()))?,
+    );
+    let signature = sign(&mut wallet, &to_sign, &owner_account)?;
+
+    let build_solution = token::offer_rental::BuildSolution {
+        item_id,
+        hashed_key,
+        price,
+        blocks,
+        current_owner: Query(owner),
+        current_nonce: Query(nonce),
+        signature,
+        contract: address.contract.clone(),
+    };
+    let solution = token::offer_rental::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn rent(mut wallet: Wallet, args: Rent, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let Rent {
+        renter_account,
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &renter_account)?;
+    let block_state = token::block_state::ADDRESS;
+
+    let block_number = node
+        .query_state(block_state.clone(), token::block_number_key())
+        .await?;
+
+    let payment_token = node
+        .query_state(address.contract.clone(), token::payment_token_key(item_id))
+        .await?;
+    let payment_token = token::payment_token(Query(payment_token))?;
+
+    let owner = node
+        .query_state(address.contract.clone(), token::get_item_owner_key(item_id))
+        .await?;
+    let hashed_owner_key = token::item_owner(Query(owner.clone()))?;
+    let auction_ended = node
+        .query_state(address.contract.clone(), token::auction_status_key(item_id))
+        .await?;
+    let price = node
+        .query_state(address.contract.clone(), token::rental_price_key(item_id))
+        .await?;
+    let blocks = node
+        .query_state(address.contract.clone(), token::rental_blocks_key(item_id))
+        .await?;
+    let renter = node
+        .query_state(address.contract.clone(), token::renter_key(item_id))
+        .await?;
+    let expiry = node
+        .query_state(address.contract.clone(), token::rental_expiry_key(item_id))
+        .await?;
+
+    let renter_balance = node
+        .query_state(payment_token.clone(), token::balance_key(hashed_key))
+        .await?;
+    let owner_balance = node
+        .query_state(payment_token.clone(), token::balance_key(hashed_owner_key))
+        .await?;
+
+    let build_solution = token::rent::BuildSolution {
+        item_id,
+        hashed_key,
+        payment_token,
+        block_state,
+        current_block_number: Query(block_number),
+        current_owner: Query(owner),
+        current_auction_ended: Query(auction_ended),
+        current_price: Query(price),
+        current_blocks: Query(blocks),
+        current_renter: Query(renter),
+        current_expiry: Query(expiry),
+        current_renter_balance: Query(renter_balance),
+        current_owner_balance: Query(owner_balance),
+        contract: address.contract.clone(),
+    };
+    let solution = token::rent::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn rental_status(args: RentalStatus) -> anyhow::Result<()> {
+    let RentalStatus {
+        item_id,
+        node_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let block_state = token::block_state::ADDRESS;
+
+    let block_number = node
+        .query_state(block_state, token::block_number_key())
+        .await?;
+    let block_number = token::cost(Query(block_number))?;
+
+    let owner = node
+        .query_state(address.contract.clone(), token::get_item_owner_key(item_id))
+        .await?;
+    let owner = token::item_owner(Query(owner))?;
+    let renter = node
+        .query_state(address.contract.clone(), token::renter_key(item_id))
+        .await?;
+    let renter = token::renter(Query(renter))?;
+    let expiry = node
+        .query_state(address.contract.clone(), token::rental_expiry_key(item_id))
+        .await?;
+    let expiry = token::cost(Query(expiry))?;
+    let price = node
+        .query_state(address.contract.clone(), token::rental_price_key(item_id))
+        .await?;
+    let blocks = node
+        .query_state(address.contract.clone(), token::rental_blocks_key(item_id))
+        .await?;
+
+    let user = token::user_of(owner, renter, expiry, block_number);
+    println!("Owner: {:?}", to_hex_string(owner.to_vec()));
+    println!("User: {:?}", to_hex_string(user.to_vec()));
+    if user != owner {
+        println!("Rented until block: {}", expiry);
+    } else {
+        println!("Not rented");
+    }
+    let price = token::cost(Query(price))?;
+    if price > 0 {
+        println!(
+            "Rent: {} for {} blocks",
+            price,
+            token::cost(Query(blocks))?
+        );
+    }
+    Ok(())
+}
+
+async fn set_arbiter(
+    mut wallet: Wallet,
+    args: SetArbiter,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let SetArbiter {
+        owner_account,
+        item_id,
+        arbiter,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &owner_account)?;
+    let arbiter = word_4_from_u8_32(
+        hex::decode(arbiter)?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Arbiter key too large"))?,
+    );
+
+    let owner = node
+        .query_state(address.contract.clone(), token::get_item_owner_key(item_id))
+        .await?;
+    let escrow_status = node
+        .query_state(address.contract.clone(), token::escrow_status_key(item_id))
+        .await?;
+
+    let build_solution = token::set_arbiter::BuildSolution {
+        item_id,
+        hashed_key,
+        arbiter,
+        current_owner: Query(owner),
+        current_escrow_status: Query(escrow_status),
+        contract: address.contract.clone(),
+    };
+    let solution = token::set_arbiter::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn confirm_delivery(
+    mut wallet: Wallet,
+    args: ConfirmDelivery,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let ConfirmDelivery {
+        buyer_account,
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &buyer_account)?;
+
+    let solution =
+        confirm_delivery_solution(&node, &address.contract, hashed_key, item_id, |to_sign| {
+            sign(&mut wallet, to_sign, &buyer_account)
+        })
+        .await?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+/// Reads the escrow for a delivery confirmation and builds it, signed by `sign`.
+async fn confirm_delivery_solution(
+    node: &EssentialNodeClient,
+    contract: &ContentAddress,
+    hashed_key: [Word; 4],
+    item_id: Word,
+    sign: impl FnOnce(&[Word]) -> anyhow::Result<RecoverableSignature>,
+) -> anyhow::Result<Solution> {
+    let buyer = node
+        .query_state(contract.clone(), token::escrow_buyer_key(item_id))
+        .await?;
+    let seller = node
+        .query_state(contract.clone(), token::escrow_seller_key(item_id))
+        .await?;
+    let escrow_status = node
+        .query_state(contract.clone(), token::escrow_status_key(item_id))
+        .await?;
+    let escrow_sale = node
+        .query_state(contract.clone(), token::escrow_sale_key(item_id))
+        .await?;
+    let escrow_amount = node
+        .query_state(contract.clone(), token::escrow_amount_key(item_id))
+        .await?;
+    let proceeds = node
+        .query_state(contract.clone(), token::proceeds_key(item_id))
+        .await?;
+
+    let to_sign =
+        token::confirm_delivery::data_to_sign(item_id, token::cost(Query(escrow_sale.clone()))?);
+    let signature = sign(&to_sign)?;
+
+    let build_solution = token::confirm_delivery::BuildSolution {
+        item_id,
+        hashed_key,
+        current_buyer: Query(buyer),
+        current_seller: Query(seller),
+        current_escrow_status: Query(escrow_status),
+        current_escrow_sale: Query(escrow_sale),
+        current_escrow_amount: Query(escrow_amount),
+        current_proceeds: Query(proceeds),
+        signature,
+        contract: contract.clone(),
+    };
+    Ok(token::confirm_delivery::build_solution(build_solution)?)
+}
+
+async fn open_dispute(
+    mut wallet: Wallet,
+    args: OpenDispute,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let OpenDispute {
+        buyer_account,
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &buyer_account)?;
+
+    let solution =
+        open_dispute_solution(&node, &address.contract, hashed_key, item_id, |to_sign| {
+            sign(&mut wallet, to_sign, &buyer_account)
+        })
+        .await?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+/// Reads the escrow for a dispute and builds it, signed by `sign`.
+async fn open_dispute_solution(
+    node: &EssentialNodeClient,
+    contract: &ContentAddress,
+    hashed_key: [Word; 4],
+    item_id: Word,
+    sign: impl FnOnce(&[Word]) -> anyhow::Result<RecoverableSignature>,
+) -> anyhow::Result<Solution> {
+    let buyer = node
+        .query_state(contract.clone(), token::escrow_buyer_key(item_id))
+        .await?;
+    let escrow_status = node
+        .query_state(contract.clone(), token::escrow_status_key(item_id))
+        .await?;
+    let escrow_sale = node
+        .query_state(contract.clone(), token::escrow_sale_key(item_id))
+        .await?;
+
+    let to_sign =
+        token::open_dispute::data_to_sign(item_id, token::cost(Query(escrow_sale.clone()))?);
+    let signature = sign(&to_sign)?;
+
+    let build_solution = token::open_dispute::BuildSolution {
+        item_id,
+        hashed_key,
+        current_buyer: Query(buyer),
+        current_escrow_status: Query(escrow_status),
+        current_escrow_sale: Query(escrow_sale),
+        signature,
+        contract: contract.clone(),
+    };
+    Ok(token::open_dispute::build_solution(build_solution)?)
+}
+
+async fn resolve(
+    mut wallet: Wallet,
+    args: Resolve,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let Resolve {
+        arbiter_account,
+        item_id,
+        refund,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &arbiter_account)?;
+
+    let solution = resolve_solution(
+        &node,
+        &address.contract,
+        hashed_key,
+        item_id,
+        refund,
+        |to_sign| sign(&mut wallet, to_sign, &arbiter_account),
+    )
+    .await?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+/// Reads the escrow for a ruling and builds it, signed by `sign`.
+async fn resolve_solution(
+    node: &EssentialNodeClient,
+    contract: &ContentAddress,
+    hashed_key: [Word; 4],
+    item_id: Word,
+    refund: Word,
+    sign: impl FnOnce(&[Word]) -> anyhow::Result<RecoverableSignature>,
+) -> anyhow::Result<Solution> {
+    let arbiter = node
+        .query_state(contract.clone(), token::arbiter_key(item_id))
+        .await?;
+    if token::arbiter(Query(arbiter))? != Some(hashed_key) {
+        bail!("Only the arbiter can resolve a dispute");
+    }
+
+    let buyer = node
+        .query_state(contract.clone(), token::escrow_buyer_key(item_id))
+        .await?;
+    let hashed_buyer_key = token::escrow_buyer(Query(buyer.clone()))?;
+    let seller = node
+        .query_state(contract.clone(), token::escrow_seller_key(item_id))
+        .await?;
+    let escrow_status = node
+        .query_state(contract.clone(), token::escrow_status_key(item_id))
+        .await?;
+    let escrow_sale = node
+        .query_state(contract.clone(), token::escrow_sale_key(item_id))
+        .await?;
+    let escrow_amount = node
+        .query_state(contract.clone(), token::escrow_amount_key(item_id))
+        .await?;
+    let buyer_refund = node
+        .query_state(
+            contract.clone(),
+            token::refund_key(item_id, hashed_buyer_key),
+        )
+        .await?;
+    let proceeds = node
+        .query_state(contract.clone(), token::proceeds_key(item_id))
+        .await?;
+
+    let to_sign =
+        token::resolve::data_to_sign(item_id, refund, token::cost(Query(escrow_sale.clone()))?);
+    let signature = sign(&to_sign)?;
+
+    let build_solution = token::resolve::BuildSolution {
+        item_id,
+        refund,
+        current_buyer: Query(buyer),
+        current_seller: Query(seller),
+        current_escrow_status: Query(escrow_status),
+        current_escrow_sale: Query(escrow_sale),
+        current_escrow_amount: Query(escrow_amount),
+        current_buyer_refund: Query(buyer_refund),
+        current_proceeds: Query(proceeds),
+        signature,
+        contract: contract.clone(),
+    };
+    Ok(token::resolve::build_solution(build_solution)?)
+}
+
+async fn create_collection(
+    mut wallet: Wallet,
+    args: CreateCollection,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let CreateCollection {
+        creator_account,
+        collection,
+        royalty,
+        node_api,
+        builder_api,
+        pint_directory,
+        open,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &creator_account)?;
+
+    let creator = node
+        .query_state(
+            address.contract.clone(),
+            token::collection_creator_key(collection),
+        )
+        .await?;
+
+    let build_solution = token::create_collection::BuildSolution {
+        collection,
+        hashed_key,
+        royalty,
+        open,
+        current_creator: Query(creator),
+        contract: address.contract.clone(),
+    };
+    let solution = token::create_collection::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn add_collection_item(
+    mut wallet: Wallet,
+    args: AddCollectionItem,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let AddCollectionItem {
+        creator_account,
+        collection,
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &creator_account)?;
+
+    let creator = node
+        .query_state(
+            address.contract.clone(),
+            token::collection_creator_key(collection),
+        )
+        .await?;
+    let owner = node
+        .query_state(address.contract.clone(), token::get_item_owner_key(item_id))
+        .await?;
+    let item_count = node
+        .query_state(
+            address.contract.clone(),
+            token::collection_item_count_key(collection),
+        )
+        .await?;
+    let current_collection = node
+        .query_state(address.contract.clone(), token::item_collection_key(item_id))
+        .await?;
+    let nonce = node
+        .query_state(address.contract.clone(), token::nonce_key(hashed_key))
+        .await?;
+
+    let to_sign = token::add_collection_item::data_to_sign(
+        collection,
+        item_id,
+        token::offer(Query(nonce.clone()))?,
+    );
+    let signature = sign(&mut wallet, &to_sign, &creator_account)?;
+
+    let build_solution = token::add_collection_item::BuildSolution {
+        collection,
+        item_id,
+        hashed_key,
+        current_creator: Query(creator),
+        current_owner: Query(owner),
+        current_item_count: Query(item_count),
+        current_collection: Query(current_collection),
+        current_nonce: Query(nonce),
+        signature,
+        contract: address.contract.clone(),
+    };
+    let solution = token::add_collection_item::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn allow_seller(
+    mut wallet: Wallet,
+    args: AllowSeller,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let AllowSeller {
+        creator_account,
+        collection,
+        seller,
+        node_api,
+        builder_api,
+        pint_directory,
+        revoke,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &creator_account)?;
+    let seller = word_4_from_u8_32(
+        hex::decode(seller)?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Seller key too large"))?,
+    );
+
+    let creator = node
+        .query_state(
+            address.contract.clone(),
+            token::collection_creator_key(collection),
+        )
+        .await?;
+    let nonce = node
+        .query_state(address.contract.clone(), token::nonce_key(hashed_key))
+        .await?;
+
+    let to_sign = token::allow_seller::data_to_sign(
+        collection,
+        seller,
+        !revoke,
+        token::offer(Query(nonce.clone()))?,
+    );
+    let signature = sign(&mut wallet, &to_sign, &creator_account)?;
+
+    let build_solution = token::allow_seller::BuildSolution {
+        collection,
+        hashed_key,
+        seller,
+        allowed: !revoke,
+        current_creator: Query(creator),
+        current_nonce: Query(nonce),
+        signature,
+        contract: address.contract.clone(),
+    };
+    let solution = token::allow_seller::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn claim_royalties(args: ClaimRoyalties, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let ClaimRoyalties {
+        collection,
+        node_api,
+        builder_api,
+        pint_directory,
+        payment_token,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let payment_token = match payment_token {
+        Some(payment_token) => parse_address(&payment_token)?,
+        None => token::token::ADDRESS,
+    };
+
+    let creator = node
+        .query_state(
+            address.contract.clone(),
+            token::collection_creator_key(collection),
+        )
+        .await?;
+    let royalties = node
+        .query_state(
+            address.contract.clone(),
+            token::collection_royalties_key(collection, &payment_token),
+        )
+        .await?;
+
+    let balance_key = token::balance_key(token::collection_creator(Query(creator.clone()))?);
+    let creator_balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    let balance_key = token::balance_key(token::escrow_key(&address.contract));
+    let escrow_balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    let build_solution = token::claim_royalties::BuildSolution {
+        collection,
+        payment_token,
+        current_creator: Query(creator),
+        current_royalties: Query(royalties),
+        current_creator_balance: Query(creator_balance),
+        current_escrow_balance: Query(escrow_balance),
+        contract: address.contract.clone(),
+    };
+    let solution = token::claim_royalties::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn show_collection(args: ShowCollection) -> anyhow::Result<()> {
+    let ShowCollection {
+        collection,
+        node_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let address = contract_addresses(&pint_directory)?;
+
+    let creator = node
+        .query_state(
+            address.contract.clone(),
+            token::collection_creator_key(collection),
+        )
+        .await?;
+    let creator = token::collection_creator(Query(creator))?;
+    let royalty = node
+        .query_state(
+            address.contract.clone(),
+            token::collection_royalty_key(collection),
+        )
+        .await?;
+    let open = node
+        .query_state(address.contract.clone(), token::collection_open_key(collection))
+        .await?;
+
+    println!("Creator: {:?}", to_hex_string(creator.to_vec()));
+    println!("Royalty: {} basis points", token::cost(Query(royalty))?);
+    println!("Open: {}", token::collection_open(Query(open))?);
+    let items = token::collection_items(&node, &address.contract, collection).await?;
+    if items.is_empty() {
+        println!("No items");
+    }
+    for item_id in items {
+        println!("Item: {}", item_id);
+    }
+    Ok(())
+}
+
+async fn set_curator_admin(
+    mut wallet: Wallet,
+    args: SetCuratorAdmin,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let SetCuratorAdmin {
+        admin_account,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &admin_account)?;
+
+    let admin = node
+        .query_state(address.contract.clone(), token::curator_admin_key())
+        .await?;
+
+    let to_sign = token::set_curator_admin::data_to_sign(hashed_key);
+    let signature = sign(&mut wallet, &to_sign, &admin_account)?;
+
+    let build_solution = token::set_curator_admin::BuildSolution {
+        hashed_key,
+        current_admin: Query(admin),
+        signature,
+        contract: address.contract.clone(),
+    };
+    let solution = token::set_curator_admin::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn appoint_curator(
+    mut wallet: Wallet,
+    args: AppointCurator,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let AppointCurator {
+        admin_account,
+        curator,
+        node_api,
+        builder_api,
+        pint_directory,
+        remove,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &admin_account)?;
+    let curator = word_4_from_u8_32(
+        hex::decode(curator)?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Curator key too large"))?,
+    );
+
+    let admin = node
+        .query_state(address.contract.clone(), token::curator_admin_key())
+        .await?;
+    let nonce = node
+        .query_state(address.contract.clone(), token::nonce_key(hashed_key))
+        .await?;
+
+    let to_sign =
+        token::set_curator::data_to_sign(curator, !remove, token::offer(Query(nonce.clone()))?);
+    let signature = sign(&mut wallet, &to_sign, &admin_account)?;
+
+    let build_solution = token::set_curator::BuildSolution {
+        hashed_key,
+        curator,
+        appointed: !remove,
+        current_admin: Query(admin),
+        current_nonce: Query(nonce),
+        signature,
+        contract: address.contract.clone(),
+    };
+    let solution = token::set_curator::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn review_listing(
+    mut wallet: Wallet,
+    args: ReviewListing,
+    approved: bool,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let ReviewListing {
+        curator_account,
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &curator_account)?;
+
+    let curator = node
+        .query_state(address.contract.clone(), token::curator_key(hashed_key))
+        .await?;
+    let status = node
+        .query_state(address.contract.clone(), token::listing_status_key(item_id))
+        .await?;
+    let nonce = node
+        .query_state(address.contract.clone(), token::nonce_key(hashed_key))
+        .await?;
+
+    let to_sign =
+        token::review_listing::data_to_sign(item_id, approved, token::offer(Query(nonce.clone()))?);
+    let signature = sign(&mut wallet, &to_sign, &curator_account)?;
+
+    let build_solution = token::review_listing::BuildSolution {
+        item_id,
+        hashed_key,
+        approved,
+        current_curator: Query(curator),
+        current_status: Query(status),
+        current_nonce: Query(nonce),
+        signature,
+        contract: address.contract.clone(),
+    };
+    let solution = token::review_listing::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn release_listing(
+    mut wallet: Wallet,
+    args: ReleaseListing,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let ReleaseListing {
+        owner_account,
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &owner_account)?;
+
+    let owner = node
+        .query_state(address.contract.clone(), token::get_item_owner_key(item_id))
+        .await?;
+    let status = node
+        .query_state(address.contract.clone(), token::listing_status_key(item_id))
+        .await?;
+    let nonce = node
+        .query_state(address.contract.clone(), token::nonce_key(hashed_key))
+        .await?;
+
+    let to_sign =
+        token::release_listing::data_to_sign(item_id, token::offer(Query(nonce.clone()))?);
+    let signature = sign(&mut wallet, &to_sign, &owner_account)?;
+
+    let build_solution = token::release_listing::BuildSolution {
+        item_id,
+        hashed_key,
+        current_owner: Query(owner),
+        current_status: Query(status),
+        current_nonce: Query(nonce),
+        signature,
+        contract: address.contract.clone(),
+    };
+    let solution = token::release_listing::build_solution(build_solution)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+async fn list_pending(args: ListPending) -> anyhow::Result<()> {
+    let ListPending {
+        node_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let address = contract_addresses(&pint_directory)?;
+
+    let pending = token::pending_listings(&node, &address.contract).await?;
+    if pending.is_empty() {
+        println!("No pending listings");
+    }
+    for item_id in pending {
+        let cost = node
+            .query_state(address.contract.clone(), token::cost_key(item_id))
+            .await?;
+        let owner = node
+            .query_state(address.contract.clone(), token::get_item_owner_key(item_id))
+            .await?;
+        let owner = token::item_owner(Query(owner))?;
+        println!(
+            "Item: {}\nCost: {}\nOwner: {:?}\n",
+            item_id,
+            token::cost(Query(cost))?,
+            to_hex_string(owner.to_vec())
+        );
+    }
+    Ok(())
+}
+
+fn publish_bid_intent(mut wallet: Wallet, args: BidIntent) -> anyhow::Result<()> {
+    let BidIntent {
+        bidder_account,
+        item_id,
+        limit,
+        expiry,
+        pool,
+        payment_token,
+    } = args;
+    let payment_token = match payment_token {
+        Some(payment_token) => parse_address(&payment_token)?,
+        None => token::token::ADDRESS,
+    };
+    if limit <= 0 {
+        bail!("Limit must be positive");
+    }
+    let bidder = hash_key(&mut wallet, &bidder_account)?;
+
+    let data = token::bid_intent::data_to_sign(item_id, limit, expiry, &payment_token);
+    let signature = sign(&mut wallet, &data, &bidder_account)?;
+    let intent = token::bid_intent::BidIntent {
+        item_id,
+        bidder,
+        limit,
+        expiry,
+        payment_token,
+        signature,
+    };
+
+    let mut intents = read_intent_pool(&pool)?;
+    intents.push(intent);
+    write_intent_pool(&pool, &intents)
+}
+
+async fn run_solver(args: RunSolver, dry_run: bool) -> anyhow::Result<()> {
+    let RunSolver {
+        pool,
+        node_api,
+        builder_api,
+        pint_directory,
+        interval,
+        once,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+
+    loop {
+        // The pool is re-read each pass so newly published intents are picked up.
+        let intents = read_intent_pool(&pool)?;
+        let fills = token::solver::find_fills(&node, &address.contract, &intents).await?;
+        for fill in fills {
+            match submit(&builder, &node, &fill.solution, &address, dry_run).await {
+                Ok(ca) => println!(
+                    "filled intent on item_id: {} for bidder: {:?} at price: {}: {}",
+                    fill.item_id,
+                    to_hex_string(fill.bidder.to_vec()),
+                    fill.price,
+                    ca
+                ),
+                Err(err) => eprintln!("failed to fill item_id: {}: {}", fill.item_id, err),
+            }
+        }
+        if once {
+            return Ok(());
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+    }
+}
+
+async fn batch(mut wallet: Wallet, args: Batch, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let Batch {
+        account,
+        actions,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &account)?;
+
+    let word = |word: &str| -> anyhow::Result<Word> { Ok(word.parse()?) };
+    let mut batch = token::SolutionBatch::new();
+    for action in &actions {
+        match action.split(':').collect::<Vec<_>>()[..] {
+            ["mint", amount] => batch.push(token::mint::Mint {
+                hashed_key,
+                amount: word(amount)?,
+            }),
+            ["transfer", to, amount] => batch.push(token::transfer::Transfer {
+                hashed_from_key: hashed_key,
+                hashed_to_key: word_4_from_u8_32(
+                    hex::decode(to)?
+                        .try_into()
+                        .map_err(|_| anyhow::anyhow!("To key too large"))?,
+                ),
+                amount: word(amount)?,
+            }),
+            ["create-auction", item_id, initial_cost] => {
+                batch.push(token::create_auction::CreateAuction {
+                    item_id: word(item_id)?,
+                    hashed_key,
+                    initial_cost: word(initial_cost)?,
+                    payment_token: token::token::ADDRESS,
+                    contract: address.contract.clone(),
+                })
+            }
+            ["step-auction", item_id, decrease_amt] => {
+                batch.push(token::step_auction::StepAuction {
+                    item_id: word(item_id)?,
+                    hashed_key,
+                    decrease: word(decrease_amt)?,
+                    contract: address.contract.clone(),
+                })
+            }
+            ["place-bid", item_id, amount] => {
+                let item = listed_item(&node, &address.contract, word(item_id)?).await?;
+                let collection = node
+                    .query_state(address.contract.clone(), token::item_collection_key(item.id))
+                    .await?;
+                batch.push(token::place_bid::PlaceBid {
+                    item_id: item.id,
+                    hashed_from_key: hashed_key,
+                    hashed_to_key: item.owner,
+                    amount: word(amount)?,
+                    payment_token: item.payment_token,
+                    collection: token::item_collection(Query(collection))?,
+                    contract: address.contract.clone(),
+                })
+            }
+            _ => bail!("Unknown action: {}", action),
+        };
+    }
+
+    let state = token::action::fetch_state(&node, &batch).await?;
+    let solution = token::SolutionBuilder::build(&batch, &state)?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+/// Builds a solution that is signed later, and writes it to the out file.
+async fn build(args: Build) -> anyhow::Result<token::envelope::Envelope> {
+    let Build { out, command } = args;
+    let mut to_sign = Vec::new();
+    let blank = |words: &[Word]| -> anyhow::Result<RecoverableSignature> {
+        to_sign = words.to_vec();
+        Ok(token::envelope::blank_signature())
+    };
+
+    let (action, item_id, signer, height, solution) = match command {
+        BuildCommand::MakeOffer(BuildMakeOffer {
+            buyer,
+            item_id,
+            amount,
+            node_api,
+            pint_directory,
+            payment_token,
+        }) => {
+            let node = EssentialNodeClient::new(node_api)?;
+            let address = contract_addresses(&pint_directory)?;
+            let signer = parse_key(&buyer)?;
+            let payment_token = match payment_token {
+                Some(payment_token) => parse_address(&payment_token)?,
+                None => token::token::ADDRESS,
+            };
+            let height = snapshot_height(&node).await?;
+            let solution = make_offer_solution(
+                &node,
+                &address.contract,
+                signer,
+                item_id,
+                amount,
+                payment_token,
+                blank,
+            )
+            .await?;
+            ("make-offer", Some(item_id), signer, height, solution)
+        }
+        BuildCommand::AcceptOffer(BuildAcceptOffer {
+            owner,
+            item_id,
+            buyer,
+            node_api,
+            pint_directory,
+        }) => {
+            let node = EssentialNodeClient::new(node_api)?;
+            let address = contract_addresses(&pint_directory)?;
+            let signer = parse_key(&owner)?;
+            let height = snapshot_height(&node).await?;
+            let solution = accept_offer_solution(
+                &node,
+                &address.contract,
+                signer,
+                item_id,
+                parse_key(&buyer)?,
+                blank,
+            )
+            .await?;
+            ("accept-offer", Some(item_id), signer, height, solution)
+        }
+        BuildCommand::WithdrawOffer(BuildWithdrawOffer {
+            buyer,
+            item_id,
+            node_api,
+            pint_directory,
+        }) => {
+            let node = EssentialNodeClient::new(node_api)?;
+            let address = contract_addresses(&pint_directory)?;
+            let signer = parse_key(&buyer)?;
+            let height = snapshot_height(&node).await?;
+            let solution =
+                withdraw_offer_solution(&node, &address.contract, signer, item_id, blank).await?;
+            ("withdraw-offer", Some(item_id), signer, height, solution)
+        }
+        BuildCommand::ConfirmDelivery(BuildConfirmDelivery {
+            buyer,
+            item_id,
+            node_api,
+            pint_directory,
+        }) => {
+            let node = EssentialNodeClient::new(node_api)?;
+            let address = contract_addresses(&pint_directory)?;
+            let signer = parse_key(&buyer)?;
+            let height = snapshot_height(&node).await?;
+            let solution =
+                confirm_delivery_solution(&node, &address.contract, signer, item_id, blank).await?;
+            ("confirm-delivery", Some(item_id), signer, height, solution)
+        }
+        BuildCommand::OpenDispute(BuildOpenDispute {
+            buyer,
+            item_id,
+            node_api,
+            pint_directory,
+        }) => {
+            let node = EssentialNodeClient::new(node_api)?;
+            let address = contract_addresses(&pint_directory)?;
+            let signer = parse_key(&buyer)?;
+            let height = snapshot_height(&node).await?;
+            let solution =
+                open_dispute_solution(&node, &address.contract, signer, item_id, blank).await?;
+            ("open-dispute", Some(item_id), signer, height, solution)
+        }
+        BuildCommand::Resolve(BuildResolve {
+            arbiter,
+            item_id,
+            refund,
+            node_api,
+            pint_directory,
+        }) => {
+            let node = EssentialNodeClient::new(node_api)?;
+            let address = contract_addresses(&pint_directory)?;
+            let signer = parse_key(&arbiter)?;
+            let height = snapshot_height(&node).await?;
+            let solution =
+                resolve_solution(&node, &address.contract, signer, item_id, refund, blank).await?;
+            ("resolve", Some(item_id), signer, height, solution)
+        }
+        BuildCommand::FillBidIntent(BuildFillBidIntent {
+            bidder,
+            item_id,
+            limit,
+            expiry,
+            node_api,
+            pint_directory,
+            payment_token,
+        }) => {
+            let node = EssentialNodeClient::new(node_api)?;
+            let address = contract_addresses(&pint_directory)?;
+            let signer = parse_key(&bidder)?;
+            let payment_token = match payment_token {
+                Some(payment_token) => parse_address(&payment_token)?,
+                None => token::token::ADDRESS,
+            };
+            let height = snapshot_height(&node).await?;
+            to_sign = token::bid_intent::data_to_sign(item_id, limit, expiry, &payment_token);
+            let intent = token::bid_intent::BidIntent {
+                item_id,
+                bidder: signer,
+                limit,
+                expiry,
+                payment_token,
+                signature: token::envelope::blank_signature(),
+            };
+            let fills = token::solver::find_fills(&node, &address.contract, &[intent]).await?;
+            let Some(fill) = fills.into_iter().next() else {
+                bail!("The intent on item_id: {} can't be filled now", item_id);
+            };
+            let solution = fill.solution;
+            ("fill-bid-intent", Some(item_id), signer, height, solution)
+        }
+        BuildCommand::Transfer(BuildTransfer {
+            from,
+            to,
+            amount,
+            node_api,
+            pint_directory,
+        }) => {
+            let node = EssentialNodeClient::new(node_api)?;
+            let address = token_addresses(&pint_directory)?;
+            let signer = parse_key(&from)?;
+            let height = snapshot_height(&node).await?;
+            let solution = signed_transfer_solution(
+                &node,
+                &address.contract,
+                signer,
+                parse_key(&to)?,
+                amount,
+                blank,
+            )
+            .await?;
+            ("transfer", None, signer, height, solution)
+        }
+    };
+
+    let slot = Some(token::envelope::SignatureSlot::find(action, &solution)?);
+    let envelope = token::envelope::Envelope {
+        action: action.to_string(),
+        item_id,
+        height,
+        signer,
+        to_sign,
+        slot,
+        signature: None,
+        solution,
+    };
+    write_envelope(&out, &envelope)?;
+    Ok(envelope)
+}
+
+/// Signs a solution file with the wallet. Nothing is read from the network.
+///
+/// The words to sign are recomputed from the solution, so a file whose
+/// solution was changed after it was built is refused.
+fn sign_solution(
+    wallet: Option<PathBuf>,
+    args: SignSolution,
+) -> anyhow::Result<token::envelope::Envelope> {
+    let SignSolution { account, solution } = args;
+    let mut envelope = read_envelope(&solution)?;
+    println!("{}", envelope.describe()?);
+    let to_sign = envelope.data_to_sign()?;
+    if to_sign != envelope.to_sign {
+        bail!("The words to sign do not match the solution");
+    }
+    if envelope.slot.is_none() {
+        bail!("The {} solution does not need signing", envelope.action);
+    }
+    let mut wallet = open_wallet(wallet)?;
+    let hashed_key = hash_key(&mut wallet, &account)?;
+    if hashed_key != envelope.signer {
+        bail!(
+            "Account {} has key {} but the solution must be signed by {}",
+            account,
+            hex::encode(u8_32_from_word_4(hashed_key)),
+            hex::encode(u8_32_from_word_4(envelope.signer))
+        );
+    }
+    let signature = sign(&mut wallet, &to_sign, &account)?;
+    envelope.sign(signature)?;
+    write_envelope(&solution, &envelope)?;
+    Ok(envelope)
+}
+
+/// Submits a signed solution file.
+async fn submit_solution(args: SubmitSolution, dry_run: bool) -> anyhow::Result<ContentAddress> {
+    let SubmitSolution {
+        solution,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let solution = read_envelope(&solution)?.into_solution()?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+fn read_envelope(path: &Path) -> anyhow::Result<token::envelope::Envelope> {
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+fn write_envelope(path: &Path, envelope: &token::envelope::Envelope) -> anyhow::Result<()> {
+    std::fs::write(path, serde_json::to_string_pretty(envelope)?)?;
+    Ok(())
+}
+
+/// The block number of the state on the node, or `None` if the block state
+/// contract has not recorded a block yet.
+async fn snapshot_height(node: &EssentialNodeClient) -> anyhow::Result<Option<Word>> {
+    let block_number = node
+        .query_state(token::block_state::ADDRESS, token::block_number_key())
+        .await?;
+    Ok(Query(block_number).as_opt_int()?)
+}
+
+/// Reads the collection of an item along with the collection's royalty rate
+/// and the royalties it is owed in `payment_token`.
+async fn collection_royalty(
+    node: &EssentialNodeClient,
+    contract: &ContentAddress,
+    item_id: Word,
+    payment_token: &ContentAddress,
+) -> anyhow::Result<(Query, Query, Query)> {
+    let collection = node
+        .query_state(contract.clone(), token::item_collection_key(item_id))
+        .await?;
+    let collection_id = token::item_collection(Query(collection.clone()))?.unwrap_or_default();
+    let royalty_rate = node
+        .query_state(
+            contract.clone(),
+            token::collection_royalty_key(collection_id),
+        )
+        .await?;
+    let royalties = node
+        .query_state(
+            contract.clone(),
+            token::collection_royalties_key(collection_id, payment_token),
+        )
+        .await?;
+    Ok((Query(collection), Query(royalty_rate), Query(royalties)))
+}
+
+/// Reads an item that must already be listed.
+async fn listed_item(
+    node: &EssentialNodeClient,
+    contract: &ContentAddress,
+    item_id: Word,
+) -> anyhow::Result<token::AuctionItem> {
+    match token::fetch_item(node, contract, item_id).await? {
+        Some(item) => Ok(item),
+        None => bail!("Item {} has not been listed", item_id),
+    }
+}
+
+/// Where `--contract-address`, `--contract-json` and `--embedded` point the CLI.
+#[derive(Default)]
+struct ContractOverride {
+    address: Option<ContentAddress>,
+    json: Option<PathBuf>,
+    embedded: bool,
+}
+
+/// The contract overrides that were passed, if any.
+static CONTRACT_OVERRIDE: OnceLock<ContractOverride> = OnceLock::new();
+
+/// The addresses of the auction contract a command acts on.
+///
+/// These are read from the output of `pint build` rather than compiled,
+/// unless overridden on the command line.
+fn contract_addresses(pint_directory: &Path) -> anyhow::Result<token::ContractAddresses> {
+    overridden_addresses(pint_directory, "auction")
+}
+
+/// The addresses of the token contract a command acts on.
+fn token_addresses(pint_directory: &Path) -> anyhow::Result<token::ContractAddresses> {
+    overridden_addresses(pint_directory, "token")
+}
+
+/// The addresses of the contract built in `pint_directory`, or of the
+/// `generated` contract with `--embedded`, with any overrides applied.
+fn overridden_addresses(
+    pint_directory: &Path,
+    generated: &str,
+) -> anyhow::Result<token::ContractAddresses> {
+    let overrides = CONTRACT_OVERRIDE.get_or_init(Default::default);
+    let addresses = match &overrides.json {
+        Some(json) => token::addresses_from_json(json)?,
+        None if overrides.embedded => token::generated_addresses(generated)?,
+        None => token::read_addresses(pint_directory)?,
+    };
+    Ok(match &overrides.address {
+        Some(address) => addresses.at(address.clone()),
+        None => addresses,
+    })
+}
+
+/// Sends a solution to the builder.
+///
+/// In a dry run the solution is instead checked against the node's state,
+/// with `address` as the auction contract, and every failing constraint is
+/// printed.
+async fn submit(
+    builder: &EssentialBuilderClient,
+    node: &EssentialNodeClient,
+    solution: &Solution,
+    address: &token::ContractAddresses,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    if !dry_run {
+        return Ok(builder.submit_solution(solution).await?);
+    }
+    let contracts = token::dry_run::Contracts::in_use(address)?;
+    let report = token::dry_run::check(node.clone(), &contracts, solution).await?;
+    print!("{}", report);
+    if !report.passed() {
+        bail!("Dry run failed, the solution was not sent");
+    }
+    Ok(essential_hash::content_addr(solution))
+}
+
+async fn make_offer(
+    mut wallet: Wallet,
+    args: MakeOffer,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let MakeOffer {
+        buyer_account,
+        item_id,
         amount,
         node_api,
         builder_api,
         pint_directory,
+        payment_token,
     } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &buyer_account)?;
+    let payment_token = match payment_token {
+        Some(payment_token) => parse_address(&payment_token)?,
+        None => token::token::ADDRESS,
+    };
+
+    let solution = make_offer_solution(
+        &node,
+        &address.contract,
+        hashed_key,
+        item_id,
+        amount,
+        payment_token,
+        |to_sign| sign(&mut wallet, to_sign, &buyer_account),
+    )
+    .await?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+/// Reads the state for an offer and builds it, signed by `sign`.
+async fn make_offer_solution(
+    node: &EssentialNodeClient,
+    contract: &ContentAddress,
+    hashed_key: [Word; 4],
+    item_id: Word,
+    amount: Word,
+    payment_token: ContentAddress,
+    sign: impl FnOnce(&[Word]) -> anyhow::Result<RecoverableSignature>,
+) -> anyhow::Result<Solution> {
+    let offer_key = token::offer_key(item_id, hashed_key);
+    let offer = node.query_state(contract.clone(), offer_key).await?;
+
+    let offer_count_key = token::offer_count_key(item_id);
+    let offer_count = node.query_state(contract.clone(), offer_count_key).await?;
+
+    let balance_key = token::balance_key(hashed_key);
+    let balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    let balance_key = token::balance_key(token::escrow_key(contract));
+    let escrow_balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    let nonce_key = token::offer_nonce_key(hashed_key);
+    let nonce = node.query_state(contract.clone(), nonce_key).await?;
+
+    let to_sign = token::make_offer::data_to_sign(
+        item_id,
+        amount,
+        &payment_token,
+        token::offer(Query(nonce.clone()))?,
+    );
+    let signature = sign(&to_sign)?;
+
+    let build_solution = token::make_offer::BuildSolution {
+        item_id,
+        hashed_key,
+        amount,
+        payment_token,
+        current_offer: Query(offer),
+        current_offer_count: Query(offer_count),
+        current_balance: Query(balance),
+        current_escrow_balance: Query(escrow_balance),
+        current_nonce: Query(nonce),
+        signature,
+        contract: contract.clone(),
+    };
+    Ok(token::make_offer::build_solution(build_solution)?)
+}
 
+async fn accept_offer(
+    mut wallet: Wallet,
+    args: AcceptOffer,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let AcceptOffer {
+        owner_account,
+        item_id,
+        buyer,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
     let node = EssentialNodeClient::new(node_api)?;
     let builder = EssentialBuilderClient::new(builder_api)?;
-    let address = compile_address(pint_directory).await?;
-    let hashed_from_key = hash_key(&mut wallet, &bidder_account);
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &owner_account)?;
+    let hashed_buyer_key = word_4_from_u8_32(
+        hex::decode(buyer)?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Buyer key too large"))?,
+    );
 
-    let item_owner_key = token::get_item_owner_key(item_id);
+    let solution = accept_offer_solution(
+        &node,
+        &address.contract,
+        hashed_key,
+        item_id,
+        hashed_buyer_key,
+        |to_sign| sign(&mut wallet, to_sign, &owner_account),
+    )
+    .await?;
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
 
-    let hashed_to_key_vec = node
-        .query_state(address.contract.clone(), item_owner_key)
-        .await?.unwrap();
+/// Reads the state for accepting an offer and builds it, signed by `sign`.
+async fn accept_offer_solution(
+    node: &EssentialNodeClient,
+    contract: &ContentAddress,
+    hashed_key: [Word; 4],
+    item_id: Word,
+    hashed_buyer_key: [Word; 4],
+    sign: impl FnOnce(&[Word]) -> anyhow::Result<RecoverableSignature>,
+) -> anyhow::Result<Solution> {
+    let offer_key = token::offer_key(item_id, hashed_buyer_key);
+    let offer = node.query_state(contract.clone(), offer_key).await?;
 
-    let hashed_to_key: [i64; 4] = match hashed_to_key_vec[..] {
-        [a, b, c, d] => [a, b, c, d],
-        _ => panic!("Expected a Vec with exactly 4 elements"),
-    };    
-    
-    let balance_key = token::balance_key(hashed_from_key);
-    let from_balance = node
-        .query_state(address.contract.clone(), balance_key)
-        .await?.unwrap();
+    let offer_token_key = token::offer_token_key(item_id, hashed_buyer_key);
+    let payment_token = node.query_state(contract.clone(), offer_token_key).await?;
+    let payment_token = token::payment_token(Query(payment_token))?;
+
+    let balance_key = token::balance_key(hashed_key);
+    let balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
 
+    let balance_key = token::balance_key(token::escrow_key(contract));
+    let escrow_balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
 
-    let balance_key = token::balance_key(hashed_to_key);
-    let to_balance = node
-        .query_state(address.contract.clone(), balance_key)
+    let nonce_key = token::offer_nonce_key(hashed_key);
+    let nonce = node.query_state(contract.clone(), nonce_key).await?;
+
+    let amount = token::offer(Query(offer.clone()))?;
+    let to_sign = token::accept_offer::data_to_sign(
+        item_id,
+        hashed_buyer_key,
+        amount,
+        token::offer(Query(nonce.clone()))?,
+    );
+    let signature = sign(&to_sign)?;
+
+    // The royalty on items in a collection stays in escrow.
+    let collection = node
+        .query_state(contract.clone(), token::item_collection_key(item_id))
         .await?;
-    
-        
-    let cost_key = token::cost_key(item_id);
-    let current_cost = node
-        .query_state(address.contract.clone(), cost_key)
+    let collection_id = token::item_collection(Query(collection.clone()))?.unwrap_or_default();
+    let royalty_rate = node
+        .query_state(contract.clone(), token::collection_royalty_key(collection_id))
+        .await?;
+    let royalties = node
+        .query_state(
+            contract.clone(),
+            token::collection_royalties_key(collection_id, &payment_token),
+        )
+        .await?;
+
+    let build_solution = token::accept_offer::BuildSolution {
+        item_id,
+        hashed_key,
+        hashed_buyer_key,
+        payment_token,
+        current_offer: Query(offer),
+        current_balance: Query(balance),
+        current_escrow_balance: Query(escrow_balance),
+        current_nonce: Query(nonce),
+        signature,
+        current_collection: Query(collection),
+        current_royalty_rate: Query(royalty_rate),
+        current_royalties: Query(royalties),
+        contract: contract.clone(),
+    };
+    Ok(token::accept_offer::build_solution(build_solution)?)
+}
+
+async fn withdraw_offer(
+    mut wallet: Wallet,
+    args: WithdrawOffer,
+    dry_run: bool,
+) -> anyhow::Result<ContentAddress> {
+    let WithdrawOffer {
+        buyer_account,
+        item_id,
+        node_api,
+        builder_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let builder = EssentialBuilderClient::new(builder_api)?;
+    let address = contract_addresses(&pint_directory)?;
+    let hashed_key = hash_key(&mut wallet, &buyer_account)?;
+
+    let solution =
+        withdraw_offer_solution(&node, &address.contract, hashed_key, item_id, |to_sign| {
+            sign(&mut wallet, to_sign, &buyer_account)
+        })
         .await?;
-        
+    let ca = submit(&builder, &node, &solution, &address, dry_run).await?;
+    Ok(ca)
+}
+
+/// Reads the state for withdrawing an offer and builds it, signed by `sign`.
+/// Reads the balances and nonce for a transfer signed by the sender and builds it.
+async fn signed_transfer_solution(
+    node: &EssentialNodeClient,
+    contract: &ContentAddress,
+    hashed_from_key: [Word; 4],
+    hashed_to_key: [Word; 4],
+    amount: Word,
+    sign: impl FnOnce(&[Word]) -> anyhow::Result<RecoverableSignature>,
+) -> anyhow::Result<Solution> {
+    let balance_key = token::balance_key(hashed_from_key);
+    let from_balance = node.query_state(contract.clone(), balance_key).await?;
+    let balance_key = token::balance_key(hashed_to_key);
+    let to_balance = node.query_state(contract.clone(), balance_key).await?;
+    let nonce_key = token::transfer_nonce_key(hashed_from_key);
+    let nonce = node.query_state(contract.clone(), nonce_key).await?;
+
+    let to_sign = token::signed_transfer::data_to_sign(
+        hashed_to_key,
+        amount,
+        token::offer(Query(nonce.clone()))?,
+    );
+    let signature = sign(&to_sign)?;
 
-    let build_solution = token::place_bid::BuildSolution {
-        item_id: item_id,
+    let build_solution = token::signed_transfer::BuildSolution {
         hashed_from_key,
         hashed_to_key,
-        amount: amount,
-        current_from_balance: Query(Some(from_balance)),
+        amount,
+        current_from_balance: Query(from_balance),
         current_to_balance: Query(to_balance),
-        current_cost: Query(current_cost),
+        current_nonce: Query(nonce),
+        signature,
     };
-    let solution = token::place_bid::build_solution(build_solution)?;
-    let ca = builder.submit_solution(&solution).await?;
-    Ok(ca)
+    let data = token::signed_transfer::build_solution_data(contract.clone(), build_solution)?;
+    Ok(Solution { data: vec![data] })
+}
+
+async fn withdraw_offer_solution(
+    node: &EssentialNodeClient,
+    contract: &ContentAddress,
+    hashed_key: [Word; 4],
+    item_id: Word,
+    sign: impl FnOnce(&[Word]) -> anyhow::Result<RecoverableSignature>,
+) -> anyhow::Result<Solution> {
+    let offer_key = token::offer_key(item_id, hashed_key);
+    let offer = node.query_state(contract.clone(), offer_key).await?;
+
+    let offer_token_key = token::offer_token_key(item_id, hashed_key);
+    let payment_token = node.query_state(contract.clone(), offer_token_key).await?;
+    let payment_token = token::payment_token(Query(payment_token))?;
+
+    let balance_key = token::balance_key(hashed_key);
+    let balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    let balance_key = token::balance_key(token::escrow_key(contract));
+    let escrow_balance = node
+        .query_state(payment_token.clone(), balance_key)
+        .await?;
+
+    let nonce_key = token::offer_nonce_key(hashed_key);
+    let nonce = node.query_state(contract.clone(), nonce_key).await?;
+
+    let to_sign = token::withdraw_offer::data_to_sign(
+        item_id,
+        hashed_key,
+        token::offer(Query(nonce.clone()))?,
+    );
+    let signature = sign(&to_sign)?;
+
+    let build_solution = token::withdraw_offer::BuildSolution {
+        item_id,
+        hashed_key,
+        payment_token,
+        current_offer: Query(offer),
+        current_balance: Query(balance),
+        current_escrow_balance: Query(escrow_balance),
+        current_nonce: Query(nonce),
+        signature,
+        contract: contract.clone(),
+    };
+    Ok(token::withdraw_offer::build_solution(build_solution)?)
+}
+
+async fn list_offers(args: ListOffers) -> anyhow::Result<()> {
+    let ListOffers {
+        item_id,
+        node_api,
+        pint_directory,
+    } = args;
+    let node = EssentialNodeClient::new(node_api)?;
+    let address = contract_addresses(&pint_directory)?;
+
+    let offer_count_key = token::offer_count_key(item_id);
+    let offer_count = node
+        .query_state(address.contract.clone(), offer_count_key)
+        .await?;
+    let offer_count = token::offer(Query(offer_count))?;
+
+    for index in 0..offer_count {
+        let offer_buyer_key = token::offer_buyer_key(item_id, index);
+        let buyer = node
+            .query_state(address.contract.clone(), offer_buyer_key)
+            .await?;
+        let buyer = token::offer_buyer(Query(buyer))?;
+
+        let offer_key = token::offer_key(item_id, buyer);
+        let offer = node
+            .query_state(address.contract.clone(), offer_key)
+            .await?;
+        let offer = token::offer(Query(offer))?;
+
+        // Accepted and withdrawn offers stay in the list with a zero amount.
+        if offer == 0 {
+            continue;
+        }
+
+        let offer_token_key = token::offer_token_key(item_id, buyer);
+        let payment_token = node
+            .query_state(address.contract.clone(), offer_token_key)
+            .await?;
+        let payment_token = token::payment_token(Query(payment_token))?;
+
+        println!(
+            "Buyer: {:?}\nAmount: {:?}\nPayment token: {:?}\n",
+            to_hex_string(buyer.to_vec()),
+            offer,
+            to_hex_string(token::address_words(&payment_token).to_vec())
+        );
+    }
+    Ok(())
 }
 
 async fn get_balance(
@@ -485,7 +5303,7 @@ async fn get_balance(
     node_api: String,
     pint_directory: PathBuf,
 ) -> anyhow::Result<Word> {
-    let address = compile_address(pint_directory).await?;
+    let address = token_addresses(&pint_directory)?;
     let node = EssentialNodeClient::new(node_api)?;
 
     let balance_key = token::balance_key(hashed_key);
@@ -495,16 +5313,58 @@ async fn get_balance(
     token::balance(Query(balance))
 }
 
-/// Compiles the contract and returns its address.
-async fn compile_address(pint_directory: PathBuf) -> Result<PredicateAddress, anyhow::Error> {
-    let counter = compile_pint_project(pint_directory).await?;
-    let contract_address = essential_hash::contract_addr::from_contract(&counter);
-    let predicate_address = essential_hash::content_addr(&counter.predicates[0]);
-    let predicate_address = PredicateAddress {
-        contract: contract_address,
-        predicate: predicate_address,
+/// Signs the words with the account's key.
+fn sign(
+    wallet: &mut Wallet,
+    words: &[Word],
+    account_name: &str,
+) -> anyhow::Result<RecoverableSignature> {
+    let Signature::Secp256k1(sig) = wallet.sign_words(words, account_name)? else {
+        bail!("Invalid signature")
+    };
+    Ok(sig)
+}
+
+/// Reads the intents in a pool file. A missing file is an empty pool.
+fn read_intent_pool(pool: &std::path::Path) -> anyhow::Result<Vec<token::bid_intent::BidIntent>> {
+    if !pool.exists() {
+        return Ok(Vec::new());
+    }
+    let pool: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(pool)?)?;
+    let Some(intents) = pool.as_array() else {
+        bail!("Intent pool must be a list of intents");
     };
-    Ok(predicate_address)
+    intents
+        .iter()
+        .map(token::bid_intent::BidIntent::from_json)
+        .collect()
+}
+
+/// Writes the intents to a pool file.
+fn write_intent_pool(
+    pool: &std::path::Path,
+    intents: &[token::bid_intent::BidIntent],
+) -> anyhow::Result<()> {
+    let intents: Vec<_> = intents.iter().map(|intent| intent.to_json()).collect();
+    std::fs::write(pool, serde_json::to_string_pretty(&intents)?)?;
+    Ok(())
+}
+
+/// Parses a hex encoded hashed key.
+fn parse_key(key: &str) -> anyhow::Result<[Word; 4]> {
+    Ok(word_4_from_u8_32(
+        hex::decode(key)?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Key must be 32 bytes"))?,
+    ))
+}
+
+/// Parses a hex encoded contract address.
+fn parse_address(address: &str) -> anyhow::Result<ContentAddress> {
+    let address: [u8; 32] = hex::decode(address.trim_start_matches("0x"))?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Contract address must be 32 bytes"))?;
+    Ok(ContentAddress(address))
 }
 
 fn to_hex_string(vec: Vec<i64>) -> String {
@@ -0,0 +1,125 @@
+//! # Make Offer
+//! Contains functionality for making an escrowed offer on an item that is not for sale.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    address_words, deployed_at, escrow_key, offer, signature_words, transfer, validate,
+    AuctionError, Query,
+};
+
+/// Contains all necessary information to build a make offer solution.
+pub struct BuildSolution {
+    /// The item the offer is for.
+    pub item_id: Word,
+    /// The hashed key of the buyer.
+    pub hashed_key: [Word; 4],
+    /// The amount of tokens offered.
+    pub amount: Word,
+    /// The token contract the offer is escrowed in.
+    pub payment_token: ContentAddress,
+    /// The buyer's current offer on the item.
+    pub current_offer: Query,
+    /// The current number of offers on the item.
+    pub current_offer_count: Query,
+    /// The current balance of the buyer.
+    pub current_balance: Query,
+    /// The current balance of the escrow.
+    pub current_escrow_balance: Query,
+    /// The buyer's current offer nonce.
+    pub current_nonce: Query,
+    /// The buyer's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The words the buyer signs to authorize an offer.
+///
+/// The nonce is the buyer's current offer nonce, so the signature can't be
+/// used again.
+pub fn data_to_sign(
+    item_id: Word,
+    amount: Word,
+    payment_token: &ContentAddress,
+    nonce: Word,
+) -> Vec<Word> {
+    let mut words = vec![item_id, amount];
+    words.extend(address_words(payment_token));
+    words.push(nonce);
+    words
+}
+
+/// Builds a make offer solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        amount,
+        payment_token,
+        current_offer,
+        current_offer_count,
+        current_balance,
+        current_escrow_balance,
+        current_nonce,
+        signature,
+        contract,
+    } = build;
+
+    validate::make_offer(offer(current_offer)?, amount)?;
+    let index = offer(current_offer_count)?;
+    let offer_count = index.checked_add(1).ok_or(AuctionError::Overflow)?;
+    let nonce = offer(current_nonce)?;
+    let next_nonce = nonce.checked_add(1).ok_or(AuctionError::Overflow)?;
+
+    let pub_vars = super::auction::MakeOffer::PubVars {
+        item_id,
+        buyer: hashed_key,
+        amount,
+        payment_token: address_words(&payment_token),
+        index,
+        nonce,
+    };
+    let vars = super::auction::MakeOffer::Vars {
+        sig: signature_words(&signature),
+        transfer_pathway: TRANSFER_PATHWAY,
+    };
+    let mutations = super::auction::storage::mutations()
+        .offers(|map| map.entry(item_id, |map| map.entry(hashed_key, amount)))
+        .offer_token(|map| {
+            map.entry(item_id, |map| {
+                map.entry(hashed_key, address_words(&payment_token))
+            })
+        })
+        .offer_buyers(|map| map.entry(item_id, |map| map.entry(index, hashed_key)))
+        .offer_count(|map| map.entry(item_id, offer_count))
+        .offer_nonces(|map| map.entry(hashed_key, next_nonce));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::MakeOffer::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+
+    // The offer is escrowed through the transfer predicate of the token.
+    let transfer = transfer::build_solution_data(
+        payment_token,
+        transfer::BuildSolution {
+            hashed_from_key: hashed_key,
+            hashed_to_key: escrow_key(&contract),
+            amount,
+            current_from_balance: current_balance,
+            current_to_balance: current_escrow_balance,
+            authority: 0,
+        },
+    )?;
+    Ok(Solution {
+        data: vec![solution, transfer],
+    })
+}
+
+/// The index of the escrow transfer within an offer solution.
+const TRANSFER_PATHWAY: Word = 1;
@@ -9,7 +9,10 @@ use essential_types::{
     Word,
 };
 
-use crate::{balance, Query};
+use crate::{
+    action::{SolutionBuilder, State, StateKey},
+    balance, balance_key, validate, AuctionError, Query,
+};
 
 /// details
 pub struct BuildSolution {
@@ -22,12 +25,13 @@ pub struct BuildSolution {
 }
 
 /// Builds a mint solution based on the provided data.
-pub fn build_solution(build: BuildSolution) -> anyhow::Result<Solution> {
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
     let BuildSolution {
         current_balance,
         hashed_key,
         amount,
     } = build;
+    validate::mint(current_balance.as_opt_int()?, amount)?;
     let balance = calculate_new_balance(balance(current_balance)?, amount)?;
     let pub_vars = super::token::Mint::PubVars {
         key: hashed_key,
@@ -46,9 +50,29 @@ pub fn build_solution(build: BuildSolution) -> anyhow::Result<Solution> {
     })
 }
 
+/// Mints tokens to an account.
+pub struct Mint {
+    /// The hashed key of the account.
+    pub hashed_key: [Word; 4],
+    /// The amount of tokens to mint.
+    pub amount: Word,
+}
+
+impl SolutionBuilder for Mint {
+    fn required_keys(&self) -> Vec<StateKey> {
+        vec![(super::token::ADDRESS, balance_key(self.hashed_key))]
+    }
+
+    fn build(&self, state: &State) -> Result<Solution, AuctionError> {
+        build_solution(BuildSolution {
+            current_balance: state.get(&super::token::ADDRESS, &balance_key(self.hashed_key)),
+            hashed_key: self.hashed_key,
+            amount: self.amount,
+        })
+    }
+}
+
 /// Calculates the new balance after minting tokens.
-fn calculate_new_balance(balance: Word, amount: Word) -> anyhow::Result<Word> {
-    balance
-        .checked_add(amount)
-        .ok_or(anyhow::anyhow!("Insufficient balance"))
+fn calculate_new_balance(balance: Word, amount: Word) -> Result<Word, AuctionError> {
+    balance.checked_add(amount).ok_or(AuctionError::Overflow)
 }
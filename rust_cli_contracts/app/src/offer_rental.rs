@@ -0,0 +1,81 @@
+//! # Offer Rental
+//! Contains functionality for an owner setting the terms their item is leased on.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{deployed_at, item_owner, offer, signature_words, validate, AuctionError, Query};
+
+/// Contains all necessary information to build an offer rental solution.
+pub struct BuildSolution {
+    /// The item being offered for rent.
+    pub item_id: Word,
+    /// The hashed key of the owner.
+    pub hashed_key: [Word; 4],
+    /// The price of one lease.
+    pub price: Word,
+    /// The number of blocks a lease lasts.
+    pub blocks: Word,
+    /// The current owner of the item.
+    pub current_owner: Query,
+    /// The owner's current nonce.
+    pub current_nonce: Query,
+    /// The owner's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The words the owner signs to set the terms of a lease.
+///
+/// The nonce is the owner's current nonce.
+pub fn data_to_sign(item_id: Word, price: Word, blocks: Word, nonce: Word) -> Vec<Word> {
+    vec![item_id, price, blocks, nonce, OFFER_RENTAL]
+}
+
+/// Builds an offer rental solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        price,
+        blocks,
+        current_owner,
+        current_nonce,
+        signature,
+        contract,
+    } = build;
+
+    validate::offer_rental(item_owner(current_owner)?, hashed_key, price, blocks)?;
+    let nonce = offer(current_nonce)?;
+    let next_nonce = nonce.checked_add(1).ok_or(AuctionError::Overflow)?;
+
+    let pub_vars = super::auction::OfferRental::PubVars {
+        item_id,
+        key: hashed_key,
+        price,
+        blocks,
+        nonce,
+    };
+    let vars = super::auction::OfferRental::Vars {
+        sig: signature_words(&signature),
+    };
+    let mutations = super::auction::storage::mutations()
+        .rental_price(|map| map.entry(item_id, price))
+        .rental_blocks(|map| map.entry(item_id, blocks))
+        .nonces(|map| map.entry(hashed_key, next_nonce));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::OfferRental::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
+
+/// Tags a signature as the terms of a lease.
+const OFFER_RENTAL: Word = 9;
@@ -0,0 +1,77 @@
+//! # Open Dispute
+//! Contains functionality for a buyer disputing a sale held in escrow.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    cost, deployed_at, escrow_buyer, signature_words, validate, AuctionError, Query,
+    ESCROW_DISPUTED,
+};
+
+/// Contains all necessary information to build an open dispute solution.
+pub struct BuildSolution {
+    /// The item being disputed.
+    pub item_id: Word,
+    /// The hashed key of the buyer.
+    pub hashed_key: [Word; 4],
+    /// The buyer of the held sale.
+    pub current_buyer: Query,
+    /// The escrow status of the sale.
+    pub current_escrow_status: Query,
+    /// The number of the item's sales held in escrow, which names this sale.
+    pub current_escrow_sale: Query,
+    /// The buyer's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The words the buyer signs to open a dispute.
+pub fn data_to_sign(item_id: Word, sale: Word) -> Vec<Word> {
+    vec![item_id, sale, DISPUTE]
+}
+
+/// Builds an open dispute solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        current_buyer,
+        current_escrow_status,
+        current_escrow_sale,
+        signature,
+        contract,
+    } = build;
+
+    validate::open_dispute(
+        escrow_buyer(current_buyer)?,
+        hashed_key,
+        cost(current_escrow_status)?,
+    )?;
+
+    let pub_vars = super::auction::OpenDispute::PubVars {
+        item_id,
+        buyer: hashed_key,
+        sale: cost(current_escrow_sale)?,
+    };
+    let vars = super::auction::OpenDispute::Vars {
+        sig: signature_words(&signature),
+    };
+    let mutations = super::auction::storage::mutations()
+        .escrow_status(|map| map.entry(item_id, ESCROW_DISPUTED));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::OpenDispute::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
+
+/// Tags a signature as a dispute.
+const DISPUTE: Word = 1;
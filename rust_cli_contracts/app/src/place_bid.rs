@@ -1,19 +1,22 @@
-//! # Mint
-//! Contains functionality for minting new tokens in the token contract.
- #![allow(unused)]  
-use essential_app_utils::inputs::Encode;
-use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+//! # Place Bid
+//! Contains functionality for bidding on Dutch auctions in the auction contract.
 use essential_types::{
-    convert::word_4_from_u8_32,
     solution::{Solution, SolutionData},
-    Word,
+    ContentAddress, Word,
 };
 
-use crate::{balance, cost, Query};
+use crate::{
+    action::{SolutionBuilder, State, StateKey},
+    address_words, arbiter, arbiter_key, balance_key,
+    buy::royalty,
+    collection_royalties_key, collection_royalty_key, cost, cost_key, deployed_at, escrow_key,
+    escrow_sale_key, item_collection, item_collection_key, item_owner, proceeds_key, transfer,
+    AuctionError, Query, ESCROW_HELD,
+};
 
-/// details
+/// Contains all necessary information to build a bid solution.
 pub struct BuildSolution {
-    /// The current balance of the account.
+    /// The item being bid on.
     pub item_id: Word,
     /// The hashed key of the sender.
     pub hashed_from_key: [Word; 4],
@@ -24,13 +27,39 @@ pub struct BuildSolution {
     /// The current balance of the sender.
     pub current_from_balance: Query,
     /// The current balance of the recipient.
+    /// This is the escrow's balance when the item has an arbiter or is in a
+    /// collection.
     pub current_to_balance: Query,
-    /// The current balance of the sender.
+    /// The current cost of the item.
     pub current_cost: Query,
+    /// The token contract the item is priced in.
+    pub payment_token: ContentAddress,
+    /// The arbiter of the item, if its sale is held in escrow.
+    pub current_arbiter: Query,
+    /// The number of the item's sales held in escrow so far.
+    pub current_escrow_sale: Query,
+    /// The collection the item is registered in.
+    pub current_collection: Query,
+    /// The royalty rate of the item's collection in basis points.
+    pub current_royalty_rate: Query,
+    /// The royalties owed to the item's collection in the payment token.
+    pub current_royalties: Query,
+    /// The unclaimed proceeds of the item's last sale.
+    pub current_proceeds: Query,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
 }
 
-/// Builds a mint solution based on the provided data.
-pub fn build_solution(build: BuildSolution) -> anyhow::Result<Solution> {
+/// Builds a bid solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    build_solution_at(build, 0)
+}
+
+/// Builds a bid solution that starts at `offset` within a larger solution,
+/// so the bid still points at its own token transfer.
+pub fn build_solution_at(build: BuildSolution, offset: usize) -> Result<Solution, AuctionError> {
     let BuildSolution {
         item_id,
         hashed_from_key,
@@ -39,46 +68,363 @@ pub fn build_solution(build: BuildSolution) -> anyhow::Result<Solution> {
         current_from_balance,
         current_to_balance,
         current_cost,
+        payment_token,
+        current_arbiter,
+        current_escrow_sale,
+        current_collection,
+        current_royalty_rate,
+        current_royalties,
+        current_proceeds,
+        block_state,
+        contract,
     } = build;
 
-    let _cost = calculate_new_balance_sub(cost(current_cost)?, amount)?;
-    let from_balance = calculate_new_balance_sub(balance(current_from_balance)?, amount)?;
-    let to_balance = calculate_new_balance_add(balance(current_to_balance)?, amount)?;
-    
-    let pub_vars = super::token::PlaceBid::PubVars {
+    let cost = cost(current_cost)?;
+    if amount < cost {
+        return Err(AuctionError::PriceBelowCost {
+            price: amount,
+            cost,
+        }
+        .into());
+    }
+    let available = current_from_balance.as_opt_int()?.unwrap_or_default();
+    if available < amount {
+        return Err(AuctionError::InsufficientBalance {
+            available,
+            required: amount,
+        }
+        .into());
+    }
+
+    let collection = item_collection(current_collection)?;
+    let payment_token_words = address_words(&payment_token);
+    let pub_vars = super::auction::PlaceBid::PubVars {
         item_id: item_id,
         bidder: hashed_from_key,
-        amount
+        amount,
+        payment_token: payment_token_words,
+        collection: collection.unwrap_or_default(),
+        block_state: address_words(&block_state),
+    };
+    let vars = super::auction::PlaceBid::Vars {
+        transfer_pathway: TRANSFER_PATHWAY + offset as Word,
     };
-    let mutations = super::token::storage::mutations()
-        .balances(|map| map.entry(hashed_from_key, from_balance))
-        .balances(|map| map.entry(hashed_to_key, to_balance))
+    let mut mutations = super::auction::storage::mutations()
         .item_owner(|map| map.entry(item_id, hashed_from_key))
         .auction_ended(|map| map.entry(item_id, true))
         .cost(|map| map.entry(item_id, amount));
-    let solution = SolutionData {
-        predicate_to_solve: super::token::PlaceBid::ADDRESS,
-        decision_variables: Default::default(),
+    let escrowed = arbiter(current_arbiter)?.is_some();
+    let mut held = amount;
+    if let Some(collection) = collection {
+        // The creator's royalty is split off before the seller is paid.
+        let royalty = royalty(amount, crate::cost(current_royalty_rate)?)?;
+        let royalties = crate::cost(current_royalties)? + royalty;
+        held = amount - royalty;
+        mutations = mutations.collection_royalties(|map| {
+            map.entry(collection, |map| map.entry(payment_token_words, royalties))
+        });
+        if !escrowed {
+            // The seller claims the rest of the bid from escrow.
+            if crate::cost(current_proceeds)? != 0 {
+                rejected!("The seller has not claimed the proceeds of the last sale");
+            }
+            mutations = mutations
+                .proceeds(|map| map.entry(item_id, held))
+                .proceeds_seller(|map| map.entry(item_id, hashed_to_key));
+        }
+    }
+    if escrowed {
+        // The sale is held until delivery is confirmed or the arbiter rules.
+        let sale = current_escrow_sale
+            .as_opt_int()?
+            .unwrap_or_default()
+            .checked_add(1)
+            .ok_or(AuctionError::Overflow)?;
+        mutations = mutations
+            .escrow_sale(|map| map.entry(item_id, sale))
+            .escrow_status(|map| map.entry(item_id, ESCROW_HELD))
+            .escrow_amount(|map| map.entry(item_id, held))
+            .escrow_seller(|map| map.entry(item_id, hashed_to_key))
+            .escrow_buyer(|map| map.entry(item_id, hashed_from_key));
+    }
+    let bid = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::PlaceBid::ADDRESS, &contract),
+        decision_variables: vars.into(),
         transient_data: pub_vars.into(),
         state_mutations: mutations.into(),
     };
+
+    // The payment goes through the transfer predicate of the item's token.
+    let transfer = transfer::build_solution_data(
+        payment_token,
+        transfer::BuildSolution {
+            hashed_from_key,
+            hashed_to_key: if escrowed || collection.is_some() {
+                escrow_key(&contract)
+            } else {
+                hashed_to_key
+            },
+            amount,
+            current_from_balance,
+            current_to_balance,
+            authority: offset as Word,
+        },
+    )?;
     Ok(Solution {
-        data: vec![solution],
+        data: vec![bid, transfer],
     })
 }
 
+/// The index of the token transfer within a bid solution.
+const TRANSFER_PATHWAY: Word = 1;
 
-/// Calculates the new balance after minting tokens.
-fn calculate_new_balance_add(balance: Word, amount: Word) -> anyhow::Result<Word> {
-    balance
-        .checked_add(amount)
-        .ok_or(anyhow::anyhow!("Insufficient balance"))
+/// Bids on a Dutch auction, buying the item.
+pub struct PlaceBid {
+    /// The item being bid on.
+    pub item_id: Word,
+    /// The hashed key of the bidder.
+    pub hashed_from_key: [Word; 4],
+    /// The hashed key of the owner.
+    pub hashed_to_key: [Word; 4],
+    /// The amount of the bid.
+    pub amount: Word,
+    /// The token contract the item is priced in.
+    pub payment_token: ContentAddress,
+    /// The collection the item is registered in, if any.
+    pub collection: Option<Word>,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
 }
 
-/// Calculates the new balance after minting tokens.
-fn calculate_new_balance_sub(balance: Word, amount: Word) -> anyhow::Result<Word> {
-    balance
-        .checked_sub(amount)
-        .ok_or(anyhow::anyhow!("Insufficient balance"))
+impl SolutionBuilder for PlaceBid {
+    /// Both the owner's and the escrow's balance are read, as which one is
+    /// paid depends on whether the item has an arbiter or is in a collection.
+    fn required_keys(&self) -> Vec<StateKey> {
+        let auction = self.contract.clone();
+        let token = &self.payment_token;
+        let collection = self.collection.unwrap_or_default();
+        vec![
+            (auction.clone(), cost_key(self.item_id)),
+            (auction.clone(), arbiter_key(self.item_id)),
+            (auction.clone(), escrow_sale_key(self.item_id)),
+            (auction.clone(), item_collection_key(self.item_id)),
+            (auction.clone(), collection_royalty_key(collection)),
+            (auction.clone(), collection_royalties_key(collection, token)),
+            (auction, proceeds_key(self.item_id)),
+            (token.clone(), balance_key(self.hashed_from_key)),
+            (token.clone(), balance_key(self.hashed_to_key)),
+            (token.clone(), balance_key(escrow_key(&self.contract))),
+        ]
+    }
+
+    fn build(&self, state: &State) -> Result<Solution, AuctionError> {
+        self.build_at(state, 0)
+    }
+
+    fn build_at(&self, state: &State, offset: usize) -> Result<Solution, AuctionError> {
+        let auction = &self.contract;
+        let token = &self.payment_token;
+        let current_arbiter = state.get(auction, &arbiter_key(self.item_id));
+        let to_key = match (current_arbiter.as_opt_b256()?, self.collection) {
+            (None, None) => self.hashed_to_key,
+            _ => escrow_key(auction),
+        };
+        let collection = self.collection.unwrap_or_default();
+        let build = BuildSolution {
+            item_id: self.item_id,
+            hashed_from_key: self.hashed_from_key,
+            hashed_to_key: self.hashed_to_key,
+            amount: self.amount,
+            current_from_balance: state.get(token, &balance_key(self.hashed_from_key)),
+            current_to_balance: state.get(token, &balance_key(to_key)),
+            current_cost: state.get(auction, &cost_key(self.item_id)),
+            payment_token: token.clone(),
+            current_arbiter,
+            current_escrow_sale: state.get(auction, &escrow_sale_key(self.item_id)),
+            current_collection: state.get(auction, &item_collection_key(self.item_id)),
+            current_royalty_rate: state.get(auction, &collection_royalty_key(collection)),
+            current_royalties: state.get(auction, &collection_royalties_key(collection, token)),
+            current_proceeds: state.get(auction, &proceeds_key(self.item_id)),
+            block_state: super::block_state::ADDRESS,
+            contract: auction.clone(),
+        };
+        build_solution_at(build, offset)
+    }
 }
 
+/// A single bid within a bundle.
+pub struct BundleItem {
+    /// The item to bid on.
+    pub item_id: Word,
+    /// The amount bid on the item.
+    pub amount: Word,
+    /// The current cost of the item.
+    pub current_cost: Query,
+    /// The current owner of the item.
+    pub current_owner: Query,
+    /// The arbiter of the item, which must not be set.
+    pub current_arbiter: Query,
+    /// The collection the item is registered in.
+    pub current_collection: Query,
+    /// The royalty rate of the item's collection in basis points.
+    pub current_royalty_rate: Query,
+    /// The royalties owed to the item's collection in the payment token.
+    pub current_royalties: Query,
+}
+
+/// Contains all necessary information to build a bundle of bids that are
+/// won or lost together.
+pub struct BuildBundle {
+    /// The hashed key of the bidder.
+    pub hashed_from_key: [Word; 4],
+    /// The bids in the bundle.
+    pub items: Vec<BundleItem>,
+    /// The token contract every item in the bundle is priced in.
+    pub payment_token: ContentAddress,
+    /// The current balance of the bidder.
+    pub current_from_balance: Query,
+    /// The current balance of the escrow.
+    pub current_escrow_balance: Query,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a single solution that bids on every item in the bundle.
+///
+/// The total is escrowed in one transfer and each seller claims their bid
+/// with [`crate::claim_proceeds`].
+pub fn build_bundle(build: BuildBundle) -> Result<Solution, AuctionError> {
+    build_bundle_at(build, 0)
+}
+
+/// Builds a bundle that starts at `offset` within a larger solution, so the
+/// bids still point at each other and at their escrow transfer.
+pub fn build_bundle_at(build: BuildBundle, offset: usize) -> Result<Solution, AuctionError> {
+    let BuildBundle {
+        hashed_from_key,
+        items,
+        payment_token,
+        current_from_balance,
+        current_escrow_balance,
+        block_state,
+        contract,
+    } = build;
+
+    if items.is_empty() {
+        rejected!("Bundle has no items");
+    }
+    let mut total: Word = 0;
+    for (i, item) in items.iter().enumerate() {
+        if items[..i].iter().any(|other| other.item_id == item.item_id) {
+            rejected!("Item {} is in the bundle twice", item.item_id);
+        }
+        total = total
+            .checked_add(item.amount)
+            .ok_or(AuctionError::Overflow)?;
+    }
+    let available = current_from_balance.as_opt_int()?.unwrap_or_default();
+    if available < total {
+        return Err(AuctionError::InsufficientBalance {
+            available,
+            required: total,
+        }
+        .into());
+    }
+
+    let payment_token_words = address_words(&payment_token);
+    let block_state = address_words(&block_state);
+    let transfer_pathway = (offset + items.len()) as Word;
+    let mut data = Vec::with_capacity(items.len() + 1);
+    let mut bid_offset: Word = 0;
+    let mut collections = Vec::with_capacity(items.len());
+    for (pathway, item) in items.into_iter().enumerate() {
+        let BundleItem {
+            item_id,
+            amount,
+            current_cost,
+            current_owner,
+            current_arbiter,
+            current_collection,
+            current_royalty_rate,
+            current_royalties,
+        } = item;
+        if arbiter(current_arbiter)?.is_some() {
+            rejected!("Item {} is sold in escrow and can't be bundled", item_id);
+        }
+        let cost = cost(current_cost)?;
+        if amount < cost {
+            return Err(AuctionError::PriceBelowCost {
+                price: amount,
+                cost,
+            }
+            .into());
+        }
+        let seller = item_owner(current_owner)?;
+        let collection = item_collection(current_collection)?;
+        // Each bid sets its collection's royalties outright, so two bids in
+        // one collection would overwrite each other's share.
+        if collection.is_some() && collections.contains(&collection) {
+            rejected!(
+                "Item {} is in the same collection as another item in the bundle",
+                item_id
+            );
+        }
+        collections.push(collection);
+
+        let pub_vars = super::auction::BundleBid::PubVars {
+            item_id,
+            bidder: hashed_from_key,
+            amount,
+            payment_token: payment_token_words,
+            offset: bid_offset,
+            total,
+            block_state,
+            collection: collection.unwrap_or_default(),
+        };
+        let vars = super::auction::BundleBid::Vars {
+            previous_pathway: (offset + pathway.saturating_sub(1)) as Word,
+            transfer_pathway,
+        };
+        let mut mutations = super::auction::storage::mutations()
+            .item_owner(|map| map.entry(item_id, hashed_from_key))
+            .auction_ended(|map| map.entry(item_id, true))
+            .cost(|map| map.entry(item_id, amount))
+            .proceeds_seller(|map| map.entry(item_id, seller));
+        match collection {
+            None => mutations = mutations.proceeds(|map| map.entry(item_id, amount)),
+            Some(collection) => {
+                // The creator's royalty is split off before the seller claims the rest.
+                let royalty = royalty(amount, crate::cost(current_royalty_rate)?)?;
+                let royalties = crate::cost(current_royalties)? + royalty;
+                mutations = mutations
+                    .proceeds(|map| map.entry(item_id, amount - royalty))
+                    .collection_royalties(|map| {
+                        map.entry(collection, |map| map.entry(payment_token_words, royalties))
+                    });
+            }
+        }
+        data.push(SolutionData {
+            predicate_to_solve: deployed_at(super::auction::BundleBid::ADDRESS, &contract),
+            decision_variables: vars.into(),
+            transient_data: pub_vars.into(),
+            state_mutations: mutations.into(),
+        });
+        bid_offset += amount;
+    }
+
+    // The whole bundle is escrowed through one transfer of the item's token.
+    data.push(transfer::build_solution_data(
+        payment_token,
+        transfer::BuildSolution {
+            hashed_from_key,
+            hashed_to_key: escrow_key(&contract),
+            amount: total,
+            current_from_balance,
+            current_to_balance: current_escrow_balance,
+            authority: offset as Word,
+        },
+    )?);
+    Ok(Solution { data })
+}
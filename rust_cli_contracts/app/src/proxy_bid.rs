@@ -0,0 +1,207 @@
+//! # Proxy Bid
+//! Contains functionality for revealing a sealed maximum in an ascending auction.
+//!
+//! Once bidding closes each bidder reveals the maximum they committed to, and
+//! the visible price only rises as far as needed to beat the next highest
+//! maximum. Until then only the deposits covering the maxima are public.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    address_words, commit_bid::commitment, cost, deployed_at, leader, validate, AuctionError, Query,
+};
+
+/// Where a proxy bid leaves the bidder once it is placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Standing {
+    /// The bidder leads at this visible price.
+    Winning(Word),
+    /// Another bidder's maximum is higher and leads at this visible price.
+    Outbid(Word),
+}
+
+/// Contains all necessary information to build a proxy bid solution.
+pub struct BuildSolution {
+    /// The item being bid on.
+    pub item_id: Word,
+    /// The hashed key of the bidder.
+    pub hashed_key: [Word; 4],
+    /// The maximum the bidder committed to.
+    pub max: Word,
+    /// The salt the maximum was sealed with.
+    pub salt: [Word; 4],
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The current block number.
+    pub current_block_number: Query,
+    /// The block number at which reveals close.
+    pub current_reveals_close: Query,
+    /// The current visible price of the item.
+    pub current_cost: Query,
+    /// The minimum raise of the auction.
+    pub current_increment: Query,
+    /// The current leading bidder.
+    pub current_leader: Query,
+    /// The revealed maximum of the current leading bidder.
+    pub current_leader_escrow: Query,
+    /// The current reclaimable escrow of the leading bidder.
+    pub current_leader_refund: Query,
+    /// The current reclaimable escrow of the bidder.
+    pub current_refund: Query,
+    /// The bidder's commitment to their maximum.
+    pub current_commitment: Query,
+    /// The bidder's deposit covering their maximum.
+    pub current_deposit: Query,
+    /// Whether the bidder has already revealed.
+    pub current_revealed: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The visible price when two maxima compete.
+///
+/// The higher maximum wins at just enough to beat the lower one,
+/// but never more than the winner's own maximum.
+pub fn visible_price(first_max: Word, second_max: Word, increment: Word) -> Word {
+    let high = first_max.max(second_max);
+    let low = first_max.min(second_max);
+    high.min(low.saturating_add(increment))
+}
+
+/// Where a revealed maximum of `max` would leave the bidder.
+///
+/// Ties go to the existing leader, as the contract requires a challenger
+/// to strictly beat the leader's maximum. The first maximum to cover the
+/// reserve leads at the reserve.
+pub fn standing(
+    current_cost: Word,
+    leader_max: Option<Word>,
+    max: Word,
+    increment: Word,
+) -> Standing {
+    match leader_max {
+        None if max >= current_cost => Standing::Winning(current_cost),
+        None => Standing::Outbid(current_cost),
+        Some(leader_max) if max > leader_max => {
+            Standing::Winning(visible_price(leader_max, max, increment))
+        }
+        Some(leader_max) => Standing::Outbid(visible_price(leader_max, max, increment)),
+    }
+}
+
+/// Builds a proxy bid solution based on the provided data.
+///
+/// A maximum that beats the leader takes the lead and frees the rest of its
+/// deposit, otherwise the bid raises the leader's visible price and frees
+/// the whole deposit. Once reveals close a maximum only frees its deposit.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        max,
+        salt,
+        block_state,
+        current_block_number,
+        current_reveals_close,
+        current_cost,
+        current_increment,
+        current_leader,
+        current_leader_escrow,
+        current_leader_refund,
+        current_refund,
+        current_commitment,
+        current_deposit,
+        current_revealed,
+        contract,
+    } = build;
+
+    let price = cost(current_cost)?;
+    let increment = cost(current_increment)?;
+    let leader = leader(current_leader)?;
+    let leader_max = match leader {
+        Some(_) => Some(cost(current_leader_escrow)?),
+        None => None,
+    };
+    let deposit = cost(current_deposit)?;
+    let revealed = current_revealed.as_opt_bool()?.unwrap_or_default();
+    validate::proxy_bid(
+        hashed_key,
+        max,
+        deposit,
+        leader,
+        current_commitment.as_opt_b256()?,
+        commitment(max, salt),
+        revealed,
+    )?;
+    let refund = cost(current_refund)?;
+    let late = cost(current_block_number)? >= cost(current_reveals_close)?;
+
+    let block_state = address_words(&block_state);
+    let solution = match standing(price, leader_max, max, increment) {
+        Standing::Winning(new_price) if !late => {
+            let previous_leader = leader.unwrap_or_default();
+            let pub_vars = super::auction::ProxyBid::PubVars {
+                item_id,
+                bidder: hashed_key,
+                max,
+                salt,
+                previous_leader,
+                block_state,
+            };
+            // The rest of the deposit can be reclaimed straight away.
+            let refund = refund
+                .checked_add(deposit - max)
+                .ok_or(AuctionError::Overflow)?;
+            let mut mutations = super::auction::storage::mutations()
+                .leader(|map| map.entry(item_id, hashed_key))
+                .leader_escrow(|map| map.entry(item_id, max))
+                .bid_revealed(|map| map.entry(item_id, |map| map.entry(hashed_key, true)))
+                .refunds(|map| map.entry(item_id, |map| map.entry(hashed_key, refund)));
+            if let (Some(previous_leader), Some(leader_max)) = (leader, leader_max) {
+                // The outbid leader can reclaim their escrowed maximum.
+                let refund = cost(current_leader_refund)? + leader_max;
+                mutations = mutations
+                    .cost(|map| map.entry(item_id, new_price))
+                    .refunds(|map| map.entry(item_id, |map| map.entry(previous_leader, refund)));
+            }
+            SolutionData {
+                predicate_to_solve: deployed_at(super::auction::ProxyBid::ADDRESS, &contract),
+                decision_variables: Default::default(),
+                transient_data: pub_vars.into(),
+                state_mutations: mutations.into(),
+            }
+        }
+        standing => {
+            let pub_vars = super::auction::RaiseBid::PubVars {
+                item_id,
+                bidder: hashed_key,
+                max,
+                salt,
+                block_state,
+            };
+            // Only a maximum above the price raises it, and a late reveal
+            // leaves the price where it is.
+            let new_price = match standing {
+                Standing::Outbid(new_price) if !late && max > price => new_price,
+                _ => price,
+            };
+            // The bidder does not lead, so their deposit can be reclaimed.
+            let refund = refund.checked_add(deposit).ok_or(AuctionError::Overflow)?;
+            let mutations = super::auction::storage::mutations()
+                .cost(|map| map.entry(item_id, new_price))
+                .bid_revealed(|map| map.entry(item_id, |map| map.entry(hashed_key, true)))
+                .refunds(|map| map.entry(item_id, |map| map.entry(hashed_key, refund)));
+            SolutionData {
+                predicate_to_solve: deployed_at(super::auction::RaiseBid::ADDRESS, &contract),
+                decision_variables: Default::default(),
+                transient_data: pub_vars.into(),
+                state_mutations: mutations.into(),
+            }
+        }
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
@@ -0,0 +1,122 @@
+//! # Redeem Shares
+//! Contains functionality for redeeming shares for their part of a buyout.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{cost, deployed_at, release, share_balance, validate, AuctionError, Query};
+
+/// Contains all necessary information to build a redeem shares solution.
+pub struct BuildSolution {
+    /// The bought out item.
+    pub item_id: Word,
+    /// The hashed key of the shareholder.
+    pub hashed_holder_key: [Word; 4],
+    /// The token contract the buyout was paid in.
+    pub payment_token: ContentAddress,
+    /// The number of shares the item was split into.
+    pub current_supply: Query,
+    /// The buyout price of the item.
+    pub current_reserve: Query,
+    /// The current shares of the shareholder.
+    pub current_shares: Query,
+    /// The shares redeemed since the buyout.
+    pub current_redeemed: Query,
+    /// The current balance of the shareholder.
+    pub current_holder_balance: Query,
+    /// The current balance of the escrow.
+    pub current_escrow_balance: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The part of a buyout paid for `shares` out of `supply` shares.
+///
+/// Rounds down, and splits the reserve the same way the contract does so
+/// the payout can't overflow for any supply the contract accepts.
+pub fn pro_rata(shares: Word, supply: Word, reserve: Word) -> Result<Word, AuctionError> {
+    if supply <= 0 {
+        rejected!("Item has no shares");
+    }
+    let whole = (reserve / supply).checked_mul(shares);
+    let part = (reserve % supply).checked_mul(shares);
+    match (whole, part) {
+        (Some(whole), Some(part)) => whole
+            .checked_add(part / supply)
+            .ok_or(AuctionError::Overflow),
+        _ => Err(AuctionError::Overflow),
+    }
+}
+
+/// Builds a redeem shares solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_holder_key,
+        payment_token,
+        current_supply,
+        current_reserve,
+        current_shares,
+        current_redeemed,
+        current_holder_balance,
+        current_escrow_balance,
+        contract,
+    } = build;
+
+    let shares = share_balance(current_shares)?;
+    validate::redeem_shares(shares)?;
+    let supply = cost(current_supply)?;
+    let payout = pro_rata(shares, supply, cost(current_reserve)?)?;
+    if payout <= 0 {
+        rejected!("Shares are worth less than one token");
+    }
+
+    let pub_vars = super::auction::RedeemShares::PubVars {
+        item_id,
+        holder: hashed_holder_key,
+    };
+    let vars = super::auction::RedeemShares::Vars {
+        release_pathway: RELEASE_PATHWAY,
+    };
+    let redeemed = cost(current_redeemed)?
+        .checked_add(shares)
+        .ok_or(AuctionError::Overflow)?;
+    let mut mutations = super::auction::storage::mutations()
+        .share_balances(|map| map.entry(item_id, |map| map.entry(hashed_holder_key, 0)));
+    if redeemed == supply {
+        // The last shares are redeemed, so the item can be split again.
+        mutations = mutations
+            .share_supply(|map| map.entry(item_id, 0))
+            .shares_redeemed(|map| map.entry(item_id, 0))
+            .bought_out(|map| map.entry(item_id, false));
+    } else {
+        mutations = mutations.shares_redeemed(|map| map.entry(item_id, redeemed));
+    }
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::RedeemShares::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+
+    // The shareholder's part is released from escrow.
+    let [release, withdraw] = release::build_solution_data(
+        release::BuildSolution {
+            settlement: super::auction::RedeemShares::ADDRESS,
+            payment_token,
+            hashed_to_key: hashed_holder_key,
+            amount: payout,
+            current_escrow_balance,
+            current_to_balance: current_holder_balance,
+            contract: contract.clone(),
+        },
+        RELEASE_PATHWAY,
+    )?;
+    Ok(Solution {
+        data: vec![solution, release, withdraw],
+    })
+}
+
+/// The index of the escrow release within a redeem shares solution.
+const RELEASE_PATHWAY: Word = 1;
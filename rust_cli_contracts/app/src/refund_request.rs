@@ -0,0 +1,86 @@
+//! # Refund Request
+//! Contains functionality for returning the unspent budget of a request to its buyer.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{address_words, cost, deployed_at, release, AuctionError, Query};
+
+/// Contains all necessary information to build a refund request solution.
+pub struct BuildSolution {
+    /// The item being requested.
+    pub item_id: Word,
+    /// The hashed key of the buyer.
+    pub hashed_buyer_key: [Word; 4],
+    /// The token contract the budget is escrowed in.
+    pub payment_token: ContentAddress,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The budget of the request.
+    pub current_budget: Query,
+    /// The current lowest ask on the request, if any.
+    pub current_best_ask: Query,
+    /// The current balance of the buyer.
+    pub current_buyer_balance: Query,
+    /// The current balance of the escrow.
+    pub current_escrow_balance: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a refund request solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_buyer_key,
+        payment_token,
+        block_state,
+        current_budget,
+        current_best_ask,
+        current_buyer_balance,
+        current_escrow_balance,
+        contract,
+    } = build;
+
+    // Without any asks the whole budget is refunded.
+    let refund = cost(current_budget)?
+        .checked_sub(cost(current_best_ask)?)
+        .ok_or(AuctionError::Overflow)?;
+
+    let pub_vars = super::auction::RefundRequest::PubVars {
+        item_id,
+        block_state: address_words(&block_state),
+    };
+    let vars = super::auction::RefundRequest::Vars {
+        release_pathway: RELEASE_PATHWAY,
+    };
+    let mutations = super::auction::storage::mutations()
+        .request_refunded(|map| map.entry(item_id, true));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::RefundRequest::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+
+    // The unspent budget is returned out of the escrow.
+    let [release, withdraw] = release::build_solution_data(
+        release::BuildSolution {
+            settlement: super::auction::RefundRequest::ADDRESS,
+            payment_token,
+            hashed_to_key: hashed_buyer_key,
+            amount: refund,
+            current_escrow_balance,
+            current_to_balance: current_buyer_balance,
+            contract: contract.clone(),
+        },
+        RELEASE_PATHWAY,
+    )?;
+    Ok(Solution {
+        data: vec![solution, release, withdraw],
+    })
+}
+
+/// The index of the refund within a refund request solution.
+const RELEASE_PATHWAY: Word = 1;
@@ -0,0 +1,87 @@
+//! # Refund Ticket
+//! Contains functionality for a losing entrant reclaiming their raffle ticket.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    address_words, cost, deployed_at, release, ticket_held, validate, AuctionError, Query,
+};
+
+/// Contains all necessary information to build a refund ticket solution.
+pub struct BuildSolution {
+    /// The item being raffled.
+    pub item_id: Word,
+    /// The hashed key of the entrant.
+    pub hashed_entrant_key: [Word; 4],
+    /// The token contract tickets are paid in.
+    pub payment_token: ContentAddress,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// Whether the entrant holds an unrefunded ticket.
+    pub current_ticket_held: Query,
+    /// The price of a ticket.
+    pub current_ticket_price: Query,
+    /// The current balance of the entrant.
+    pub current_entrant_balance: Query,
+    /// The current balance of the escrow.
+    pub current_escrow_balance: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a refund ticket solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_entrant_key,
+        payment_token,
+        block_state,
+        current_ticket_held,
+        current_ticket_price,
+        current_entrant_balance,
+        current_escrow_balance,
+        contract,
+    } = build;
+
+    validate::refund_ticket(ticket_held(current_ticket_held)?)?;
+    let price = cost(current_ticket_price)?;
+
+    let pub_vars = super::auction::RefundTicket::PubVars {
+        item_id,
+        entrant: hashed_entrant_key,
+        block_state: address_words(&block_state),
+    };
+    let vars = super::auction::RefundTicket::Vars {
+        release_pathway: RELEASE_PATHWAY,
+    };
+    let mutations = super::auction::storage::mutations()
+        .ticket_held(|map| map.entry(item_id, |map| map.entry(hashed_entrant_key, false)));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::RefundTicket::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+
+    // The ticket is released from escrow to the entrant.
+    let [release, withdraw] = release::build_solution_data(
+        release::BuildSolution {
+            settlement: super::auction::RefundTicket::ADDRESS,
+            payment_token,
+            hashed_to_key: hashed_entrant_key,
+            amount: price,
+            current_escrow_balance,
+            current_to_balance: current_entrant_balance,
+            contract: contract.clone(),
+        },
+        RELEASE_PATHWAY,
+    )?;
+    Ok(Solution {
+        data: vec![solution, release, withdraw],
+    })
+}
+
+/// The index of the ticket refund within a refund ticket solution.
+const RELEASE_PATHWAY: Word = 1;
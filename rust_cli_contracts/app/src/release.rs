@@ -0,0 +1,71 @@
+//! # Release
+//! Contains functionality for paying a settlement out of the auction's escrow.
+use essential_types::{solution::SolutionData, ContentAddress, PredicateAddress, Word};
+
+use crate::{address_words, deployed_at, escrow_key, transfer, AuctionError, Query};
+
+/// Contains all necessary information to build an escrow release.
+pub struct BuildSolution {
+    /// The settlement predicate that the release pays for.
+    pub settlement: PredicateAddress,
+    /// The token contract the escrow is held in.
+    pub payment_token: ContentAddress,
+    /// The hashed key of the recipient.
+    pub hashed_to_key: [Word; 4],
+    /// The amount paid out of escrow.
+    pub amount: Word,
+    /// The current balance of the escrow.
+    pub current_escrow_balance: Query,
+    /// The current balance of the recipient.
+    pub current_to_balance: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds the release at `pathway` and the withdrawal it names right after it.
+///
+/// The settlement must be placed right before the release.
+pub fn build_solution_data(
+    build: BuildSolution,
+    pathway: Word,
+) -> Result<[SolutionData; 2], AuctionError> {
+    let BuildSolution {
+        settlement,
+        payment_token,
+        hashed_to_key,
+        amount,
+        current_escrow_balance,
+        current_to_balance,
+        contract,
+    } = build;
+
+    let transfer_pathway = pathway + 1;
+    let pub_vars = super::auction::Release::PubVars {
+        payment_token: address_words(&payment_token),
+        to: hashed_to_key,
+        amount,
+        transfer_pathway,
+    };
+    let vars = super::auction::Release::Vars {
+        settlement: address_words(&settlement.predicate),
+    };
+    let release = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::Release::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: Default::default(),
+    };
+
+    let withdraw = transfer::build_withdraw_data(
+        payment_token,
+        transfer::BuildSolution {
+            hashed_from_key: escrow_key(&contract),
+            hashed_to_key,
+            amount,
+            current_from_balance: current_escrow_balance,
+            current_to_balance,
+            authority: pathway,
+        },
+    )?;
+    Ok([release, withdraw])
+}
@@ -0,0 +1,84 @@
+//! # Release Listing
+//! Contains functionality for the owner taking a rejected listing off sale.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    deployed_at, item_owner, listing_status, offer, signature_words, validate, AuctionError, Query,
+};
+
+/// Contains all necessary information to build a release listing solution.
+pub struct BuildSolution {
+    /// The item whose listing was rejected.
+    pub item_id: Word,
+    /// The hashed key of the owner.
+    pub hashed_key: [Word; 4],
+    /// The current owner of the item.
+    pub current_owner: Query,
+    /// The current listing status of the item.
+    pub current_status: Query,
+    /// The owner's current nonce.
+    pub current_nonce: Query,
+    /// The owner's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The words the owner signs to take a rejected listing off sale.
+///
+/// The nonce is the owner's current nonce.
+pub fn data_to_sign(item_id: Word, nonce: Word) -> Vec<Word> {
+    vec![item_id, nonce, RELEASE_LISTING]
+}
+
+/// Builds a release listing solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        current_owner,
+        current_status,
+        current_nonce,
+        signature,
+        contract,
+    } = build;
+
+    validate::release_listing(
+        item_owner(current_owner)?,
+        hashed_key,
+        listing_status(current_status)?,
+    )?;
+    let nonce = offer(current_nonce)?;
+    let next_nonce = nonce.checked_add(1).ok_or(AuctionError::Overflow)?;
+
+    let pub_vars = super::auction::ReleaseListing::PubVars {
+        item_id,
+        key: hashed_key,
+        nonce,
+    };
+    let vars = super::auction::ReleaseListing::Vars {
+        sig: signature_words(&signature),
+    };
+    let mutations = super::auction::storage::mutations()
+        .auction_ended(|map| map.entry(item_id, true))
+        .fixed_price(|map| map.entry(item_id, false))
+        .ascending(|map| map.entry(item_id, false))
+        .raffle(|map| map.entry(item_id, false))
+        .nonces(|map| map.entry(hashed_key, next_nonce));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::ReleaseListing::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
+
+/// Tags a signature as taking a rejected listing off sale.
+const RELEASE_LISTING: Word = 14;
@@ -0,0 +1,113 @@
+//! # Rent
+//! Contains functionality for leasing an item on its owner's terms.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    address_words, cost, deployed_at, item_owner, transfer, validate, AuctionError, Query,
+};
+
+/// Contains all necessary information to build a rent solution.
+pub struct BuildSolution {
+    /// The item being rented.
+    pub item_id: Word,
+    /// The hashed key of the renter.
+    pub hashed_key: [Word; 4],
+    /// The token contract the item is priced in.
+    pub payment_token: ContentAddress,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The current block number.
+    pub current_block_number: Query,
+    /// The current owner of the item.
+    pub current_owner: Query,
+    /// Whether the item's current sale has ended.
+    pub current_auction_ended: Query,
+    /// The price of one lease.
+    pub current_price: Query,
+    /// The number of blocks a lease lasts.
+    pub current_blocks: Query,
+    /// The current or last renter of the item.
+    pub current_renter: Query,
+    /// The block number the current or last lease expires at.
+    pub current_expiry: Query,
+    /// The current balance of the renter.
+    pub current_renter_balance: Query,
+    /// The current balance of the owner.
+    pub current_owner_balance: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a rent solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        payment_token,
+        block_state,
+        current_block_number,
+        current_owner,
+        current_auction_ended,
+        current_price,
+        current_blocks,
+        current_renter,
+        current_expiry,
+        current_renter_balance,
+        current_owner_balance,
+        contract,
+    } = build;
+
+    let block_number = cost(current_block_number)?;
+    let owner = item_owner(current_owner)?;
+    let price = cost(current_price)?;
+    validate::rent(
+        owner,
+        hashed_key,
+        price,
+        current_auction_ended.as_opt_bool()? == Some(false),
+        current_expiry.as_opt_int()?,
+        block_number,
+    )?;
+    let expiry = block_number + cost(current_blocks)?;
+
+    let pub_vars = super::auction::Rent::PubVars {
+        item_id,
+        key: hashed_key,
+        payment_token: address_words(&payment_token),
+        block_state: address_words(&block_state),
+    };
+    let vars = super::auction::Rent::Vars {
+        transfer_pathway: TRANSFER_PATHWAY,
+    };
+    let mutations = super::auction::storage::mutations()
+        .renter(|map| map.entry(item_id, hashed_key))
+        .rental_expiry(|map| map.entry(item_id, expiry));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::Rent::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+
+    // The rent is paid straight to the owner.
+    let transfer = transfer::build_solution_data(
+        payment_token,
+        transfer::BuildSolution {
+            hashed_from_key: hashed_key,
+            hashed_to_key: owner,
+            amount: price,
+            current_from_balance: current_renter_balance,
+            current_to_balance: current_owner_balance,
+            authority: 0,
+        },
+    )?;
+    Ok(Solution {
+        data: vec![solution, transfer],
+    })
+}
+
+/// The index of the rent payment within a rent solution.
+const TRANSFER_PATHWAY: Word = 1;
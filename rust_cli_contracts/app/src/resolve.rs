@@ -0,0 +1,96 @@
+//! # Resolve
+//! Contains functionality for an arbiter splitting a disputed sale.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    cost, deployed_at, escrow_buyer, item_owner, signature_words, validate, AuctionError, Query,
+    ESCROW_SETTLED,
+};
+
+/// Contains all necessary information to build a resolve solution.
+pub struct BuildSolution {
+    /// The item being disputed.
+    pub item_id: Word,
+    /// The part of the sale returned to the buyer.
+    pub refund: Word,
+    /// The buyer of the disputed sale.
+    pub current_buyer: Query,
+    /// The seller of the disputed sale.
+    pub current_seller: Query,
+    /// The escrow status of the sale.
+    pub current_escrow_status: Query,
+    /// The number of the item's sales held in escrow, which names this sale.
+    pub current_escrow_sale: Query,
+    /// The amount held in escrow.
+    pub current_escrow_amount: Query,
+    /// The buyer's current reclaimable escrow on the item.
+    pub current_buyer_refund: Query,
+    /// The current unclaimed proceeds of the item.
+    pub current_proceeds: Query,
+    /// The arbiter's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The words the arbiter signs to rule on a dispute.
+pub fn data_to_sign(item_id: Word, refund: Word, sale: Word) -> Vec<Word> {
+    vec![item_id, refund, sale, RULING]
+}
+
+/// Builds a resolve solution based on the provided data.
+///
+/// The buyer reclaims their part with `claim_refund`
+/// and the seller theirs with `claim_proceeds`.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        refund,
+        current_buyer,
+        current_seller,
+        current_escrow_status,
+        current_escrow_sale,
+        current_escrow_amount,
+        current_buyer_refund,
+        current_proceeds,
+        signature,
+        contract,
+    } = build;
+
+    let amount = cost(current_escrow_amount)?;
+    validate::resolve(cost(current_escrow_status)?, refund, amount)?;
+    let buyer = escrow_buyer(current_buyer)?;
+    let seller = item_owner(current_seller)?;
+    let buyer_refund = cost(current_buyer_refund)? + refund;
+    let proceeds = cost(current_proceeds)? + amount - refund;
+
+    let pub_vars = super::auction::Resolve::PubVars {
+        item_id,
+        refund,
+        sale: cost(current_escrow_sale)?,
+    };
+    let vars = super::auction::Resolve::Vars {
+        sig: signature_words(&signature),
+    };
+    let mutations = super::auction::storage::mutations()
+        .refunds(|map| map.entry(item_id, |map| map.entry(buyer, buyer_refund)))
+        .proceeds(|map| map.entry(item_id, proceeds))
+        .proceeds_seller(|map| map.entry(item_id, seller))
+        .escrow_status(|map| map.entry(item_id, ESCROW_SETTLED));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::Resolve::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
+
+/// Tags a signature as a ruling.
+const RULING: Word = 2;
@@ -0,0 +1,75 @@
+//! # Reveal Raffle
+//! Contains functionality for revealing a committed secret into a raffle's seed.
+use essential_types::{
+    convert::word_4_from_u8_32,
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{address_words, cost, deployed_at, validate, AuctionError, Query};
+
+/// Secrets and the seed they sum into are kept below this bound.
+pub const SEED_MODULUS: Word = 1 << 32;
+
+/// Contains all necessary information to build a reveal raffle solution.
+pub struct BuildSolution {
+    /// The item being raffled.
+    pub item_id: Word,
+    /// The hashed key of the seller or entrant revealing.
+    pub hashed_key: [Word; 4],
+    /// The secret that was committed to.
+    pub secret: Word,
+    /// The salt the secret was committed with.
+    pub salt: [Word; 4],
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The current seed of the raffle.
+    pub current_seed: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The commitment to a secret that is stored until it is revealed.
+pub fn commitment(secret: Word, salt: [Word; 4]) -> Result<[Word; 4], AuctionError> {
+    validate::reveal_raffle(secret)?;
+    let mut words = vec![secret];
+    words.extend_from_slice(&salt);
+    Ok(word_4_from_u8_32(essential_hash::hash_words(&words)))
+}
+
+/// Builds a reveal raffle solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        secret,
+        salt,
+        block_state,
+        current_seed,
+        contract,
+    } = build;
+
+    // Checks the secret is in range.
+    commitment(secret, salt)?;
+    let seed = (cost(current_seed)? + secret) % SEED_MODULUS;
+
+    let pub_vars = super::auction::RevealRaffle::PubVars {
+        item_id,
+        revealer: hashed_key,
+        secret,
+        salt,
+        block_state: address_words(&block_state),
+    };
+    let mutations = super::auction::storage::mutations()
+        .raffle_revealed(|map| map.entry(item_id, |map| map.entry(hashed_key, true)))
+        .raffle_seed(|map| map.entry(item_id, seed));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::RevealRaffle::ADDRESS, &contract),
+        decision_variables: Default::default(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
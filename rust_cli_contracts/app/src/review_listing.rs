@@ -0,0 +1,90 @@
+//! # Review Listing
+//! Contains functionality for a curator approving or rejecting a pending auction.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    deployed_at, is_curator, listing_status, offer, signature_words, validate, AuctionError, Query,
+    LISTING_APPROVED, LISTING_REJECTED,
+};
+
+/// Contains all necessary information to build a review listing solution.
+pub struct BuildSolution {
+    /// The auction being reviewed.
+    pub item_id: Word,
+    /// The hashed key of the curator.
+    pub hashed_key: [Word; 4],
+    /// Whether the auction is approved or rejected.
+    pub approved: bool,
+    /// Whether the reviewer is currently a curator.
+    pub current_curator: Query,
+    /// The current listing status of the auction.
+    pub current_status: Query,
+    /// The curator's current nonce.
+    pub current_nonce: Query,
+    /// The curator's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The words the curator signs to approve or reject a listing.
+///
+/// The nonce is the curator's current nonce.
+pub fn data_to_sign(item_id: Word, approved: bool, nonce: Word) -> Vec<Word> {
+    vec![item_id, approved as Word, nonce, REVIEW_LISTING]
+}
+
+/// Builds a review listing solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        approved,
+        current_curator,
+        current_status,
+        current_nonce,
+        signature,
+        contract,
+    } = build;
+
+    validate::review_listing(
+        is_curator(current_curator)?,
+        listing_status(current_status)?,
+    )?;
+    let nonce = offer(current_nonce)?;
+    let next_nonce = nonce.checked_add(1).ok_or(AuctionError::Overflow)?;
+
+    let pub_vars = super::auction::ReviewListing::PubVars {
+        item_id,
+        key: hashed_key,
+        approved,
+        nonce,
+    };
+    let vars = super::auction::ReviewListing::Vars {
+        sig: signature_words(&signature),
+    };
+    let status = if approved {
+        LISTING_APPROVED
+    } else {
+        LISTING_REJECTED
+    };
+    let mutations = super::auction::storage::mutations()
+        .listing_status(|map| map.entry(item_id, status))
+        .nonces(|map| map.entry(hashed_key, next_nonce));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::ReviewListing::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
+
+/// Tags a signature as a curator's review.
+const REVIEW_LISTING: Word = 13;
@@ -0,0 +1,59 @@
+//! # Set Arbiter
+//! Contains functionality for an owner naming the arbiter of their item's next sale.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{cost, deployed_at, item_owner, validate, AuctionError, Query};
+
+/// Contains all necessary information to build a set arbiter solution.
+pub struct BuildSolution {
+    /// The item to be sold in escrow.
+    pub item_id: Word,
+    /// The hashed key of the owner.
+    pub hashed_key: [Word; 4],
+    /// The hashed key of the arbiter.
+    pub arbiter: [Word; 4],
+    /// The current owner of the item.
+    pub current_owner: Query,
+    /// The escrow status of the item's last sale.
+    pub current_escrow_status: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a set arbiter solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        arbiter,
+        current_owner,
+        current_escrow_status,
+        contract,
+    } = build;
+
+    validate::set_arbiter(
+        item_owner(current_owner)?,
+        hashed_key,
+        arbiter,
+        cost(current_escrow_status)?,
+    )?;
+
+    let pub_vars = super::auction::SetArbiter::PubVars {
+        item_id,
+        key: hashed_key,
+        arbiter,
+    };
+    let mutations = super::auction::storage::mutations().arbiter(|map| map.entry(item_id, arbiter));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::SetArbiter::ADDRESS, &contract),
+        decision_variables: Default::default(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
@@ -0,0 +1,78 @@
+//! # Set Curator
+//! Contains functionality for the curator admin appointing or removing a curator.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{curator_admin, deployed_at, offer, signature_words, validate, AuctionError, Query};
+
+/// Contains all necessary information to build a set curator solution.
+pub struct BuildSolution {
+    /// The hashed key of the curator admin.
+    pub hashed_key: [Word; 4],
+    /// The hashed key of the curator.
+    pub curator: [Word; 4],
+    /// Whether the curator is appointed or removed.
+    pub appointed: bool,
+    /// The current curator admin.
+    pub current_admin: Query,
+    /// The admin's current nonce.
+    pub current_nonce: Query,
+    /// The admin's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The words the admin signs to appoint or remove a curator.
+///
+/// The nonce is the admin's current nonce.
+pub fn data_to_sign(curator: [Word; 4], appointed: bool, nonce: Word) -> Vec<Word> {
+    let mut words = curator.to_vec();
+    words.extend([appointed as Word, nonce, SET_CURATOR]);
+    words
+}
+
+/// Builds a set curator solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        hashed_key,
+        curator,
+        appointed,
+        current_admin,
+        current_nonce,
+        signature,
+        contract,
+    } = build;
+
+    validate::set_curator(curator_admin(current_admin)?, hashed_key)?;
+    let nonce = offer(current_nonce)?;
+    let next_nonce = nonce.checked_add(1).ok_or(AuctionError::Overflow)?;
+
+    let pub_vars = super::auction::SetCurator::PubVars {
+        key: hashed_key,
+        curator,
+        appointed,
+        nonce,
+    };
+    let vars = super::auction::SetCurator::Vars {
+        sig: signature_words(&signature),
+    };
+    let mutations = super::auction::storage::mutations()
+        .curators(|map| map.entry(curator, appointed))
+        .nonces(|map| map.entry(hashed_key, next_nonce));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::SetCurator::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
+
+/// Tags a signature as appointing or removing a curator.
+const SET_CURATOR: Word = 12;
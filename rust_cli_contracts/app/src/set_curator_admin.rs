@@ -0,0 +1,61 @@
+//! # Set Curator Admin
+//! Contains functionality for turning on curation of new listings.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{curator_admin, deployed_at, signature_words, validate, AuctionError, Query};
+
+/// Contains all necessary information to build a set curator admin solution.
+pub struct BuildSolution {
+    /// The hashed key that will appoint curators, which must be
+    /// [`crate::CURATOR_ADMIN`].
+    pub hashed_key: [Word; 4],
+    /// The current curator admin.
+    pub current_admin: Query,
+    /// The admin's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The words the admin signs to turn on curation.
+pub fn data_to_sign(hashed_key: [Word; 4]) -> Vec<Word> {
+    let mut words = hashed_key.to_vec();
+    words.push(SET_CURATOR_ADMIN);
+    words
+}
+
+/// Builds a set curator admin solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        hashed_key,
+        current_admin,
+        signature,
+        contract,
+    } = build;
+
+    validate::set_curator_admin(curator_admin(current_admin)?, hashed_key)?;
+
+    let pub_vars = super::auction::SetCuratorAdmin::PubVars { key: hashed_key };
+    let vars = super::auction::SetCuratorAdmin::Vars {
+        sig: signature_words(&signature),
+    };
+    let mutations = super::auction::storage::mutations()
+        .curator_admin(hashed_key)
+        .curated_item_count(0);
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::SetCuratorAdmin::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
+
+/// Tags a signature as turning on curation.
+const SET_CURATOR_ADMIN: Word = 5;
@@ -0,0 +1,102 @@
+//! # Settle Ascending
+//! Contains functionality for closing an ascending auction once its deadline has passed.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{
+    address_words, buy::royalty, cost, deployed_at, item_collection, item_owner, leader, validate,
+    AuctionError, Query,
+};
+
+/// Contains all necessary information to build a settle ascending solution.
+pub struct BuildSolution {
+    /// The item being auctioned.
+    pub item_id: Word,
+    /// The token contract the item is priced in.
+    pub payment_token: ContentAddress,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The current owner of the item.
+    pub current_owner: Query,
+    /// The current visible price of the item.
+    pub current_cost: Query,
+    /// The current leading bidder.
+    pub current_leader: Query,
+    /// The escrowed maximum of the leading bidder.
+    pub current_leader_escrow: Query,
+    /// The current reclaimable escrow of the leading bidder.
+    pub current_leader_refund: Query,
+    /// The collection the item is registered in.
+    pub current_collection: Query,
+    /// The royalty rate of the item's collection in basis points.
+    pub current_royalty_rate: Query,
+    /// The royalties owed to the item's collection in the payment token.
+    pub current_royalties: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a settle ascending solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        payment_token,
+        block_state,
+        current_owner,
+        current_cost,
+        current_leader,
+        current_leader_escrow,
+        current_leader_refund,
+        current_collection,
+        current_royalty_rate,
+        current_royalties,
+        contract,
+    } = build;
+
+    let leader = leader(current_leader)?;
+    validate::settle_ascending(leader)?;
+    let winner = leader.unwrap_or_default();
+    let seller = item_owner(current_owner)?;
+    let price = cost(current_cost)?;
+    // The winner pays the visible price and reclaims the rest of their maximum.
+    let refund = cost(current_leader_refund)? + cost(current_leader_escrow)? - price;
+    let collection = item_collection(current_collection)?;
+    let royalty = match collection {
+        Some(_) => royalty(price, cost(current_royalty_rate)?)?,
+        None => 0,
+    };
+
+    let payment_token = address_words(&payment_token);
+    let pub_vars = super::auction::SettleAscending::PubVars {
+        item_id,
+        winner,
+        payment_token,
+        collection: collection.unwrap_or_default(),
+        block_state: address_words(&block_state),
+    };
+    let mut mutations = super::auction::storage::mutations()
+        .item_owner(|map| map.entry(item_id, winner))
+        .proceeds(|map| map.entry(item_id, price - royalty))
+        .proceeds_seller(|map| map.entry(item_id, seller))
+        .refunds(|map| map.entry(item_id, |map| map.entry(winner, refund)))
+        .auction_ended(|map| map.entry(item_id, true))
+        .ascending(|map| map.entry(item_id, false));
+    if let Some(collection) = collection {
+        // The creator's royalty is split off before the seller claims the rest.
+        let royalties = cost(current_royalties)? + royalty;
+        mutations = mutations.collection_royalties(|map| {
+            map.entry(collection, |map| map.entry(payment_token, royalties))
+        });
+    }
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::SettleAscending::ADDRESS, &contract),
+        decision_variables: Default::default(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
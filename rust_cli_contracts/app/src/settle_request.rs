@@ -0,0 +1,82 @@
+//! # Settle Request
+//! Contains functionality for paying the lowest ask once a request's deadline has passed.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{address_words, deployed_at, release, validate, AuctionError, Query};
+
+/// Contains all necessary information to build a settle request solution.
+pub struct BuildSolution {
+    /// The item being requested.
+    pub item_id: Word,
+    /// The hashed key of the provider with the lowest ask.
+    pub hashed_provider_key: [Word; 4],
+    /// The token contract the budget is escrowed in.
+    pub payment_token: ContentAddress,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The current lowest ask on the request.
+    pub current_best_ask: Query,
+    /// The current balance of the provider.
+    pub current_provider_balance: Query,
+    /// The current balance of the escrow.
+    pub current_escrow_balance: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a settle request solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_provider_key,
+        payment_token,
+        block_state,
+        current_best_ask,
+        current_provider_balance,
+        current_escrow_balance,
+        contract,
+    } = build;
+
+    let ask = current_best_ask.as_opt_int()?;
+    validate::settle_request(ask)?;
+    let ask = ask.unwrap_or_default();
+
+    let pub_vars = super::auction::SettleRequest::PubVars {
+        item_id,
+        block_state: address_words(&block_state),
+    };
+    let vars = super::auction::SettleRequest::Vars {
+        release_pathway: RELEASE_PATHWAY,
+    };
+    let mutations = super::auction::storage::mutations()
+        .request_settled(|map| map.entry(item_id, true));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::SettleRequest::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+
+    // The lowest ask is paid out of the escrow.
+    let [release, withdraw] = release::build_solution_data(
+        release::BuildSolution {
+            settlement: super::auction::SettleRequest::ADDRESS,
+            payment_token,
+            hashed_to_key: hashed_provider_key,
+            amount: ask,
+            current_escrow_balance,
+            current_to_balance: current_provider_balance,
+            contract: contract.clone(),
+        },
+        RELEASE_PATHWAY,
+    )?;
+    Ok(Solution {
+        data: vec![solution, release, withdraw],
+    })
+}
+
+/// The index of the payment within a settle request solution.
+const RELEASE_PATHWAY: Word = 1;
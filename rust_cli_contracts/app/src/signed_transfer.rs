@@ -0,0 +1,100 @@
+//! # Signed Transfer
+//! Contains functionality for transferring tokens with the sender's signature.
+//!
+//! Unlike a plain transfer, the token contract checks the sender signed the
+//! recipient, amount and their nonce, so the solution can be built by anyone
+//! but only the holder of the key can authorize it.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, PredicateAddress, Word,
+};
+
+use crate::{
+    balance, offer, signature_words,
+    transfer::{calculate_from_balance, calculate_to_balance},
+    validate, AuctionError, Query,
+};
+
+/// Contains all necessary information to build a signed transfer solution.
+pub struct BuildSolution {
+    /// The hashed key of the sender.
+    pub hashed_from_key: [Word; 4],
+    /// The hashed key of the recipient.
+    pub hashed_to_key: [Word; 4],
+    /// The amount of tokens to transfer.
+    pub amount: Word,
+    /// The current balance of the sender.
+    pub current_from_balance: Query,
+    /// The current balance of the recipient.
+    pub current_to_balance: Query,
+    /// The sender's current transfer nonce.
+    pub current_nonce: Query,
+    /// The sender's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+}
+
+/// The words the sender signs to transfer `amount` to `hashed_to_key`.
+///
+/// The nonce is the sender's current transfer nonce.
+pub fn data_to_sign(hashed_to_key: [Word; 4], amount: Word, nonce: Word) -> Vec<Word> {
+    let mut words = hashed_to_key.to_vec();
+    words.extend([amount, nonce, SIGNED_TRANSFER]);
+    words
+}
+
+/// Builds a signed transfer solution in the bundled token contract.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let solution = build_solution_data(super::token::ADDRESS, build)?;
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
+
+/// Builds the solution data for a signed transfer in the given token contract.
+pub fn build_solution_data(
+    token_contract: ContentAddress,
+    build: BuildSolution,
+) -> Result<SolutionData, AuctionError> {
+    let BuildSolution {
+        hashed_from_key,
+        hashed_to_key,
+        amount,
+        current_from_balance,
+        current_to_balance,
+        current_nonce,
+        signature,
+    } = build;
+    let current_from_balance = balance(current_from_balance)?;
+    let from_balance = calculate_from_balance(current_from_balance, amount)?;
+    validate::transfer(current_from_balance, amount)?;
+    let to_balance = calculate_to_balance(balance(current_to_balance)?, amount)?;
+    let nonce = offer(current_nonce)?;
+    let next_nonce = nonce.checked_add(1).ok_or(AuctionError::Overflow)?;
+
+    let pub_vars = super::token::SignedTransfer::PubVars {
+        key: hashed_from_key,
+        to: hashed_to_key,
+        amount,
+        nonce,
+    };
+    let vars = super::token::SignedTransfer::Vars {
+        sig: signature_words(&signature),
+    };
+    let mutations = super::token::storage::mutations()
+        .balances(|map| map.entry(hashed_from_key, from_balance))
+        .balances(|map| map.entry(hashed_to_key, to_balance))
+        .nonces(|map| map.entry(hashed_from_key, next_nonce));
+    Ok(SolutionData {
+        predicate_to_solve: PredicateAddress {
+            contract: token_contract,
+            predicate: super::token::SignedTransfer::ADDRESS.predicate,
+        },
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    })
+}
+
+/// Tags a signature as a transfer.
+const SIGNED_TRANSFER: Word = 15;
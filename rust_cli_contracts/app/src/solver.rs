@@ -0,0 +1,125 @@
+//! # Solver
+//! Contains functionality for filling bid intents from an intent pool.
+//!
+//! A solver watches the Dutch auctions named by published intents and
+//! completes an intent as soon as the auction's price steps down within
+//! the bidder's limit.
+use essential_rest_client::node_client::EssentialNodeClient;
+use essential_types::{solution::Solution, ContentAddress, Word};
+
+use crate::{
+    arbiter, arbiter_key, ascending_key, auction_status_key, balance, balance_key, bid_intent,
+    bid_intent::BidIntent, block_number_key, block_state, collection_royalties_key,
+    collection_royalty_key, cost, cost_key, escrow_key, fixed_price_key, flag, get_item_owner_key,
+    item_collection, item_collection_key, item_owner, listing_status, listing_status_key,
+    payment_token, payment_token_key, proceeds_key, raffle_key, Query,
+};
+
+/// An intent the solver can fill, with the solution that fills it.
+pub struct Fill {
+    /// The item the intent wins.
+    pub item_id: Word,
+    /// The hashed key of the bidder.
+    pub bidder: [Word; 4],
+    /// The price the bidder pays.
+    pub price: Word,
+    /// The solution filling the intent.
+    pub solution: Solution,
+}
+
+/// Finds the intents that can be filled right now and builds their solutions.
+///
+/// Intents on items that are not open Dutch auctions, that have expired or
+/// whose limit is below the current price are skipped. At most one intent
+/// is filled per item, in pool order.
+pub async fn find_fills(
+    node: &EssentialNodeClient,
+    contract: &ContentAddress,
+    intents: &[BidIntent],
+) -> anyhow::Result<Vec<Fill>> {
+    let block_state = block_state::ADDRESS;
+    let block_number = node
+        .query_state(block_state.clone(), block_number_key())
+        .await?;
+    let current_block = cost(Query(block_number.clone()))?;
+
+    let mut fills: Vec<Fill> = Vec::new();
+    for intent in intents {
+        let item_id = intent.item_id;
+        if fills.iter().any(|fill| fill.item_id == item_id) {
+            continue;
+        }
+
+        let query = |key| node.query_state(contract.clone(), key);
+        let fixed_price = query(fixed_price_key(item_id)).await?;
+        if flag(Query(query(auction_status_key(item_id)).await?))?
+            || flag(Query(fixed_price.clone()))?
+            || flag(Query(query(ascending_key(item_id)).await?))?
+            || flag(Query(query(raffle_key(item_id)).await?))?
+            || arbiter(Query(query(arbiter_key(item_id)).await?))?.is_some()
+            || !listing_status(Query(query(listing_status_key(item_id)).await?))?.accepts_bids()
+        {
+            continue;
+        }
+        let listing_token = query(payment_token_key(item_id)).await?;
+        if listing_token.is_none() || payment_token(Query(listing_token))? != intent.payment_token {
+            continue;
+        }
+        let current_cost = query(cost_key(item_id)).await?;
+        let price = cost(Query(current_cost.clone()))?;
+        if !bid_intent::fillable(intent, price, current_block) {
+            continue;
+        }
+
+        let current_owner = query(get_item_owner_key(item_id)).await?;
+        let owner = item_owner(Query(current_owner.clone()))?;
+        let bidder_balance = node
+            .query_state(intent.payment_token.clone(), balance_key(intent.bidder))
+            .await?;
+        if balance(Query(bidder_balance.clone()))? < price {
+            continue;
+        }
+        let collection = query(item_collection_key(item_id)).await?;
+        let collection_id = item_collection(Query(collection.clone()))?;
+
+        // Sales in a collection are paid into escrow to split off the royalty.
+        let to_key = match collection_id {
+            Some(_) => escrow_key(contract),
+            None => owner,
+        };
+        let owner_balance = node
+            .query_state(intent.payment_token.clone(), balance_key(to_key))
+            .await?;
+        let collection_id = collection_id.unwrap_or_default();
+        let royalty_rate = query(collection_royalty_key(collection_id)).await?;
+        let royalties = query(collection_royalties_key(
+            collection_id,
+            &intent.payment_token,
+        ))
+        .await?;
+        let proceeds = query(proceeds_key(item_id)).await?;
+
+        let solution = bid_intent::build_solution(bid_intent::BuildSolution {
+            intent: intent.clone(),
+            block_state: block_state.clone(),
+            current_block_number: Query(block_number.clone()),
+            current_cost: Query(current_cost),
+            current_owner: Query(current_owner),
+            current_fixed_price: Query(fixed_price),
+            current_bidder_balance: Query(bidder_balance),
+            current_owner_balance: Query(owner_balance),
+            current_collection: Query(collection),
+            current_royalty_rate: Query(royalty_rate),
+            current_royalties: Query(royalties),
+            current_proceeds: Query(proceeds),
+            contract: contract.clone(),
+        })?;
+        fills.push(Fill {
+            item_id,
+            bidder: intent.bidder,
+            price,
+            solution,
+        });
+    }
+    Ok(fills)
+}
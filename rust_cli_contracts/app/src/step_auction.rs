@@ -6,10 +6,15 @@ use essential_sign::secp256k1::ecdsa::RecoverableSignature;
 use essential_types::{
     convert::word_4_from_u8_32,
     solution::{Solution, SolutionData},
-    Word,
+    ContentAddress, Word,
 };
 
-use crate::{balance, Query, cost};
+use crate::{
+    action::{SolutionBuilder, State, StateKey},
+    balance, cost, cost_key, deployed_at,
+    validate::Rule,
+    AuctionError, Query,
+};
 
 /// details
 pub struct BuildSolution {
@@ -21,27 +26,30 @@ pub struct BuildSolution {
     pub amount: Word,
     /// The current balance of the sender.
     pub current_cost: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
 }
 
 /// Builds a mint solution based on the provided data.
-pub fn build_solution(build: BuildSolution) -> anyhow::Result<Solution> {
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
     let BuildSolution {
         item_id,
         hashed_key,
         amount,
         current_cost,
+        contract,
     } = build;
 
-    let pub_vars = super::token::StepAuction::PubVars {
+    let pub_vars = super::auction::StepAuction::PubVars {
         item_id: item_id,
         decrease_amt: amount,
         key: hashed_key,
     };
     let cost = calculate_from_cost(cost(current_cost)?, amount)?;
-    let mutations = super::token::storage::mutations()
+    let mutations = super::auction::storage::mutations()
         .cost(|map| map.entry(item_id, cost));
     let solution = SolutionData {
-        predicate_to_solve: super::token::StepAuction::ADDRESS,
+        predicate_to_solve: deployed_at(super::auction::StepAuction::ADDRESS, &contract),
         decision_variables: Default::default(),
         transient_data: pub_vars.into(),
         state_mutations: mutations.into(),
@@ -52,9 +60,42 @@ pub fn build_solution(build: BuildSolution) -> anyhow::Result<Solution> {
 }
 
 
-/// Calculates the new cost.
-fn calculate_from_cost(from_cost: Word, amount: Word) -> anyhow::Result<Word> {
-    from_cost
-        .checked_sub(amount)
-        .ok_or(anyhow::anyhow!("Insufficient balance"))
+/// Lowers the price of a Dutch auction.
+pub struct StepAuction {
+    /// The item being auctioned.
+    pub item_id: Word,
+    /// The hashed key of the owner.
+    pub hashed_key: [Word; 4],
+    /// The amount to lower the price by.
+    pub decrease: Word,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+impl SolutionBuilder for StepAuction {
+    fn required_keys(&self) -> Vec<StateKey> {
+        vec![(self.contract.clone(), cost_key(self.item_id))]
+    }
+
+    fn build(&self, state: &State) -> Result<Solution, AuctionError> {
+        build_solution(BuildSolution {
+            item_id: self.item_id,
+            hashed_key: self.hashed_key,
+            amount: self.decrease,
+            current_cost: state.get(&self.contract, &cost_key(self.item_id)),
+            contract: self.contract.clone(),
+        })
+    }
+}
+
+/// Calculates the new cost, which must stay above zero.
+fn calculate_from_cost(from_cost: Word, amount: Word) -> Result<Word, AuctionError> {
+    if amount <= 0 {
+        return Err(Rule::DecreasePositive.into());
+    }
+    match from_cost.checked_sub(amount) {
+        Some(cost) if cost > 0 => Ok(cost),
+        Some(_) => Err(Rule::CostRemainsPositive.into()),
+        None => Err(AuctionError::Overflow),
+    }
 }
@@ -0,0 +1,61 @@
+//! # Submit Ask
+//! Contains functionality for a provider undercutting the best ask on a request.
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{address_words, cost, deployed_at, validate, AuctionError, Query};
+
+/// Contains all necessary information to build a submit ask solution.
+pub struct BuildSolution {
+    /// The item being requested.
+    pub item_id: Word,
+    /// The hashed key of the provider.
+    pub hashed_key: [Word; 4],
+    /// The amount the provider asks to be paid.
+    pub ask: Word,
+    /// The block state contract the auction contract depends on.
+    pub block_state: ContentAddress,
+    /// The budget of the request.
+    pub current_budget: Query,
+    /// The current lowest ask on the request.
+    pub current_best_ask: Query,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// Builds a submit ask solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        ask,
+        block_state,
+        current_budget,
+        current_best_ask,
+        contract,
+    } = build;
+
+    // The first ask must beat the budget, later asks the best ask.
+    validate::submit_ask(ask, cost(current_budget)?, current_best_ask.as_opt_int()?)?;
+
+    let pub_vars = super::auction::SubmitAsk::PubVars {
+        item_id,
+        provider: hashed_key,
+        ask,
+        block_state: address_words(&block_state),
+    };
+    let mutations = super::auction::storage::mutations()
+        .best_ask(|map| map.entry(item_id, ask))
+        .best_provider(|map| map.entry(item_id, hashed_key));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::SubmitAsk::ADDRESS, &contract),
+        decision_variables: Default::default(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
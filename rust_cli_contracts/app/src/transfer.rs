@@ -5,10 +5,13 @@ use essential_app_utils::inputs::Encode;
 use essential_sign::secp256k1::ecdsa::RecoverableSignature;
 use essential_types::{
     solution::{Solution, SolutionData},
-    Word,
+    ContentAddress, PredicateAddress, Word,
 };
 
-use crate::{balance, Query};
+use crate::{
+    action::{SolutionBuilder, State, StateKey},
+    balance, balance_key, validate, AuctionError, Query,
+};
 
 
 /// Contains all necessary information to build a transfer solution.
@@ -22,50 +25,152 @@ pub struct BuildSolution {
     /// The current balance of the sender.
     pub current_from_balance: Query,
     /// The current balance of the recipient.
-    pub current_to_balance: Query
+    pub current_to_balance: Query,
+    /// The position in the solution of the predicate that authorizes the transfer.
+    ///
+    /// A balance held by a contract is only paid into through one of its
+    /// predicates once it is held, and only paid out by a withdrawal.
+    pub authority: Word,
 }
 
 /// Builds a transfer solution based on the provided data.
-pub fn build_solution(build: BuildSolution) -> anyhow::Result<Solution> {
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let solution = build_solution_data(super::token::ADDRESS, build)?;
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
+
+/// Builds the solution data for a transfer in the given token contract.
+///
+/// This is used by actions in other contracts that pay through a token.
+pub fn build_solution_data(
+    token_contract: ContentAddress,
+    build: BuildSolution,
+) -> Result<SolutionData, AuctionError> {
     let BuildSolution {
         hashed_from_key,
         hashed_to_key,
         amount,
         current_from_balance,
         current_to_balance,
+        authority,
     } = build;
-    let from_balance = calculate_from_balance(balance(current_from_balance)?, amount)?;
+    let current_from_balance = balance(current_from_balance)?;
+    let from_balance = calculate_from_balance(current_from_balance, amount)?;
+    validate::transfer(current_from_balance, amount)?;
     let to_balance = calculate_to_balance(balance(current_to_balance)?, amount)?;
     let pub_vars = super::token::Transfer::PubVars {
         key: hashed_from_key,
         to: hashed_to_key,
         amount,
+        authority,
     };
     
     let mutations = super::token::storage::mutations()
         .balances(|map| map.entry(hashed_from_key, from_balance))
         .balances(|map| map.entry(hashed_to_key, to_balance));
     let solution = SolutionData {
-        predicate_to_solve: super::token::Transfer::ADDRESS,
+        predicate_to_solve: PredicateAddress {
+            contract: token_contract,
+            predicate: super::token::Transfer::ADDRESS.predicate,
+        },
         decision_variables: Default::default(),
         transient_data: pub_vars.into(),
         state_mutations: mutations.into(),
     };
-    Ok(Solution {
-        data: vec![solution],
+    Ok(solution)
+}
+
+/// Builds the solution data for a withdrawal from a held balance in the given token contract.
+///
+/// The `authority` is the position of the release of the holding contract
+/// that names the withdrawal.
+pub fn build_withdraw_data(
+    token_contract: ContentAddress,
+    build: BuildSolution,
+) -> Result<SolutionData, AuctionError> {
+    let BuildSolution {
+        hashed_from_key,
+        hashed_to_key,
+        amount,
+        current_from_balance,
+        current_to_balance,
+        authority,
+    } = build;
+    let current_from_balance = balance(current_from_balance)?;
+    let from_balance = calculate_from_balance(current_from_balance, amount)?;
+    validate::transfer(current_from_balance, amount)?;
+    let to_balance = calculate_to_balance(balance(current_to_balance)?, amount)?;
+    let pub_vars = super::token::Withdraw::PubVars {
+        key: hashed_from_key,
+        to: hashed_to_key,
+        amount,
+        authority,
+    };
+
+    let mutations = super::token::storage::mutations()
+        .balances(|map| map.entry(hashed_from_key, from_balance))
+        .balances(|map| map.entry(hashed_to_key, to_balance));
+    Ok(SolutionData {
+        predicate_to_solve: PredicateAddress {
+            contract: token_contract,
+            predicate: super::token::Withdraw::ADDRESS.predicate,
+        },
+        decision_variables: Default::default(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
     })
 }
 
+/// Transfers tokens between accounts in the token contract.
+pub struct Transfer {
+    /// The hashed key of the sender.
+    pub hashed_from_key: [Word; 4],
+    /// The hashed key of the recipient.
+    pub hashed_to_key: [Word; 4],
+    /// The amount of tokens to transfer.
+    pub amount: Word,
+}
+
+impl SolutionBuilder for Transfer {
+    fn required_keys(&self) -> Vec<StateKey> {
+        vec![
+            (super::token::ADDRESS, balance_key(self.hashed_from_key)),
+            (super::token::ADDRESS, balance_key(self.hashed_to_key)),
+        ]
+    }
+
+    fn build(&self, state: &State) -> Result<Solution, AuctionError> {
+        self.build_at(state, 0)
+    }
+
+    fn build_at(&self, state: &State, offset: usize) -> Result<Solution, AuctionError> {
+        let token = super::token::ADDRESS;
+        // A plain transfer authorizes itself.
+        build_solution(BuildSolution {
+            hashed_from_key: self.hashed_from_key,
+            hashed_to_key: self.hashed_to_key,
+            amount: self.amount,
+            current_from_balance: state.get(&token, &balance_key(self.hashed_from_key)),
+            current_to_balance: state.get(&token, &balance_key(self.hashed_to_key)),
+            authority: offset as Word,
+        })
+    }
+}
+
 /// Calculates the new balance for the sender after transferring tokens.
-fn calculate_from_balance(from_balance: Word, amount: Word) -> anyhow::Result<Word> {
-    from_balance
-        .checked_sub(amount)
-        .ok_or(anyhow::anyhow!("Insufficient balance"))
+pub(crate) fn calculate_from_balance(from_balance: Word, amount: Word) -> Result<Word, AuctionError> {
+    if from_balance < amount {
+        return Err(AuctionError::InsufficientBalance {
+            available: from_balance,
+            required: amount,
+        });
+    }
+    from_balance.checked_sub(amount).ok_or(AuctionError::Overflow)
 }
 
 /// Calculates the new balance for the recipient after receiving tokens.
-fn calculate_to_balance(to_balance: Word, amount: Word) -> anyhow::Result<Word> {
-    to_balance
-        .checked_add(amount)
-        .ok_or(anyhow::anyhow!("Insufficient balance"))
+pub(crate) fn calculate_to_balance(to_balance: Word, amount: Word) -> Result<Word, AuctionError> {
+    to_balance.checked_add(amount).ok_or(AuctionError::Overflow)
 }
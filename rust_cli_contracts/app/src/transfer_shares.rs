@@ -0,0 +1,104 @@
+//! # Transfer Shares
+//! Contains functionality for transferring shares of a fractionalized item.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{deployed_at, offer, share_balance, signature_words, validate, AuctionError, Query};
+
+/// Contains all necessary information to build a transfer shares solution.
+pub struct BuildSolution {
+    /// The fractionalized item.
+    pub item_id: Word,
+    /// The hashed key of the sender.
+    pub hashed_from_key: [Word; 4],
+    /// The hashed key of the receiver.
+    pub hashed_to_key: [Word; 4],
+    /// The number of shares to transfer.
+    pub amount: Word,
+    /// The current shares of the sender.
+    pub current_from_shares: Query,
+    /// The current shares of the receiver.
+    pub current_to_shares: Query,
+    /// The sender's current share transfer nonce.
+    pub current_nonce: Query,
+    /// The sender's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The words the sender signs to transfer shares.
+///
+/// The nonce is the sender's current share transfer nonce.
+pub fn data_to_sign(
+    item_id: Word,
+    hashed_to_key: [Word; 4],
+    amount: Word,
+    nonce: Word,
+) -> Vec<Word> {
+    let mut words = vec![item_id];
+    words.extend(hashed_to_key);
+    words.extend([amount, nonce, TRANSFER_SHARES]);
+    words
+}
+
+/// Builds a transfer shares solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_from_key,
+        hashed_to_key,
+        amount,
+        current_from_shares,
+        current_to_shares,
+        current_nonce,
+        signature,
+        contract,
+    } = build;
+
+    let from_shares = share_balance(current_from_shares)?;
+    let to_shares = share_balance(current_to_shares)?;
+    validate::transfer_shares(from_shares, amount)?;
+    if hashed_from_key == hashed_to_key {
+        rejected!("Cannot transfer shares to yourself");
+    }
+    let nonce = offer(current_nonce)?;
+    let next_nonce = nonce.checked_add(1).ok_or(AuctionError::Overflow)?;
+    let to_shares = to_shares
+        .checked_add(amount)
+        .ok_or(AuctionError::Overflow)?;
+
+    let pub_vars = super::auction::TransferShares::PubVars {
+        item_id,
+        key: hashed_from_key,
+        to: hashed_to_key,
+        amount,
+        nonce,
+    };
+    let vars = super::auction::TransferShares::Vars {
+        sig: signature_words(&signature),
+    };
+    let mutations = super::auction::storage::mutations()
+        .share_balances(|map| {
+            map.entry(item_id, |map| {
+                map.entry(hashed_from_key, from_shares - amount)
+            })
+        })
+        .share_balances(|map| map.entry(item_id, |map| map.entry(hashed_to_key, to_shares)))
+        .share_nonces(|map| map.entry(hashed_from_key, next_nonce));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::TransferShares::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+    Ok(Solution {
+        data: vec![solution],
+    })
+}
+
+/// Tags a signature as a share transfer.
+const TRANSFER_SHARES: Word = 4;
@@ -0,0 +1,621 @@
+//! # Validate
+//! Contains pre-flight checks that mirror the constraints of each predicate.
+//!
+//! Each check runs against state that has already been fetched and returns
+//! the first [`Rule`] the solution would break, so a bad action fails
+//! locally instead of after submission. The solution builders run the check
+//! of their predicate over the state they read.
+use essential_types::{ContentAddress, Word};
+
+use crate::{
+    fractionalize::MAX_SUPPLY, reveal_raffle::SEED_MODULUS, AuctionItem, ListingStatus,
+    CURATOR_ADMIN, ESCROW_DISPUTED, ESCROW_HELD, ESCROW_SETTLED,
+};
+
+/// A predicate constraint that a solution would break.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// The amount must be positive.
+    AmountPositive,
+    /// A bid must cover the current cost.
+    AmountCoversCost,
+    /// The auction must not have ended.
+    AuctionNotEnded,
+    /// The item must not already be for sale.
+    NotForSale,
+    /// Only the owner can act on the item.
+    OwnerOnly,
+    /// A new auction must start at a positive cost.
+    InitialCostPositive,
+    /// An auction can only be created for an item that does not exist yet.
+    ItemUnlisted,
+    /// A step must lower the cost by a positive amount.
+    DecreasePositive,
+    /// A step must leave the cost positive.
+    CostRemainsPositive,
+    /// A listing must have a positive price.
+    PricePositive,
+    /// The payment must be in the token the item is priced in.
+    PaymentTokenMatches,
+    /// Fixed price listings are bought, not bid on or stepped.
+    NotFixedPrice,
+    /// Only fixed price listings are bought or delisted.
+    FixedPrice,
+    /// Ascending auctions are bid on through proxy bids.
+    NotAscending,
+    /// Raffled items are won through a draw.
+    NotRaffle,
+    /// Curated listings only take bids once approved.
+    ListingApproved,
+    /// The payer must hold the amount.
+    BalanceCoversAmount,
+    /// An account can only be minted to once.
+    MintOnce,
+    /// A buyer has one open offer on an item at a time.
+    NoOpenOffer,
+    /// There must be an open offer to accept or withdraw.
+    OfferOpen,
+    /// A request must escrow a positive budget.
+    BudgetPositive,
+    /// An ask must be positive.
+    AskPositive,
+    /// An ask must beat the budget or the best ask so far.
+    AskBeatsBest,
+    /// A request is only settled once a provider has asked.
+    ProviderSet,
+    /// An ascending auction must raise by a positive increment.
+    IncrementPositive,
+    /// The leading bidder can't bid against themselves.
+    NotLeader,
+    /// Reveals must close after bidding closes.
+    RevealsAfterBids,
+    /// A bidder seals one maximum per auction.
+    NotSealed,
+    /// A deposit must cover the visible price.
+    DepositCoversCost,
+    /// A deposit must cover the sealed maximum.
+    DepositCoversMax,
+    /// A maximum is only revealed once.
+    NotRevealed,
+    /// A revealed maximum must match its commitment.
+    CommitmentMatches,
+    /// An ascending auction is only settled once someone has bid.
+    LeaderSet,
+    /// An ascending auction is only cancelled while no one leads.
+    NoLeader,
+    /// There must be a refund to claim.
+    RefundPositive,
+    /// Reveals must close after entries close.
+    RevealsAfterEntries,
+    /// The item must be raffled.
+    Raffle,
+    /// A raffle secret must be below the seed modulus.
+    SecretInRange,
+    /// A raffle is only drawn once someone has entered.
+    EntriesPositive,
+    /// A raffle is only closed when it can't be drawn.
+    Undrawable,
+    /// Only a ticket holder is refunded.
+    TicketHeld,
+    /// A fractionalized item must have shares.
+    SupplyPositive,
+    /// The share supply is bounded so payouts can't overflow.
+    SupplyBounded,
+    /// A fractionalized item must have a positive buyout reserve.
+    ReservePositive,
+    /// The item must be fractionalized.
+    Fractionalized,
+    /// The sender must hold the shares.
+    SharesCoverAmount,
+    /// There must be shares to redeem.
+    SharesHeld,
+    /// A lease must last at least one block.
+    BlocksPositive,
+    /// The owner can't rent their own item.
+    RenterNotOwner,
+    /// An item is rented to one renter at a time.
+    NotRented,
+    /// The owner can't arbitrate their own sale.
+    ArbiterNotOwner,
+    /// The arbiter is only changed while no sale is held in escrow.
+    EscrowSettled,
+    /// Only the buyer acts on a sale held in escrow.
+    BuyerOnly,
+    /// The sale must be held in escrow.
+    EscrowHeld,
+    /// The sale must be disputed.
+    Disputed,
+    /// The arbiter refunds at most the escrowed amount.
+    RefundWithinEscrow,
+    /// A collection can only be created once.
+    CollectionUnused,
+    /// A royalty is at most the whole price.
+    RoyaltyInRange,
+    /// Only the creator manages a collection.
+    CreatorOnly,
+    /// An item is in at most one collection.
+    NotInCollection,
+    /// There must be royalties to claim.
+    RoyaltiesPositive,
+    /// The curator admin can only be set once.
+    CurationOff,
+    /// Only the key the contract was deployed with turns on curation.
+    DeployedAdminOnly,
+    /// Only the curator admin appoints curators.
+    CuratorAdminOnly,
+    /// Only a curator reviews listings.
+    CuratorOnly,
+    /// Only pending listings are reviewed.
+    ListingPending,
+    /// Only rejected listings are released.
+    ListingRejected,
+    /// An intent is only filled until it expires.
+    IntentUnexpired,
+    /// An intent is only filled at or below its limit.
+    LimitCoversCost,
+}
+
+impl std::fmt::Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rule = match self {
+            Rule::AmountPositive => "amount > 0",
+            Rule::AmountCoversCost => "amount >= current_cost",
+            Rule::AuctionNotEnded => "auction_ended == false",
+            Rule::NotForSale => "auction_ended == nil || auction_ended == true",
+            Rule::OwnerOnly => "key == owner",
+            Rule::InitialCostPositive => "initial_cost > 0",
+            Rule::ItemUnlisted => "@init_once(owner; key)",
+            Rule::DecreasePositive => "decrease_amt > 0",
+            Rule::CostRemainsPositive => "current_cost - decrease_amt > 0",
+            Rule::PricePositive => "price > 0",
+            Rule::PaymentTokenMatches => "payment_token == listing_token",
+            Rule::NotFixedPrice => "fixed_price == nil || fixed_price == false",
+            Rule::FixedPrice => "fixed_price == true",
+            Rule::NotAscending => "ascending == nil || ascending == false",
+            Rule::NotRaffle => "raffle == nil || raffle == false",
+            Rule::ListingApproved => "listing_status == nil || listing_status == 2",
+            Rule::BalanceCoversAmount => "balance >= amount",
+            Rule::MintOnce => "@init_once(balance; amount)",
+            Rule::NoOpenOffer => "offer == nil || offer == 0",
+            Rule::OfferOpen => "offer > 0",
+            Rule::BudgetPositive => "budget > 0",
+            Rule::AskPositive => "ask > 0",
+            Rule::AskBeatsBest => "ask < (current_ask == nil ? budget : current_ask)",
+            Rule::ProviderSet => "provider != nil",
+            Rule::IncrementPositive => "increment > 0",
+            Rule::NotLeader => "bidder != leader",
+            Rule::RevealsAfterBids => "reveals_close > deadline",
+            Rule::NotSealed => "bidder_commitment == nil",
+            Rule::DepositCoversCost => "deposit >= current_cost",
+            Rule::DepositCoversMax => "max <= deposit",
+            Rule::NotRevealed => "revealed == nil",
+            Rule::CommitmentMatches => "commitment == __sha256({ max, salt })",
+            Rule::LeaderSet => "leader != nil",
+            Rule::NoLeader => "leader == nil",
+            Rule::RefundPositive => "refund > 0",
+            Rule::RevealsAfterEntries => "reveals_close > entries_close",
+            Rule::Raffle => "raffle == true",
+            Rule::SecretInRange => "secret >= 0 && secret < 4294967296",
+            Rule::EntriesPositive => "entry_count > 0",
+            Rule::Undrawable => "seller_revealed != true || entry_count == 0",
+            Rule::TicketHeld => "held == true",
+            Rule::SupplyPositive => "supply > 0",
+            Rule::SupplyBounded => "supply <= 2147483648",
+            Rule::ReservePositive => "reserve > 0",
+            Rule::Fractionalized => "fractionalized == true",
+            Rule::SharesCoverAmount => "sender_shares' >= 0",
+            Rule::SharesHeld => "holder_shares > 0",
+            Rule::BlocksPositive => "blocks > 0",
+            Rule::RenterNotOwner => "key != owner",
+            Rule::NotRented => "expiry == nil || block_number >= expiry",
+            Rule::ArbiterNotOwner => "arbiter != key",
+            Rule::EscrowSettled => "escrow_status == nil || escrow_status == 3",
+            Rule::BuyerOnly => "buyer == escrow_buyer",
+            Rule::EscrowHeld => "escrow_status == 1",
+            Rule::Disputed => "escrow_status == 2",
+            Rule::RefundWithinEscrow => "refund >= 0 && refund <= escrow_amount",
+            Rule::CollectionUnused => "@init_once(creator; key)",
+            Rule::RoyaltyInRange => "royalty >= 0 && royalty <= 10000",
+            Rule::CreatorOnly => "key == creator",
+            Rule::NotInCollection => "item_collection == nil",
+            Rule::RoyaltiesPositive => "royalties > 0",
+            Rule::CurationOff => "curator_admin == nil",
+            Rule::DeployedAdminOnly => "key == CURATOR_ADMIN",
+            Rule::CuratorAdminOnly => "key == curator_admin",
+            Rule::CuratorOnly => "is_curator == true",
+            Rule::ListingPending => "listing_status == 1",
+            Rule::ListingRejected => "listing_status == 3",
+            Rule::IntentUnexpired => "block_number <= expiry",
+            Rule::LimitCoversCost => "current_cost <= limit",
+        };
+        write!(f, "{}", rule)
+    }
+}
+
+/// Fails with `rule` unless `holds`.
+fn check(holds: bool, rule: Rule) -> Result<(), Rule> {
+    if holds {
+        Ok(())
+    } else {
+        Err(rule)
+    }
+}
+
+/// Checks the constraints of `Mint`.
+pub fn mint(current_balance: Option<Word>, amount: Word) -> Result<(), Rule> {
+    check(current_balance.is_none(), Rule::MintOnce)?;
+    check(amount > 0, Rule::AmountPositive)
+}
+
+/// Checks the constraints of `Transfer`.
+pub fn transfer(balance: Word, amount: Word) -> Result<(), Rule> {
+    check(amount > 0, Rule::AmountPositive)?;
+    check(balance >= amount, Rule::BalanceCoversAmount)
+}
+
+/// Checks the constraints of `CreateAuction`.
+pub fn create_auction(existing: Option<&AuctionItem>, initial_cost: Word) -> Result<(), Rule> {
+    check(existing.is_none(), Rule::ItemUnlisted)?;
+    check(initial_cost > 0, Rule::InitialCostPositive)
+}
+
+/// Checks the constraints of `StepAuction`.
+pub fn step_auction(item: &AuctionItem, key: [Word; 4], decrease: Word) -> Result<(), Rule> {
+    check(item.active, Rule::AuctionNotEnded)?;
+    dutch_auction(item)?;
+    check(decrease > 0, Rule::DecreasePositive)?;
+    check(
+        matches!(item.cost.checked_sub(decrease), Some(cost) if cost > 0),
+        Rule::CostRemainsPositive,
+    )?;
+    check(key == item.owner, Rule::OwnerOnly)
+}
+
+/// Checks the constraints of `PlaceBid`.
+pub fn place_bid(
+    item: &AuctionItem,
+    amount: Word,
+    payment_token: &ContentAddress,
+    bidder_balance: Word,
+) -> Result<(), Rule> {
+    check(item.active, Rule::AuctionNotEnded)?;
+    dutch_auction(item)?;
+    check(item.status.accepts_bids(), Rule::ListingApproved)?;
+    check(
+        *payment_token == item.payment_token,
+        Rule::PaymentTokenMatches,
+    )?;
+    check(amount > 0, Rule::AmountPositive)?;
+    check(amount >= item.cost, Rule::AmountCoversCost)?;
+    check(bidder_balance >= amount, Rule::BalanceCoversAmount)
+}
+
+/// Checks the constraints of `List`.
+pub fn list(existing: Option<&AuctionItem>, key: [Word; 4], price: Word) -> Result<(), Rule> {
+    check(price > 0, Rule::PricePositive)?;
+    if let Some(item) = existing {
+        check(key == item.owner, Rule::OwnerOnly)?;
+        check(!item.active, Rule::NotForSale)?;
+    }
+    Ok(())
+}
+
+/// Checks the constraints of `Delist`.
+pub fn delist(item: &AuctionItem, key: [Word; 4]) -> Result<(), Rule> {
+    check(key == item.owner, Rule::OwnerOnly)?;
+    check(item.fixed_price, Rule::FixedPrice)?;
+    check(item.active, Rule::AuctionNotEnded)
+}
+
+/// Checks the constraints of `Buy`.
+///
+/// The buyer always pays in the token the item is listed in.
+pub fn buy(item: &AuctionItem, buyer_balance: Word) -> Result<(), Rule> {
+    check(item.fixed_price, Rule::FixedPrice)?;
+    check(item.active, Rule::AuctionNotEnded)?;
+    check(item.status.accepts_bids(), Rule::ListingApproved)?;
+    check(buyer_balance >= item.cost, Rule::BalanceCoversAmount)
+}
+
+/// Checks the constraints of `MakeOffer`.
+pub fn make_offer(open_offer: Word, amount: Word) -> Result<(), Rule> {
+    check(open_offer == 0, Rule::NoOpenOffer)?;
+    check(amount > 0, Rule::AmountPositive)
+}
+
+/// Checks the constraints of `AcceptOffer`.
+pub fn accept_offer(offer: Word) -> Result<(), Rule> {
+    check(offer > 0, Rule::OfferOpen)
+}
+
+/// Checks the constraints of `WithdrawOffer`.
+pub fn withdraw_offer(offer: Word) -> Result<(), Rule> {
+    check(offer > 0, Rule::OfferOpen)
+}
+
+/// Checks the constraints of `CreateRequest`.
+pub fn create_request(budget: Word) -> Result<(), Rule> {
+    check(budget > 0, Rule::BudgetPositive)
+}
+
+/// Checks the constraints of `SubmitAsk`.
+pub fn submit_ask(ask: Word, budget: Word, best_ask: Option<Word>) -> Result<(), Rule> {
+    check(ask > 0, Rule::AskPositive)?;
+    check(ask < best_ask.unwrap_or(budget), Rule::AskBeatsBest)
+}
+
+/// Checks the constraints of `SettleRequest`.
+///
+/// Every ask names its provider, so a request with an ask has a provider.
+pub fn settle_request(best_ask: Option<Word>) -> Result<(), Rule> {
+    check(best_ask.is_some(), Rule::ProviderSet)
+}
+
+/// Checks the constraints of `CreateAscendingAuction`.
+pub fn create_ascending_auction(
+    reserve: Word,
+    increment: Word,
+    deadline: Word,
+    reveals_close: Word,
+) -> Result<(), Rule> {
+    check(reserve > 0, Rule::InitialCostPositive)?;
+    check(increment > 0, Rule::IncrementPositive)?;
+    check(reveals_close > deadline, Rule::RevealsAfterBids)
+}
+
+/// Checks the constraints of `CommitBid`.
+pub fn commit_bid(
+    sealed: Option<[Word; 4]>,
+    max: Word,
+    deposit: Word,
+    cost: Word,
+) -> Result<(), Rule> {
+    check(sealed.is_none(), Rule::NotSealed)?;
+    check(deposit >= cost, Rule::DepositCoversCost)?;
+    check(max <= deposit, Rule::DepositCoversMax)
+}
+
+/// Checks the constraints shared by `ProxyBid` and `RaiseBid`.
+///
+/// `sealed` is the bidder's stored commitment and `commitment` the one
+/// computed from the revealed maximum and salt.
+pub fn proxy_bid(
+    bidder: [Word; 4],
+    max: Word,
+    deposit: Word,
+    leader: Option<[Word; 4]>,
+    sealed: Option<[Word; 4]>,
+    commitment: [Word; 4],
+    revealed: bool,
+) -> Result<(), Rule> {
+    check(!revealed, Rule::NotRevealed)?;
+    check(sealed == Some(commitment), Rule::CommitmentMatches)?;
+    check(max <= deposit, Rule::DepositCoversMax)?;
+    check(leader != Some(bidder), Rule::NotLeader)
+}
+
+/// Checks the constraints of `SettleAscending`.
+pub fn settle_ascending(leader: Option<[Word; 4]>) -> Result<(), Rule> {
+    check(leader.is_some(), Rule::LeaderSet)
+}
+
+/// Checks the constraints of `CancelAscending`.
+pub fn cancel_ascending(leader: Option<[Word; 4]>) -> Result<(), Rule> {
+    check(leader.is_none(), Rule::NoLeader)
+}
+
+/// Checks the constraints of `ClaimRefund`.
+pub fn claim_refund(refund: Word) -> Result<(), Rule> {
+    check(refund > 0, Rule::RefundPositive)
+}
+
+/// Checks the constraints of `CreateRaffle`.
+pub fn create_raffle(price: Word, entries_close: Word, reveals_close: Word) -> Result<(), Rule> {
+    check(price > 0, Rule::PricePositive)?;
+    check(reveals_close > entries_close, Rule::RevealsAfterEntries)
+}
+
+/// Checks the constraints of `EnterRaffle`.
+///
+/// Only raffles have a ticket price, and it is always positive.
+pub fn enter_raffle(ticket_price: Word) -> Result<(), Rule> {
+    check(ticket_price > 0, Rule::Raffle)
+}
+
+/// Checks the constraints of `RevealRaffle`.
+pub fn reveal_raffle(secret: Word) -> Result<(), Rule> {
+    check((0..SEED_MODULUS).contains(&secret), Rule::SecretInRange)
+}
+
+/// Checks the constraints of `DrawRaffle`.
+pub fn draw_raffle(entry_count: Word) -> Result<(), Rule> {
+    check(entry_count > 0, Rule::EntriesPositive)
+}
+
+/// Checks the constraints of `CloseRaffle`.
+pub fn close_raffle(seller_revealed: bool, entry_count: Word) -> Result<(), Rule> {
+    check(!seller_revealed || entry_count == 0, Rule::Undrawable)
+}
+
+/// Checks the constraints of `RefundTicket`.
+pub fn refund_ticket(held: bool) -> Result<(), Rule> {
+    check(held, Rule::TicketHeld)
+}
+
+/// Checks the constraints of `Fractionalize`.
+pub fn fractionalize(
+    owner: [Word; 4],
+    key: [Word; 4],
+    supply: Word,
+    reserve: Word,
+) -> Result<(), Rule> {
+    check(key == owner, Rule::OwnerOnly)?;
+    check(supply > 0, Rule::SupplyPositive)?;
+    check(supply <= MAX_SUPPLY, Rule::SupplyBounded)?;
+    check(reserve > 0, Rule::ReservePositive)
+}
+
+/// Checks the constraints of `TransferShares`.
+pub fn transfer_shares(shares: Word, amount: Word) -> Result<(), Rule> {
+    check(amount > 0, Rule::AmountPositive)?;
+    check(shares >= amount, Rule::SharesCoverAmount)
+}
+
+/// Checks the constraints of `Buyout`.
+///
+/// Only fractionalized items have a buyout reserve, and it is always
+/// positive.
+pub fn buyout(reserve: Word) -> Result<(), Rule> {
+    check(reserve > 0, Rule::Fractionalized)
+}
+
+/// Checks the constraints of `RedeemShares`.
+pub fn redeem_shares(shares: Word) -> Result<(), Rule> {
+    check(shares > 0, Rule::SharesHeld)
+}
+
+/// Checks the constraints of `OfferRental`.
+pub fn offer_rental(
+    owner: [Word; 4],
+    key: [Word; 4],
+    price: Word,
+    blocks: Word,
+) -> Result<(), Rule> {
+    check(key == owner, Rule::OwnerOnly)?;
+    check(price > 0, Rule::PricePositive)?;
+    check(blocks > 0, Rule::BlocksPositive)
+}
+
+/// Checks the constraints of `Rent`.
+pub fn rent(
+    owner: [Word; 4],
+    key: [Word; 4],
+    price: Word,
+    for_sale: bool,
+    expiry: Option<Word>,
+    block_number: Word,
+) -> Result<(), Rule> {
+    check(price > 0, Rule::PricePositive)?;
+    check(key != owner, Rule::RenterNotOwner)?;
+    check(!for_sale, Rule::NotForSale)?;
+    check(
+        expiry.map_or(true, |expiry| block_number >= expiry),
+        Rule::NotRented,
+    )
+}
+
+/// Checks the constraints of `SetArbiter`.
+pub fn set_arbiter(
+    owner: [Word; 4],
+    key: [Word; 4],
+    arbiter: [Word; 4],
+    escrow_status: Word,
+) -> Result<(), Rule> {
+    check(key == owner, Rule::OwnerOnly)?;
+    check(arbiter != key, Rule::ArbiterNotOwner)?;
+    check(
+        escrow_status == 0 || escrow_status == ESCROW_SETTLED,
+        Rule::EscrowSettled,
+    )
+}
+
+/// Checks the constraints of `ConfirmDelivery`.
+pub fn confirm_delivery(buyer: [Word; 4], key: [Word; 4], escrow_status: Word) -> Result<(), Rule> {
+    held_by(buyer, key, escrow_status)
+}
+
+/// Checks the constraints of `OpenDispute`.
+pub fn open_dispute(buyer: [Word; 4], key: [Word; 4], escrow_status: Word) -> Result<(), Rule> {
+    held_by(buyer, key, escrow_status)
+}
+
+/// Checks the constraints of `Resolve`.
+pub fn resolve(escrow_status: Word, refund: Word, escrow_amount: Word) -> Result<(), Rule> {
+    check(escrow_status == ESCROW_DISPUTED, Rule::Disputed)?;
+    check(
+        (0..=escrow_amount).contains(&refund),
+        Rule::RefundWithinEscrow,
+    )
+}
+
+/// Checks the constraints of `CreateCollection`.
+pub fn create_collection(creator: Option<[Word; 4]>, royalty: Word) -> Result<(), Rule> {
+    check(creator.is_none(), Rule::CollectionUnused)?;
+    check((0..=10000).contains(&royalty), Rule::RoyaltyInRange)
+}
+
+/// Checks the constraints of `AddCollectionItem`.
+pub fn add_collection_item(
+    creator: [Word; 4],
+    owner: [Word; 4],
+    key: [Word; 4],
+    item_collection: Option<Word>,
+) -> Result<(), Rule> {
+    check(key == creator, Rule::CreatorOnly)?;
+    check(key == owner, Rule::OwnerOnly)?;
+    check(item_collection.is_none(), Rule::NotInCollection)
+}
+
+/// Checks the constraints of `AllowSeller`.
+pub fn allow_seller(creator: [Word; 4], key: [Word; 4]) -> Result<(), Rule> {
+    check(key == creator, Rule::CreatorOnly)
+}
+
+/// Checks the constraints of `ClaimRoyalties`.
+pub fn claim_royalties(royalties: Word) -> Result<(), Rule> {
+    check(royalties > 0, Rule::RoyaltiesPositive)
+}
+
+/// Checks the constraints of `SetCuratorAdmin`.
+pub fn set_curator_admin(curator_admin: Option<[Word; 4]>, key: [Word; 4]) -> Result<(), Rule> {
+    check(curator_admin.is_none(), Rule::CurationOff)?;
+    check(key == CURATOR_ADMIN, Rule::DeployedAdminOnly)
+}
+
+/// Checks the constraints of `SetCurator`.
+pub fn set_curator(curator_admin: Option<[Word; 4]>, key: [Word; 4]) -> Result<(), Rule> {
+    check(curator_admin == Some(key), Rule::CuratorAdminOnly)
+}
+
+/// Checks the constraints of `ReviewListing`.
+pub fn review_listing(is_curator: bool, status: ListingStatus) -> Result<(), Rule> {
+    check(is_curator, Rule::CuratorOnly)?;
+    check(status == ListingStatus::Pending, Rule::ListingPending)
+}
+
+/// Checks the constraints of `ReleaseListing`.
+pub fn release_listing(
+    owner: [Word; 4],
+    key: [Word; 4],
+    status: ListingStatus,
+) -> Result<(), Rule> {
+    check(key == owner, Rule::OwnerOnly)?;
+    check(status == ListingStatus::Rejected, Rule::ListingRejected)
+}
+
+/// Checks the constraints of `FillBidIntent`.
+pub fn fill_bid_intent(
+    fixed_price: bool,
+    cost: Word,
+    limit: Word,
+    expiry: Word,
+    block_number: Word,
+) -> Result<(), Rule> {
+    check(!fixed_price, Rule::NotFixedPrice)?;
+    check(block_number <= expiry, Rule::IntentUnexpired)?;
+    check(cost <= limit, Rule::LimitCoversCost)
+}
+
+/// The constraints shared by predicates the buyer of a sale held in escrow
+/// signs.
+fn held_by(buyer: [Word; 4], key: [Word; 4], escrow_status: Word) -> Result<(), Rule> {
+    check(key == buyer, Rule::BuyerOnly)?;
+    check(escrow_status == ESCROW_HELD, Rule::EscrowHeld)
+}
+
+/// The constraints shared by predicates that act on a Dutch auction.
+fn dutch_auction(item: &AuctionItem) -> Result<(), Rule> {
+    check(!item.fixed_price, Rule::NotFixedPrice)?;
+    check(!item.ascending, Rule::NotAscending)?;
+    check(!item.raffle, Rule::NotRaffle)
+}
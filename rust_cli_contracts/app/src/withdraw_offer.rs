@@ -0,0 +1,100 @@
+//! # Withdraw Offer
+//! Contains functionality for a buyer withdrawing an escrowed offer.
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{
+    solution::{Solution, SolutionData},
+    ContentAddress, Word,
+};
+
+use crate::{deployed_at, offer, release, signature_words, validate, AuctionError, Query};
+
+/// Contains all necessary information to build a withdraw offer solution.
+pub struct BuildSolution {
+    /// The item the offer is for.
+    pub item_id: Word,
+    /// The hashed key of the buyer.
+    pub hashed_key: [Word; 4],
+    /// The token contract the offer is escrowed in.
+    pub payment_token: ContentAddress,
+    /// The buyer's current offer on the item.
+    pub current_offer: Query,
+    /// The current balance of the buyer.
+    pub current_balance: Query,
+    /// The current balance of the escrow.
+    pub current_escrow_balance: Query,
+    /// The buyer's current offer nonce.
+    pub current_nonce: Query,
+    /// The buyer's signature over the data from [`data_to_sign`].
+    pub signature: RecoverableSignature,
+    /// The address the auction contract is deployed at.
+    pub contract: ContentAddress,
+}
+
+/// The words the buyer signs to withdraw an offer.
+///
+/// The nonce is the buyer's current offer nonce.
+pub fn data_to_sign(item_id: Word, hashed_key: [Word; 4], nonce: Word) -> Vec<Word> {
+    let mut words = vec![item_id];
+    words.extend(hashed_key);
+    words.push(nonce);
+    words
+}
+
+/// Builds a withdraw offer solution based on the provided data.
+pub fn build_solution(build: BuildSolution) -> Result<Solution, AuctionError> {
+    let BuildSolution {
+        item_id,
+        hashed_key,
+        payment_token,
+        current_offer,
+        current_balance,
+        current_escrow_balance,
+        current_nonce,
+        signature,
+        contract,
+    } = build;
+
+    let amount = offer(current_offer)?;
+    validate::withdraw_offer(amount)?;
+    let nonce = offer(current_nonce)?;
+    let next_nonce = nonce.checked_add(1).ok_or(AuctionError::Overflow)?;
+
+    let pub_vars = super::auction::WithdrawOffer::PubVars {
+        item_id,
+        buyer: hashed_key,
+        nonce,
+    };
+    let vars = super::auction::WithdrawOffer::Vars {
+        sig: signature_words(&signature),
+        release_pathway: RELEASE_PATHWAY,
+    };
+    let mutations = super::auction::storage::mutations()
+        .offers(|map| map.entry(item_id, |map| map.entry(hashed_key, 0)))
+        .offer_nonces(|map| map.entry(hashed_key, next_nonce));
+    let solution = SolutionData {
+        predicate_to_solve: deployed_at(super::auction::WithdrawOffer::ADDRESS, &contract),
+        decision_variables: vars.into(),
+        transient_data: pub_vars.into(),
+        state_mutations: mutations.into(),
+    };
+
+    // The escrowed offer is refunded to the buyer.
+    let [release, withdraw] = release::build_solution_data(
+        release::BuildSolution {
+            settlement: super::auction::WithdrawOffer::ADDRESS,
+            payment_token,
+            hashed_to_key: hashed_key,
+            amount,
+            current_escrow_balance,
+            current_to_balance: current_balance,
+            contract: contract.clone(),
+        },
+        RELEASE_PATHWAY,
+    )?;
+    Ok(Solution {
+        data: vec![solution, release, withdraw],
+    })
+}
+
+/// The index of the escrow refund within a withdraw offer solution.
+const RELEASE_PATHWAY: Word = 1;
@@ -0,0 +1,4094 @@
+use essential_app_utils::{self as utils, compile::compile_pint_project, db::Dbs};
+use essential_sign::secp256k1::ecdsa::RecoverableSignature;
+use essential_types::{convert::word_4_from_u8_32, Word};
+use essential_wallet::Wallet;
+use token::Query;
+
+/// The id of the item being auctioned.
+const ITEM_ID: Word = 0;
+/// The initial cost of the item.
+const INITIAL_COST: Word = 100;
+/// The amount the seller steps the auction down by.
+const DECREASE_AMT: Word = 20;
+/// The amount a buyer is funded with before buying the item.
+const FUNDS: Word = 1000;
+
+const INCREMENT: Word = 10;
+
+const TICKET_PRICE: Word = 50;
+
+const RAFFLE_SALT: [Word; 4] = [1, 2, 3, 4];
+
+const BID_SALT: [Word; 4] = [5, 6, 7, 8];
+
+/// The secret of the development keeper that the block state contract trusts.
+const KEEPER_SECRET: [u8; 32] = [1; 32];
+
+/// The secret of the development curator admin the auction contract is deployed with.
+const CURATOR_ADMIN_SECRET: [u8; 32] = [2; 32];
+
+#[tokio::test]
+async fn auction_lifecycle_with_unfunded_seller() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+
+    // Alice is the bidder and Bob is a seller that has never minted.
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+    let alice_balance_key = token::balance_key(alice_hashed_key);
+    let bob_balance_key = token::balance_key(bob_hashed_key);
+
+    // Fund Alice only
+    let first_mint_amount = 1000;
+    let balance =
+        utils::node::query_state_head(&dbs.node, &token::token::ADDRESS, &alice_balance_key)
+            .await
+            .unwrap();
+    let solution = token::mint::build_solution(token::mint::BuildSolution {
+        current_balance: Query(balance),
+        hashed_key: alice_hashed_key,
+        amount: first_mint_amount,
+    })
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    // Bob has no balance entry at all
+    let balance =
+        utils::node::query_state_head(&dbs.node, &token::token::ADDRESS, &bob_balance_key)
+            .await
+            .unwrap();
+    assert!(balance.is_none());
+
+    // Bob lists the item
+    let solution = token::create_auction::build_solution(token::create_auction::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_key: bob_hashed_key,
+        amount: INITIAL_COST,
+        payment_token: token::token::ADDRESS,
+        collection: None,
+        current_curator_admin: Query(None),
+        current_curated_item_count: Query(None),
+        block_state: token::block_state::ADDRESS,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    // Bob steps the price down
+    let cost_key = token::cost_key(ITEM_ID);
+    let current_cost =
+        utils::node::query_state_head(&dbs.node, &token::auction::ADDRESS, &cost_key)
+            .await
+            .unwrap();
+    let solution = token::step_auction::build_solution(token::step_auction::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_key: bob_hashed_key,
+        amount: DECREASE_AMT,
+        current_cost: Query(current_cost),
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    // Alice bids the current price
+    let bid = INITIAL_COST - DECREASE_AMT;
+    let solution = place_bid_solution(&dbs, alice_hashed_key, bid)
+        .await
+        .unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    // Bob's nil balance has been initialized with the proceeds
+    let balance =
+        utils::node::query_state_head(&dbs.node, &token::token::ADDRESS, &bob_balance_key)
+            .await
+            .unwrap();
+    assert_eq!(token::balance(Query(balance)).unwrap(), bid);
+
+    let balance =
+        utils::node::query_state_head(&dbs.node, &token::token::ADDRESS, &alice_balance_key)
+            .await
+            .unwrap();
+    assert_eq!(
+        token::balance(Query(balance)).unwrap(),
+        first_mint_amount - bid
+    );
+
+    // Alice now owns the item and the auction is over
+    let owner_key = token::get_item_owner_key(ITEM_ID);
+    let owner = utils::node::query_state_head(&dbs.node, &token::auction::ADDRESS, &owner_key)
+        .await
+        .unwrap();
+    assert_eq!(token::item_owner(Query(owner)).unwrap(), alice_hashed_key);
+
+    let status_key = token::auction_status_key(ITEM_ID);
+    let status = utils::node::query_state_head(&dbs.node, &token::auction::ADDRESS, &status_key)
+        .await
+        .unwrap();
+    assert_eq!(status, Some(vec![1]));
+}
+
+#[tokio::test]
+async fn unfunded_bidder_cannot_buy_from_unfunded_seller() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+
+    // Neither account has ever minted.
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+
+    let solution = token::create_auction::build_solution(token::create_auction::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_key: bob_hashed_key,
+        amount: INITIAL_COST,
+        payment_token: token::token::ADDRESS,
+        collection: None,
+        current_curator_admin: Query(None),
+        current_curated_item_count: Query(None),
+        block_state: token::block_state::ADDRESS,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    // The missing bidder balance is read as zero rather than panicking,
+    // and the bid is refused before anything is submitted.
+    let err = place_bid_solution(&dbs, alice_hashed_key, INITIAL_COST)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        token::AuctionError::InsufficientBalance {
+            available: 0,
+            required: INITIAL_COST,
+        }
+    );
+
+    // The item is still listed by Bob
+    let owner_key = token::get_item_owner_key(ITEM_ID);
+    let owner = utils::node::query_state_head(&dbs.node, &token::auction::ADDRESS, &owner_key)
+        .await
+        .unwrap();
+    assert_eq!(token::item_owner(Query(owner)).unwrap(), bob_hashed_key);
+}
+
+#[tokio::test]
+async fn bid_on_unlisted_item_is_an_error() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+
+    // There is no owner entry for an item that was never listed.
+    assert!(place_bid_solution(&dbs, alice_hashed_key, INITIAL_COST)
+        .await
+        .is_err());
+}
+
+#[test]
+fn proxy_bids_only_raise_the_price_as_needed() {
+    use token::proxy_bid::{standing, visible_price, Standing};
+
+    // The higher maximum wins one increment above the lower one.
+    assert_eq!(visible_price(150, 200, 10), 160);
+    assert_eq!(visible_price(200, 150, 10), 160);
+    // But never more than its own maximum.
+    assert_eq!(visible_price(195, 200, 10), 200);
+
+    assert_eq!(standing(100, None, 300, 10), Standing::Winning(100));
+    // A first maximum below the reserve leads no one.
+    assert_eq!(standing(100, None, 90, 10), Standing::Outbid(100));
+    assert_eq!(standing(100, Some(150), 200, 10), Standing::Winning(160));
+    // Ties go to the existing leader.
+    assert_eq!(standing(100, Some(150), 150, 10), Standing::Outbid(150));
+    assert_eq!(standing(100, Some(300), 120, 10), Standing::Outbid(130));
+}
+
+#[test]
+fn raffle_secrets_are_bounded_and_pick_an_entry() {
+    use token::{
+        draw_raffle::winning_index,
+        reveal_raffle::{commitment, SEED_MODULUS},
+    };
+
+    let salt = [1, 2, 3, 4];
+    assert_eq!(commitment(7, salt).unwrap(), commitment(7, salt).unwrap());
+    assert_ne!(commitment(7, salt).unwrap(), commitment(8, salt).unwrap());
+    assert!(commitment(SEED_MODULUS, salt).is_err());
+    assert!(commitment(-1, salt).is_err());
+
+    assert_eq!(winning_index(10, 3).unwrap(), 1);
+    assert!(winning_index(10, 0).is_err());
+}
+
+#[test]
+fn buyouts_are_split_pro_rata() {
+    use token::redeem_shares::pro_rata;
+
+    assert_eq!(pro_rata(25, 100, 1000).unwrap(), 250);
+    // Remainders stay in escrow.
+    assert_eq!(pro_rata(1, 3, 100).unwrap(), 33);
+    assert!(pro_rata(1, 0, 100).is_err());
+    assert!(pro_rata(Word::MAX, 1, 2).is_err());
+    // Any reserve can be redeemed from a bounded supply.
+    let max_supply = token::fractionalize::MAX_SUPPLY;
+    assert_eq!(
+        pro_rata(max_supply, max_supply, Word::MAX).unwrap(),
+        Word::MAX
+    );
+    assert_eq!(pro_rata(2, 3, Word::MAX).unwrap(), Word::MAX / 3 * 2);
+}
+
+#[test]
+fn renter_uses_item_until_lease_expires() {
+    let owner = [1; 4];
+    let renter = [2; 4];
+
+    assert_eq!(token::user_of(owner, None, 0, 10), owner);
+    assert_eq!(token::user_of(owner, Some(renter), 20, 10), renter);
+    assert_eq!(token::user_of(owner, Some(renter), 20, 20), owner);
+}
+
+#[test]
+fn collection_sales_split_off_the_royalty() {
+    use token::buy::royalty;
+
+    assert_eq!(royalty(1000, 250).unwrap(), 25);
+    assert_eq!(royalty(1000, 10000).unwrap(), 1000);
+    // Fractions of a token go to the seller.
+    assert_eq!(royalty(99, 100).unwrap(), 0);
+    assert!(royalty(Word::MAX, 2).is_err());
+}
+
+#[test]
+fn only_approved_or_uncurated_auctions_take_bids() {
+    use token::{listing_status, ListingStatus};
+
+    let status = |words: Option<Vec<Word>>| listing_status(Query(words)).unwrap();
+    assert_eq!(status(None), ListingStatus::Uncurated);
+    assert_eq!(status(Some(vec![1])), ListingStatus::Pending);
+    assert!(status(None).accepts_bids());
+    assert!(status(Some(vec![2])).accepts_bids());
+    assert!(!status(Some(vec![1])).accepts_bids());
+    assert!(!status(Some(vec![3])).accepts_bids());
+    assert!(listing_status(Query(Some(vec![4]))).is_err());
+}
+
+#[test]
+fn bid_intents_round_trip_and_fill_within_limit() {
+    use token::bid_intent::{data_to_sign, fillable, BidIntent};
+
+    let mut wallet = Wallet::temp().unwrap();
+    let bidder = new_account(&mut wallet, "alice");
+    let data = data_to_sign(ITEM_ID, 60, 10, &token::token::ADDRESS);
+    let essential_signer::Signature::Secp256k1(signature) =
+        wallet.sign_words(&data, "alice").unwrap()
+    else {
+        panic!("Invalid signature")
+    };
+    let intent = BidIntent {
+        item_id: ITEM_ID,
+        bidder,
+        limit: 60,
+        expiry: 10,
+        payment_token: token::token::ADDRESS,
+        signature,
+    };
+
+    let decoded = BidIntent::from_json(&intent.to_json()).unwrap();
+    assert_eq!(decoded.bidder, bidder);
+    assert_eq!(decoded.limit, 60);
+    assert_eq!(decoded.signature, signature);
+
+    assert!(!fillable(&intent, INITIAL_COST, 0));
+    assert!(fillable(&intent, INITIAL_COST - 2 * DECREASE_AMT, 10));
+    assert!(!fillable(&intent, INITIAL_COST - 2 * DECREASE_AMT, 11));
+}
+
+#[test]
+fn builder_errors_can_be_matched() {
+    use token::{validate::Rule, AuctionError};
+
+    let err = token::mint::build_solution(token::mint::BuildSolution {
+        current_balance: Query(Some(vec![FUNDS])),
+        hashed_key: [0; 4],
+        amount: 1,
+    })
+    .unwrap_err();
+    assert_eq!(err, AuctionError::Violation(Rule::MintOnce));
+
+    let err = token::transfer::build_solution(token::transfer::BuildSolution {
+        hashed_from_key: [1; 4],
+        hashed_to_key: [2; 4],
+        amount: 1,
+        current_from_balance: Query(Some(vec![FUNDS])),
+        current_to_balance: Query(Some(vec![Word::MAX])),
+        authority: 0,
+    })
+    .unwrap_err();
+    assert_eq!(err, AuctionError::Overflow);
+
+    // A transfer larger than the balance is refused rather than leaving the
+    // sender with a negative balance.
+    let err = token::transfer::build_solution(token::transfer::BuildSolution {
+        hashed_from_key: [1; 4],
+        hashed_to_key: [2; 4],
+        amount: FUNDS + 1,
+        current_from_balance: Query(Some(vec![FUNDS])),
+        current_to_balance: Query(None),
+        authority: 0,
+    })
+    .unwrap_err();
+    assert_eq!(
+        err,
+        AuctionError::InsufficientBalance {
+            available: FUNDS,
+            required: FUNDS + 1,
+        }
+    );
+
+    // A negative step would raise the price, and a step past zero is not
+    // an overflow.
+    let step = |decrease| {
+        token::step_auction::build_solution(token::step_auction::BuildSolution {
+            item_id: ITEM_ID,
+            hashed_key: [1; 4],
+            amount: decrease,
+            current_cost: Query(Some(vec![INITIAL_COST])),
+            contract: token::auction::ADDRESS,
+        })
+        .unwrap_err()
+    };
+    assert_eq!(
+        step(-DECREASE_AMT),
+        AuctionError::Violation(Rule::DecreasePositive)
+    );
+    assert_eq!(
+        step(INITIAL_COST),
+        AuctionError::Violation(Rule::CostRemainsPositive)
+    );
+    assert_eq!(
+        step(Word::MIN),
+        AuctionError::Violation(Rule::DecreasePositive)
+    );
+
+    let err = token::place_bid::build_solution(token::place_bid::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_from_key: [1; 4],
+        hashed_to_key: [2; 4],
+        amount: INITIAL_COST - 1,
+        current_from_balance: Query(Some(vec![INITIAL_COST])),
+        current_to_balance: Query(None),
+        current_cost: Query(Some(vec![INITIAL_COST])),
+        payment_token: token::token::ADDRESS,
+        current_arbiter: Query(None),
+        current_escrow_sale: Query(None),
+        current_collection: Query(None),
+        current_royalty_rate: Query(None),
+        current_royalties: Query(None),
+        current_proceeds: Query(None),
+        block_state: token::block_state::ADDRESS,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap_err();
+    assert_eq!(
+        err,
+        AuctionError::PriceBelowCost {
+            price: INITIAL_COST - 1,
+            cost: INITIAL_COST,
+        }
+    );
+
+    assert!(matches!(
+        token::cost(Query(Some(vec![1, 2]))),
+        Err(AuctionError::MalformedState(_))
+    ));
+}
+
+#[test]
+fn preflight_checks_name_the_broken_rule() {
+    use token::{validate, validate::Rule, AuctionItem, ListingStatus};
+
+    let owner = [2; 4];
+    let item = AuctionItem {
+        id: ITEM_ID,
+        cost: INITIAL_COST,
+        owner,
+        active: true,
+        payment_token: token::token::ADDRESS,
+        fixed_price: false,
+        ascending: false,
+        raffle: false,
+        status: ListingStatus::Uncurated,
+    };
+
+    assert_eq!(
+        validate::place_bid(&item, INITIAL_COST, &token::token::ADDRESS, INITIAL_COST),
+        Ok(())
+    );
+    assert_eq!(
+        validate::place_bid(
+            &item,
+            INITIAL_COST - 1,
+            &token::token::ADDRESS,
+            INITIAL_COST
+        ),
+        Err(Rule::AmountCoversCost)
+    );
+    assert_eq!(
+        validate::place_bid(&item, INITIAL_COST, &token::token::ADDRESS, 0),
+        Err(Rule::BalanceCoversAmount)
+    );
+    let pending = AuctionItem {
+        status: ListingStatus::Pending,
+        ..item.clone()
+    };
+    assert_eq!(
+        validate::place_bid(&pending, INITIAL_COST, &token::token::ADDRESS, INITIAL_COST),
+        Err(Rule::ListingApproved)
+    );
+
+    assert_eq!(
+        validate::step_auction(&item, [1; 4], 1),
+        Err(Rule::OwnerOnly)
+    );
+    assert_eq!(
+        validate::step_auction(&item, owner, INITIAL_COST),
+        Err(Rule::CostRemainsPositive)
+    );
+    assert_eq!(
+        validate::create_auction(Some(&item), INITIAL_COST),
+        Err(Rule::ItemUnlisted)
+    );
+    assert_eq!(
+        validate::step_auction(&item, owner, Word::MIN),
+        Err(Rule::DecreasePositive)
+    );
+    let underwater = AuctionItem {
+        cost: Word::MIN,
+        ..item.clone()
+    };
+    assert_eq!(
+        validate::step_auction(&underwater, owner, 1),
+        Err(Rule::CostRemainsPositive)
+    );
+    assert_eq!(validate::mint(Some(1), 1), Err(Rule::MintOnce));
+
+    assert_eq!(
+        validate::submit_ask(90, 100, Some(80)),
+        Err(Rule::AskBeatsBest)
+    );
+    assert_eq!(validate::submit_ask(70, 100, Some(80)), Ok(()));
+    let sealed = token::commit_bid::commitment(INITIAL_COST, BID_SALT);
+    assert_eq!(
+        validate::commit_bid(None, INITIAL_COST, INITIAL_COST - 1, INITIAL_COST),
+        Err(Rule::DepositCoversCost)
+    );
+    assert_eq!(
+        validate::commit_bid(Some(sealed), INITIAL_COST, INITIAL_COST, INITIAL_COST),
+        Err(Rule::NotSealed)
+    );
+    assert_eq!(
+        validate::proxy_bid(
+            owner,
+            INITIAL_COST,
+            INITIAL_COST,
+            Some(owner),
+            Some(sealed),
+            sealed,
+            false
+        ),
+        Err(Rule::NotLeader)
+    );
+    assert_eq!(
+        validate::proxy_bid(
+            [1; 4],
+            INITIAL_COST + 1,
+            INITIAL_COST,
+            Some(owner),
+            Some(sealed),
+            token::commit_bid::commitment(INITIAL_COST + 1, BID_SALT),
+            false
+        ),
+        Err(Rule::CommitmentMatches)
+    );
+    assert_eq!(
+        validate::proxy_bid(
+            [1; 4],
+            INITIAL_COST,
+            INITIAL_COST,
+            None,
+            Some(sealed),
+            sealed,
+            true
+        ),
+        Err(Rule::NotRevealed)
+    );
+    assert_eq!(validate::cancel_ascending(Some(owner)), Err(Rule::NoLeader));
+    assert_eq!(
+        validate::resolve(token::ESCROW_DISPUTED, INITIAL_COST + 1, INITIAL_COST),
+        Err(Rule::RefundWithinEscrow)
+    );
+    assert_eq!(
+        validate::resolve(token::ESCROW_HELD, 0, INITIAL_COST),
+        Err(Rule::Disputed)
+    );
+    assert_eq!(
+        validate::rent(owner, owner, 1, false, None, 0),
+        Err(Rule::RenterNotOwner)
+    );
+    assert_eq!(
+        validate::rent(owner, [0; 4], 1, true, None, 0),
+        Err(Rule::NotForSale)
+    );
+    assert_eq!(
+        validate::fill_bid_intent(false, INITIAL_COST, INITIAL_COST, 10, 11),
+        Err(Rule::IntentUnexpired)
+    );
+    assert_eq!(
+        token::AuctionError::from(Rule::AmountCoversCost).to_string(),
+        "Violates constraint: amount >= current_cost"
+    );
+}
+
+#[tokio::test]
+async fn dry_run_reports_failing_predicates_without_submitting() {
+    use token::dry_run::{Contracts, NodeDb};
+
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let contracts = Contracts::generated(&token::auction::ADDRESS).unwrap();
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+
+    let mint = || {
+        token::mint::build_solution(token::mint::BuildSolution {
+            current_balance: Query(None),
+            hashed_key: alice_hashed_key,
+            amount: INITIAL_COST,
+        })
+        .unwrap()
+    };
+
+    // The first mint passes and nothing is written by the dry run.
+    let report = token::dry_run::check(NodeDb(dbs.node.clone()), &contracts, &mint())
+        .await
+        .unwrap();
+    assert!(report.passed(), "{:?}", report);
+    let balance = utils::node::query_state_head(
+        &dbs.node,
+        &token::token::ADDRESS,
+        &token::balance_key(alice_hashed_key),
+    )
+    .await
+    .unwrap();
+    assert_eq!(token::balance(Query(balance)).unwrap(), 0);
+
+    // Once Alice has minted, minting again breaks the mint predicate.
+    submit_and_build(&dbs, mint()).await;
+    let report = token::dry_run::check(NodeDb(dbs.node.clone()), &contracts, &mint())
+        .await
+        .unwrap();
+    assert!(!report.passed());
+    let checked = &report.predicates[0];
+    assert_eq!(checked.error, None);
+    assert!(checked.constraints.iter().any(Result::is_err));
+}
+
+#[test]
+fn addresses_are_read_without_compiling() {
+    // A project without build output is an error rather than a guess.
+    assert!(token::read_addresses(std::path::Path::new("not-built/auction")).is_err());
+    let addresses = token::generated_addresses("auction").unwrap();
+    assert_eq!(addresses.contract, token::auction::ADDRESS);
+    assert_eq!(
+        addresses.predicate("PlaceBid").unwrap(),
+        token::auction::PlaceBid::ADDRESS
+    );
+
+    let pint = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../pint");
+    let built = token::read_addresses(&pint.join("token")).unwrap();
+    assert_eq!(built.contract, token::token::ADDRESS);
+
+    // The output named after the package is read, whatever else was built next to it.
+    let project = std::env::temp_dir().join("addresses-are-read-by-package-name");
+    let out = project.join("out").join("debug");
+    std::fs::create_dir_all(&out).unwrap();
+    std::fs::copy(pint.join("token/pint.toml"), project.join("pint.toml")).unwrap();
+    for (from, to) in [
+        ("block_state/out/debug/block_state.json", "a.json"),
+        ("block_state/out/debug/block_state-abi.json", "a-abi.json"),
+        ("token/out/debug/token.json", "token.json"),
+        ("token/out/debug/token-abi.json", "token-abi.json"),
+    ] {
+        std::fs::copy(pint.join(from), out.join(to)).unwrap();
+    }
+    let read = token::read_addresses(&project).unwrap();
+    assert_eq!(read.contract, token::token::ADDRESS);
+
+    let moved = built.at(token::auction::ADDRESS);
+    assert_eq!(
+        moved.predicate("Mint").unwrap().contract,
+        token::auction::ADDRESS
+    );
+}
+
+#[test]
+fn queries_decode_with_precise_errors() {
+    use token::AuctionError;
+
+    assert_eq!(Query(Some(vec![7])).as_int(), Ok(7));
+    assert_eq!(Query(None).as_opt_int(), Ok(None));
+    assert_eq!(Query(Some(vec![])).as_opt_bool(), Ok(None));
+    assert_eq!(Query(Some(vec![1])).as_bool(), Ok(true));
+    assert_eq!(Query(Some(vec![1, 2, 3, 4])).as_b256(), Ok([1, 2, 3, 4]));
+
+    // Unexpected state is an error rather than a panic or a silent default.
+    assert!(matches!(
+        Query(None).as_int(),
+        Err(AuctionError::MalformedState(_))
+    ));
+    assert!(matches!(
+        Query(Some(vec![2])).as_bool(),
+        Err(AuctionError::MalformedState(_))
+    ));
+    assert!(matches!(
+        Query(Some(vec![1, 2])).as_opt_b256(),
+        Err(AuctionError::MalformedState(_))
+    ));
+    assert!(matches!(
+        token::item_owner(Query(None)),
+        Err(AuctionError::MalformedState(_))
+    ));
+}
+
+#[tokio::test]
+async fn actions_run_through_one_pipeline() {
+    use token::{auction, mint::Mint, transfer::Transfer};
+
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+
+    run_action(
+        &dbs,
+        &Mint {
+            hashed_key: alice_hashed_key,
+            amount: INITIAL_COST,
+        },
+    )
+    .await;
+    run_action(
+        &dbs,
+        &Transfer {
+            hashed_from_key: alice_hashed_key,
+            hashed_to_key: bob_hashed_key,
+            amount: DECREASE_AMT,
+        },
+    )
+    .await;
+    run_action(
+        &dbs,
+        &token::create_auction::CreateAuction {
+            item_id: ITEM_ID,
+            hashed_key: bob_hashed_key,
+            initial_cost: INITIAL_COST,
+            payment_token: token::token::ADDRESS,
+            contract: token::auction::ADDRESS,
+        },
+    )
+    .await;
+    run_action(
+        &dbs,
+        &token::step_auction::StepAuction {
+            item_id: ITEM_ID,
+            hashed_key: bob_hashed_key,
+            decrease: DECREASE_AMT,
+            contract: token::auction::ADDRESS,
+        },
+    )
+    .await;
+    run_action(
+        &dbs,
+        &token::place_bid::PlaceBid {
+            item_id: ITEM_ID,
+            hashed_from_key: alice_hashed_key,
+            hashed_to_key: bob_hashed_key,
+            amount: INITIAL_COST - DECREASE_AMT,
+            payment_token: token::token::ADDRESS,
+            collection: None,
+            contract: token::auction::ADDRESS,
+        },
+    )
+    .await;
+
+    let owner = utils::node::query_state_head(
+        &dbs.node,
+        &auction::ADDRESS,
+        &token::get_item_owner_key(ITEM_ID),
+    )
+    .await
+    .unwrap();
+    assert_eq!(token::item_owner(Query(owner)).unwrap(), alice_hashed_key);
+    let balance = utils::node::query_state_head(
+        &dbs.node,
+        &token::token::ADDRESS,
+        &token::balance_key(bob_hashed_key),
+    )
+    .await
+    .unwrap();
+    assert_eq!(token::balance(Query(balance)).unwrap(), INITIAL_COST);
+}
+
+#[tokio::test]
+async fn the_client_reads_and_submits_through_one_contract() {
+    use token::{dry_run::NodeDb, AuctionClient};
+
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (_, dbs) = setup().await;
+    let client = |name: &str, dry_run: bool| {
+        let mut wallet = essential_wallet::Wallet::temp().unwrap();
+        new_account(&mut wallet, name);
+        AuctionClient::with_services(
+            NodeDb(dbs.node.clone()),
+            LocalBuilder(&dbs),
+            token::auction::ADDRESS,
+            wallet,
+            name,
+        )
+        .unwrap()
+        .with_dry_run(dry_run)
+    };
+    let alice = client("alice", false);
+    let bob = client("bob", false);
+
+    alice
+        .create_auction(ITEM_ID, INITIAL_COST, token::token::ADDRESS)
+        .await
+        .unwrap();
+    build_block(&dbs).await;
+    alice.step_auction(ITEM_ID, DECREASE_AMT).await.unwrap();
+    build_block(&dbs).await;
+    bob.mint(FUNDS).await.unwrap();
+    build_block(&dbs).await;
+    bob.place_bid(ITEM_ID, INITIAL_COST - DECREASE_AMT)
+        .await
+        .unwrap();
+    build_block(&dbs).await;
+    bob.transfer(alice.hashed_key(), DECREASE_AMT)
+        .await
+        .unwrap();
+    build_block(&dbs).await;
+
+    // The client reads back what it wrote from the same contract.
+    let item = alice.item(ITEM_ID).await.unwrap().unwrap();
+    assert_eq!(item.owner, bob.hashed_key());
+    assert_eq!(alice.list_items().await.unwrap().len(), 1);
+    let paid = INITIAL_COST - DECREASE_AMT;
+    let token = token::token::ADDRESS;
+    assert_eq!(
+        bob.balance(&token, bob.hashed_key()).await.unwrap(),
+        FUNDS - paid - DECREASE_AMT
+    );
+    assert_eq!(
+        bob.balance(&token, alice.hashed_key()).await.unwrap(),
+        paid + DECREASE_AMT
+    );
+
+    // Checks that fail are caught before anything is built.
+    assert!(alice.step_auction(ITEM_ID, DECREASE_AMT).await.is_err());
+    assert!(bob.mint(FUNDS).await.is_err());
+
+    // A dry run that passes sends nothing.
+    let carol = client("carol", true);
+    carol.mint(FUNDS).await.unwrap();
+    build_block(&dbs).await;
+    assert_eq!(balance_of(&dbs, carol.hashed_key()).await, 0);
+
+    // A dry run that fails is an error naming the failing constraints.
+    let mint_again = token::mint::build_solution(token::mint::BuildSolution {
+        current_balance: Query(None),
+        hashed_key: bob.hashed_key(),
+        amount: FUNDS,
+    })
+    .unwrap();
+    let err = carol.submit(&mint_again).await.unwrap_err().to_string();
+    assert!(err.starts_with("Dry run failed"), "{}", err);
+    assert!(err.contains("fails"), "{}", err);
+}
+
+#[tokio::test]
+async fn batches_build_actions_into_one_solution() {
+    use token::{
+        auction, create_auction::CreateAuction, mint::Mint, transfer::Transfer, SolutionBatch,
+        SolutionBuilder,
+    };
+
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+
+    let mut batch = SolutionBatch::new();
+    batch
+        .push(Mint {
+            hashed_key: alice_hashed_key,
+            amount: INITIAL_COST,
+        })
+        .push(CreateAuction {
+            item_id: ITEM_ID,
+            hashed_key: bob_hashed_key,
+            initial_cost: INITIAL_COST,
+            payment_token: token::token::ADDRESS,
+            contract: auction::ADDRESS,
+        });
+    run_action(&dbs, &batch).await;
+
+    let owner = utils::node::query_state_head(
+        &dbs.node,
+        &auction::ADDRESS,
+        &token::get_item_owner_key(ITEM_ID),
+    )
+    .await
+    .unwrap();
+    assert_eq!(token::item_owner(Query(owner)).unwrap(), bob_hashed_key);
+    let balance = utils::node::query_state_head(
+        &dbs.node,
+        &token::token::ADDRESS,
+        &token::balance_key(alice_hashed_key),
+    )
+    .await
+    .unwrap();
+    assert_eq!(token::balance(Query(balance)).unwrap(), INITIAL_COST);
+
+    // Actions that write the same balance cannot both hold, so passing on
+    // what Alice sends Bob in the same batch is rejected before it is sent.
+    let charlie_hashed_key = new_account(&mut wallet, "charlie");
+    let mut batch = SolutionBatch::new();
+    batch
+        .push(Transfer {
+            hashed_from_key: alice_hashed_key,
+            hashed_to_key: bob_hashed_key,
+            amount: DECREASE_AMT,
+        })
+        .push(Transfer {
+            hashed_from_key: bob_hashed_key,
+            hashed_to_key: charlie_hashed_key,
+            amount: DECREASE_AMT,
+        });
+    let state = read_state(&dbs, &batch).await;
+    assert!(matches!(
+        batch.build(&state),
+        Err(token::AuctionError::Rejected(_))
+    ));
+
+    // Actions on separate balances are included together.
+    let mut batch = SolutionBatch::new();
+    batch
+        .push(Transfer {
+            hashed_from_key: alice_hashed_key,
+            hashed_to_key: charlie_hashed_key,
+            amount: DECREASE_AMT,
+        })
+        .push(Mint {
+            hashed_key: bob_hashed_key,
+            amount: INITIAL_COST,
+        });
+    run_action(&dbs, &batch).await;
+    assert_eq!(
+        balance_of(&dbs, alice_hashed_key).await,
+        INITIAL_COST - DECREASE_AMT
+    );
+    assert_eq!(balance_of(&dbs, bob_hashed_key).await, INITIAL_COST);
+    assert_eq!(balance_of(&dbs, charlie_hashed_key).await, DECREASE_AMT);
+
+    // Each action is still checked against the state it is built from.
+    let mut batch = SolutionBatch::new();
+    batch.push(Mint {
+        hashed_key: alice_hashed_key,
+        amount: INITIAL_COST,
+    });
+    let state = read_state(&dbs, &batch).await;
+    assert_eq!(
+        batch.build(&state).unwrap_err(),
+        token::AuctionError::Violation(token::validate::Rule::MintOnce)
+    );
+}
+
+#[test]
+fn envelopes_round_trip_and_sign_offline() {
+    use token::envelope::{blank_signature, Envelope, SignatureSlot};
+    use token::make_offer::{build_solution, data_to_sign, BuildSolution};
+
+    let mut wallet = Wallet::temp().unwrap();
+    let buyer = new_account(&mut wallet, "alice");
+    let offer = |signature| BuildSolution {
+        item_id: ITEM_ID,
+        hashed_key: buyer,
+        amount: DECREASE_AMT,
+        payment_token: token::token::ADDRESS,
+        current_offer: Query(None),
+        current_offer_count: Query(None),
+        current_balance: Query(Some(vec![INITIAL_COST])),
+        current_escrow_balance: Query(None),
+        current_nonce: Query(None),
+        signature,
+        contract: token::auction::ADDRESS,
+    };
+    let to_sign = data_to_sign(ITEM_ID, DECREASE_AMT, &token::token::ADDRESS, 0);
+    let solution = build_solution(offer(blank_signature())).unwrap();
+    let slot = SignatureSlot::find("make-offer", &solution).unwrap();
+    assert_eq!(slot, SignatureSlot { data: 0, var: 0 });
+    let envelope = Envelope {
+        action: "make-offer".to_string(),
+        item_id: Some(ITEM_ID),
+        height: Some(7),
+        signer: buyer,
+        to_sign: to_sign.clone(),
+        slot: Some(slot),
+        signature: None,
+        solution,
+    };
+
+    let json = serde_json::to_string(&envelope).unwrap();
+    let mut decoded: Envelope = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.action, "make-offer");
+    assert_eq!(decoded.item_id, Some(ITEM_ID));
+    assert_eq!(decoded.height, Some(7));
+    assert_eq!(decoded.signer, buyer);
+    assert_eq!(decoded.to_sign, to_sign);
+    assert_eq!(decoded.solution, envelope.solution);
+    assert!(decoded.clone().into_solution().is_err());
+
+    // The words to sign come from the solution, not from the envelope.
+    assert_eq!(decoded.data_to_sign().unwrap(), to_sign);
+    let mut tampered = decoded.clone();
+    tampered.to_sign[1] = 1;
+    assert_eq!(tampered.data_to_sign().unwrap(), to_sign);
+    assert!(decoded.describe().unwrap().contains("amount"));
+
+    let essential_signer::Signature::Secp256k1(signature) =
+        wallet.sign_words(&decoded.to_sign, "alice").unwrap()
+    else {
+        panic!("Invalid signature")
+    };
+    decoded.sign(signature).unwrap();
+    let json = serde_json::to_string(&decoded).unwrap();
+    let signed: Envelope = serde_json::from_str(&json).unwrap();
+    assert_eq!(signed.signature, Some(signature));
+    assert_eq!(
+        signed.into_solution().unwrap(),
+        build_solution(offer(signature)).unwrap()
+    );
+}
+
+#[test]
+fn accepted_offers_are_checked_against_the_payment() {
+    use token::accept_offer::{build_solution, data_to_sign, BuildSolution};
+    use token::envelope::{blank_signature, Envelope, SignatureSlot};
+
+    let mut wallet = Wallet::temp().unwrap();
+    let owner = new_account(&mut wallet, "alice");
+    let buyer = new_account(&mut wallet, "bob");
+    let solution = build_solution(BuildSolution {
+        item_id: ITEM_ID,
+        hashed_key: owner,
+        hashed_buyer_key: buyer,
+        payment_token: token::token::ADDRESS,
+        current_offer: Query(Some(vec![INITIAL_COST])),
+        current_balance: Query(None),
+        current_escrow_balance: Query(Some(vec![INITIAL_COST])),
+        current_nonce: Query(None),
+        signature: blank_signature(),
+        current_collection: Query(None),
+        current_royalty_rate: Query(None),
+        current_royalties: Query(None),
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap();
+    let to_sign = data_to_sign(ITEM_ID, buyer, INITIAL_COST, 0);
+    let envelope = Envelope {
+        action: "accept-offer".to_string(),
+        item_id: Some(ITEM_ID),
+        height: None,
+        signer: owner,
+        to_sign: to_sign.clone(),
+        slot: Some(SignatureSlot::find("accept-offer", &solution).unwrap()),
+        signature: None,
+        solution,
+    };
+    assert_eq!(envelope.data_to_sign().unwrap(), to_sign);
+    assert!(envelope.describe().unwrap().contains("not verified"));
+
+    // An offer below what the solution pays the owner is refused.
+    let mut tampered = envelope.clone();
+    tampered.to_sign[5] = DECREASE_AMT;
+    assert!(tampered.data_to_sign().is_err());
+}
+
+#[tokio::test]
+async fn treasury_transfers_are_signed_offline() {
+    use token::envelope::{blank_signature, Envelope, SignatureSlot};
+    use token::signed_transfer::{build_solution, data_to_sign, BuildSolution};
+
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let treasury = new_account(&mut wallet, "treasury");
+    let bob = new_account(&mut wallet, "bob");
+    mint(&dbs, treasury, FUNDS).await;
+
+    let transfer = |signature| BuildSolution {
+        hashed_from_key: treasury,
+        hashed_to_key: bob,
+        amount: INITIAL_COST,
+        current_from_balance: Query(Some(vec![FUNDS])),
+        current_to_balance: Query(None),
+        current_nonce: Query(None),
+        signature,
+    };
+    let to_sign = data_to_sign(bob, INITIAL_COST, 0);
+    let solution = build_solution(transfer(blank_signature())).unwrap();
+    let mut envelope = Envelope {
+        action: "transfer".to_string(),
+        item_id: None,
+        height: None,
+        signer: treasury,
+        to_sign: to_sign.clone(),
+        slot: Some(SignatureSlot::find("transfer", &solution).unwrap()),
+        signature: None,
+        solution: solution.clone(),
+    };
+    assert_eq!(envelope.data_to_sign().unwrap(), to_sign);
+
+    // Without the treasury's signature the transfer is rejected.
+    submit_and_fail(&dbs, solution).await;
+    let sign = |wallet: &mut Wallet, account: &str| {
+        let essential_signer::Signature::Secp256k1(signature) =
+            wallet.sign_words(&to_sign, account).unwrap()
+        else {
+            panic!("Invalid signature")
+        };
+        signature
+    };
+    let forged = build_solution(transfer(sign(&mut wallet, "bob"))).unwrap();
+    submit_and_fail(&dbs, forged).await;
+
+    envelope.sign(sign(&mut wallet, "treasury")).unwrap();
+    let json = serde_json::to_string(&envelope).unwrap();
+    let signed: Envelope = serde_json::from_str(&json).unwrap();
+    let solution = signed.into_solution().unwrap();
+    submit_and_build(&dbs, solution.clone()).await;
+    assert_eq!(balance_of(&dbs, treasury).await, FUNDS - INITIAL_COST);
+    assert_eq!(balance_of(&dbs, bob).await, INITIAL_COST);
+
+    // The nonce moved on, so the signed transfer cannot be replayed.
+    submit_and_fail(&dbs, solution).await;
+    let nonce = query(
+        &dbs,
+        &token::token::ADDRESS,
+        &token::transfer_nonce_key(treasury),
+    )
+    .await;
+    assert_eq!(nonce.as_int().unwrap(), 1);
+}
+
+#[tokio::test]
+async fn offers_are_escrowed_until_accepted() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+    let carol_hashed_key = new_account(&mut wallet, "carol");
+    let token = token::token::ADDRESS;
+    let escrow_balance_key = token::balance_key(token::escrow_key(&token::auction::ADDRESS));
+
+    // Bob sells the item to Alice and offers to buy it back.
+    sell_item(&dbs, bob_hashed_key, alice_hashed_key).await;
+    let solution = make_offer_solution(&dbs, bob_hashed_key, DECREASE_AMT, |to_sign| {
+        sign(&mut wallet, to_sign, "bob")
+    })
+    .await
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    let escrow_balance = query(&dbs, &token, &escrow_balance_key).await;
+    assert_eq!(token::balance(escrow_balance).unwrap(), DECREASE_AMT);
+    let offer = query(
+        &dbs,
+        &token::auction::ADDRESS,
+        &token::offer_key(ITEM_ID, bob_hashed_key),
+    )
+    .await;
+    assert_eq!(token::offer(offer).unwrap(), DECREASE_AMT);
+
+    // The escrow is held by the auction, so a plain transfer can't take it.
+    let solution = token::transfer::build_solution(token::transfer::BuildSolution {
+        hashed_from_key: token::escrow_key(&token::auction::ADDRESS),
+        hashed_to_key: carol_hashed_key,
+        amount: DECREASE_AMT,
+        current_from_balance: query(&dbs, &token, &escrow_balance_key).await,
+        current_to_balance: Query(None),
+        authority: 0,
+    })
+    .unwrap();
+    submit_and_fail(&dbs, solution).await;
+
+    // Nor can a release that follows anything but a settlement.
+    let mut solution =
+        token::create_auction::build_solution(token::create_auction::BuildSolution {
+            item_id: ITEM_ID + 1,
+            hashed_key: carol_hashed_key,
+            amount: INITIAL_COST,
+            payment_token: token,
+            collection: None,
+            current_curator_admin: Query(None),
+            current_curated_item_count: Query(None),
+            block_state: token::block_state::ADDRESS,
+            contract: token::auction::ADDRESS,
+        })
+        .unwrap();
+    let release = token::release::build_solution_data(
+        token::release::BuildSolution {
+            settlement: token::auction::CreateAuction::ADDRESS,
+            payment_token: token,
+            hashed_to_key: carol_hashed_key,
+            amount: DECREASE_AMT,
+            current_escrow_balance: query(&dbs, &token, &escrow_balance_key).await,
+            current_to_balance: Query(None),
+            contract: token::auction::ADDRESS,
+        },
+        solution.data.len() as Word,
+    )
+    .unwrap();
+    solution.data.extend(release);
+    submit_and_fail(&dbs, solution).await;
+    let escrow_balance = query(&dbs, &token, &escrow_balance_key).await;
+    assert_eq!(token::balance(escrow_balance).unwrap(), DECREASE_AMT);
+
+    // Alice accepts and is paid from escrow.
+    let solution = accept_offer_solution(&dbs, alice_hashed_key, bob_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "alice")
+    })
+    .await
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    let owner = query(
+        &dbs,
+        &token::auction::ADDRESS,
+        &token::get_item_owner_key(ITEM_ID),
+    )
+    .await;
+    assert_eq!(token::item_owner(owner).unwrap(), bob_hashed_key);
+    let balance = query(&dbs, &token, &token::balance_key(alice_hashed_key)).await;
+    assert_eq!(
+        token::balance(balance).unwrap(),
+        FUNDS - INITIAL_COST + DECREASE_AMT
+    );
+    let escrow_balance = query(&dbs, &token, &escrow_balance_key).await;
+    assert_eq!(token::balance(escrow_balance).unwrap(), 0);
+}
+
+#[tokio::test]
+async fn accepted_offers_on_collection_items_pay_the_royalty() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+    let charlie_hashed_key = new_account(&mut wallet, "charlie");
+    let auction = token::auction::ADDRESS;
+    let payment_token = token::token::ADDRESS;
+    let collection = 0;
+    let royalty = INITIAL_COST / 10;
+
+    // Alice puts the item in a closed collection with a 10% royalty and
+    // Bob wins it.
+    create_auction(&dbs, ITEM_ID, alice_hashed_key).await;
+    let solution =
+        token::create_collection::build_solution(token::create_collection::BuildSolution {
+            collection,
+            hashed_key: alice_hashed_key,
+            royalty: 1000,
+            open: false,
+            current_creator: Query(None),
+            contract: token::auction::ADDRESS,
+        })
+        .unwrap();
+    submit_and_build(&dbs, solution).await;
+    let solution =
+        add_collection_item_solution(&dbs, collection, ITEM_ID, alice_hashed_key, |to_sign| {
+            sign(&mut wallet, to_sign, "alice")
+        })
+        .await;
+    submit_and_build(&dbs, solution).await;
+    mint(&dbs, bob_hashed_key, FUNDS).await;
+    let solution = place_bid_solution(&dbs, bob_hashed_key, INITIAL_COST)
+        .await
+        .unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    // Charlie offers on the item.
+    mint(&dbs, charlie_hashed_key, FUNDS).await;
+    let solution = make_offer_solution(&dbs, charlie_hashed_key, INITIAL_COST, |to_sign| {
+        sign(&mut wallet, to_sign, "charlie")
+    })
+    .await
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    // Bob isn't an allowed seller of the collection.
+    let solution = accept_offer_solution(&dbs, bob_hashed_key, charlie_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "bob")
+    })
+    .await
+    .unwrap();
+    submit_and_fail(&dbs, solution).await;
+
+    let solution = allow_seller_solution(
+        &dbs,
+        collection,
+        alice_hashed_key,
+        bob_hashed_key,
+        |to_sign| sign(&mut wallet, to_sign, "alice"),
+    )
+    .await;
+    submit_and_build(&dbs, solution).await;
+
+    // Releasing the whole offer to Bob would skip the royalty.
+    let to_sign = token::accept_offer::data_to_sign(ITEM_ID, charlie_hashed_key, INITIAL_COST, 0);
+    let solution = token::accept_offer::build_solution(token::accept_offer::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_key: bob_hashed_key,
+        hashed_buyer_key: charlie_hashed_key,
+        current_offer: query(
+            &dbs,
+            &auction,
+            &token::offer_key(ITEM_ID, charlie_hashed_key),
+        )
+        .await,
+        current_balance: query(&dbs, &payment_token, &token::balance_key(bob_hashed_key)).await,
+        current_escrow_balance: query(
+            &dbs,
+            &payment_token,
+            &token::balance_key(token::escrow_key(&token::auction::ADDRESS)),
+        )
+        .await,
+        current_nonce: Query(None),
+        signature: sign(&mut wallet, &to_sign, "bob"),
+        current_collection: Query(None),
+        current_royalty_rate: Query(None),
+        current_royalties: Query(None),
+        payment_token: payment_token.clone(),
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap();
+    submit_and_fail(&dbs, solution).await;
+
+    // Bob is paid the offer less the royalty, which is left for Alice
+    // alongside the royalty on Bob's winning bid.
+    let solution = accept_offer_solution(&dbs, bob_hashed_key, charlie_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "bob")
+    })
+    .await
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+    let owner = query(&dbs, &auction, &token::get_item_owner_key(ITEM_ID)).await;
+    assert_eq!(token::item_owner(owner).unwrap(), charlie_hashed_key);
+    assert_eq!(balance_of(&dbs, bob_hashed_key).await, FUNDS - royalty);
+    let royalties = query(
+        &dbs,
+        &auction,
+        &token::collection_royalties_key(collection, &payment_token),
+    )
+    .await;
+    assert_eq!(token::cost(royalties).unwrap(), 2 * royalty);
+}
+
+#[tokio::test]
+async fn collection_changes_are_signed_and_auctions_and_raffles_pay_the_royalty() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+    let auction = token::auction::ADDRESS;
+    let payment_token = token::token::ADDRESS;
+    let collection = 0;
+    let auctioned = ITEM_ID;
+    let raffled = ITEM_ID + 1;
+    let royalties_key = token::collection_royalties_key(collection, &payment_token);
+    mint(&dbs, bob_hashed_key, FUNDS).await;
+    submit_and_build(&dbs, advance(1)).await;
+
+    // Alice auctions one item and raffles another, then puts both in a
+    // closed collection with a 10% royalty.
+    create_auction(&dbs, auctioned, alice_hashed_key).await;
+    let solution = token::create_raffle::build_solution(token::create_raffle::BuildSolution {
+        item_id: raffled,
+        hashed_key: alice_hashed_key,
+        ticket_price: TICKET_PRICE,
+        entries_close: 3,
+        reveals_close: 5,
+        secret: 7,
+        salt: RAFFLE_SALT,
+        payment_token: payment_token.clone(),
+        block_state: token::block_state::ADDRESS,
+        collection: None,
+        current_curator_admin: Query(None),
+        current_curated_item_count: Query(None),
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+    let solution =
+        token::create_collection::build_solution(token::create_collection::BuildSolution {
+            collection,
+            hashed_key: alice_hashed_key,
+            royalty: 1000,
+            open: false,
+            current_creator: Query(None),
+            contract: token::auction::ADDRESS,
+        })
+        .unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    // Only Alice's signature adds her items, and only once.
+    let solution =
+        add_collection_item_solution(&dbs, collection, auctioned, alice_hashed_key, |to_sign| {
+            sign(&mut wallet, to_sign, "bob")
+        })
+        .await;
+    submit_and_fail(&dbs, solution).await;
+    let solution =
+        add_collection_item_solution(&dbs, collection, auctioned, alice_hashed_key, |to_sign| {
+            sign(&mut wallet, to_sign, "alice")
+        })
+        .await;
+    submit_and_build(&dbs, solution.clone()).await;
+    submit_and_fail(&dbs, solution).await;
+    let solution =
+        add_collection_item_solution(&dbs, collection, raffled, alice_hashed_key, |to_sign| {
+            sign(&mut wallet, to_sign, "alice")
+        })
+        .await;
+    submit_and_build(&dbs, solution).await;
+
+    // Only Alice's signature allows sellers.
+    let solution = allow_seller_solution(
+        &dbs,
+        collection,
+        alice_hashed_key,
+        bob_hashed_key,
+        |to_sign| sign(&mut wallet, to_sign, "bob"),
+    )
+    .await;
+    submit_and_fail(&dbs, solution).await;
+    let allowed = query(
+        &dbs,
+        &auction,
+        &token::collection_seller_key(collection, bob_hashed_key),
+    )
+    .await;
+    assert_eq!(allowed.as_opt_bool().unwrap(), None);
+
+    // A bid that pays Alice in full would skip the royalty.
+    let mut build = place_bid_build(&dbs, auctioned, bob_hashed_key, INITIAL_COST)
+        .await
+        .unwrap();
+    build.current_collection = Query(None);
+    build.current_to_balance =
+        query(&dbs, &payment_token, &token::balance_key(alice_hashed_key)).await;
+    let solution = token::place_bid::build_solution(build).unwrap();
+    submit_and_fail(&dbs, solution).await;
+
+    // Bob's winning bid is escrowed and the royalty split off for Alice.
+    let solution = place_bid_solution_for(&dbs, auctioned, bob_hashed_key, INITIAL_COST)
+        .await
+        .unwrap();
+    submit_and_build(&dbs, solution).await;
+    let royalty = INITIAL_COST / 10;
+    let royalties = query(&dbs, &auction, &royalties_key).await;
+    assert_eq!(token::cost(royalties).unwrap(), royalty);
+    let proceeds = query(&dbs, &auction, &token::proceeds_key(auctioned)).await;
+    assert_eq!(token::cost(proceeds).unwrap(), INITIAL_COST - royalty);
+    assert_eq!(balance_of(&dbs, alice_hashed_key).await, 0);
+
+    // Bob wins the raffle and its ticket pays the royalty too.
+    let solution = enter_raffle_solution(&dbs, raffled, bob_hashed_key, 11).await;
+    submit_and_build(&dbs, solution).await;
+    submit_and_build(&dbs, advance(3)).await;
+    let solution = reveal_raffle_solution(&dbs, raffled, alice_hashed_key, 7).await;
+    submit_and_build(&dbs, solution).await;
+    submit_and_build(&dbs, advance(5)).await;
+    let solution = draw_raffle_solution(&dbs, raffled, 0).await;
+    let mut forged = solution.clone();
+    forged.data[0].state_mutations = token::auction::storage::mutations()
+        .item_owner(|map| map.entry(raffled, bob_hashed_key))
+        .raffle_winner(|map| map.entry(raffled, bob_hashed_key))
+        .proceeds(|map| map.entry(raffled, TICKET_PRICE))
+        .proceeds_seller(|map| map.entry(raffled, alice_hashed_key))
+        .auction_ended(|map| map.entry(raffled, true))
+        .into();
+    submit_and_fail(&dbs, forged).await;
+    submit_and_build(&dbs, solution).await;
+    let owner = query(&dbs, &auction, &token::get_item_owner_key(raffled)).await;
+    assert_eq!(token::item_owner(owner).unwrap(), bob_hashed_key);
+    let ticket_royalty = TICKET_PRICE / 10;
+    let royalties = query(&dbs, &auction, &royalties_key).await;
+    assert_eq!(token::cost(royalties).unwrap(), royalty + ticket_royalty);
+    let proceeds = query(&dbs, &auction, &token::proceeds_key(raffled)).await;
+    assert_eq!(
+        token::cost(proceeds).unwrap(),
+        TICKET_PRICE - ticket_royalty
+    );
+}
+
+#[tokio::test]
+async fn offer_signatures_cannot_be_replayed() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+    let bob_balance_key = token::balance_key(bob_hashed_key);
+
+    sell_item(&dbs, bob_hashed_key, alice_hashed_key).await;
+
+    // Bob makes an offer and withdraws it.
+    let mut signature = None;
+    let solution = make_offer_solution(&dbs, bob_hashed_key, DECREASE_AMT, |to_sign| {
+        let sig = sign(&mut wallet, to_sign, "bob");
+        signature = Some(sig);
+        sig
+    })
+    .await
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+    let solution = withdraw_offer_solution(&dbs, bob_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "bob")
+    })
+    .await
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    let nonce = query(
+        &dbs,
+        &token::auction::ADDRESS,
+        &token::offer_nonce_key(bob_hashed_key),
+    )
+    .await;
+    assert_eq!(token::offer(nonce).unwrap(), 2);
+    let balance = query(&dbs, &token::token::ADDRESS, &bob_balance_key).await;
+    assert_eq!(token::balance(balance).unwrap(), INITIAL_COST);
+
+    // The first offer's signature can't escrow Bob's tokens again.
+    let signature = signature.unwrap();
+    let solution = make_offer_solution(&dbs, bob_hashed_key, DECREASE_AMT, |_| signature)
+        .await
+        .unwrap();
+    submit_and_fail(&dbs, solution).await;
+    let balance = query(&dbs, &token::token::ADDRESS, &bob_balance_key).await;
+    assert_eq!(token::balance(balance).unwrap(), INITIAL_COST);
+}
+
+#[tokio::test]
+async fn fixed_price_items_are_listed_delisted_and_bought() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+    let auction = token::auction::ADDRESS;
+    mint(&dbs, alice_hashed_key, FUNDS).await;
+
+    // Only the owner's signature lists an item.
+    let solution = list_solution(&dbs, ITEM_ID, bob_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "alice")
+    })
+    .await;
+    submit_and_fail(&dbs, solution).await;
+
+    // Bob lists the item at a fixed price.
+    let solution = list_solution(&dbs, ITEM_ID, bob_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "bob")
+    })
+    .await;
+    submit_and_build(&dbs, solution).await;
+    let fixed_price = query(&dbs, &auction, &token::fixed_price_key(ITEM_ID)).await;
+    assert_eq!(fixed_price.as_opt_bool().unwrap(), Some(true));
+    let status = query(&dbs, &auction, &token::auction_status_key(ITEM_ID)).await;
+    assert_eq!(status.as_opt_bool().unwrap(), Some(false));
+
+    // Only the owner can take it off sale.
+    let solution = delist_solution(&dbs, ITEM_ID, alice_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "alice")
+    })
+    .await;
+    submit_and_fail(&dbs, solution).await;
+    let delisting = delist_solution(&dbs, ITEM_ID, bob_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "bob")
+    })
+    .await;
+    submit_and_build(&dbs, delisting.clone()).await;
+    let fixed_price = query(&dbs, &auction, &token::fixed_price_key(ITEM_ID)).await;
+    assert_eq!(fixed_price.as_opt_bool().unwrap(), Some(false));
+    let status = query(&dbs, &auction, &token::auction_status_key(ITEM_ID)).await;
+    assert_eq!(status.as_opt_bool().unwrap(), Some(true));
+
+    // A delisted item can't be bought.
+    submit_and_fail(&dbs, buy_solution(&dbs, alice_hashed_key).await.unwrap()).await;
+
+    // Bob lists it again, and his delisting can't be replayed to take it off sale.
+    let solution = list_solution(&dbs, ITEM_ID, bob_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "bob")
+    })
+    .await;
+    submit_and_build(&dbs, solution).await;
+    submit_and_fail(&dbs, delisting).await;
+
+    // Alice buys it at the listed price.
+    submit_and_build(&dbs, buy_solution(&dbs, alice_hashed_key).await.unwrap()).await;
+
+    let owner = query(&dbs, &auction, &token::get_item_owner_key(ITEM_ID)).await;
+    assert_eq!(token::item_owner(owner).unwrap(), alice_hashed_key);
+    let balance = query(
+        &dbs,
+        &token::token::ADDRESS,
+        &token::balance_key(alice_hashed_key),
+    )
+    .await;
+    assert_eq!(token::balance(balance).unwrap(), FUNDS - INITIAL_COST);
+    let balance = query(
+        &dbs,
+        &token::token::ADDRESS,
+        &token::balance_key(bob_hashed_key),
+    )
+    .await;
+    assert_eq!(token::balance(balance).unwrap(), INITIAL_COST);
+
+    // The sale ends the listing, so Bob can no longer delist it.
+    let solution = delist_solution(&dbs, ITEM_ID, bob_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "bob")
+    })
+    .await;
+    submit_and_fail(&dbs, solution).await;
+}
+
+#[tokio::test]
+async fn deadlines_are_measured_against_the_block_state() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    mint(&dbs, alice_hashed_key, FUNDS).await;
+
+    // Only the keeper advances the block number.
+    let signature = sign(&mut wallet, &[10], "alice");
+    submit_and_fail(&dbs, advance_signed(10, signature)).await;
+
+    // The block number only moves forward.
+    submit_and_build(&dbs, advance(10)).await;
+    submit_and_fail(&dbs, advance(9)).await;
+    submit_and_fail(&dbs, advance(10)).await;
+    let number = query(
+        &dbs,
+        &token::block_state::ADDRESS,
+        &token::block_number_key(),
+    )
+    .await;
+    assert_eq!(number.as_opt_int().unwrap(), Some(10));
+
+    // The failed attempts leave the balances as they are.
+    let balance_key = token::balance_key(alice_hashed_key);
+    let balance = query(&dbs, &token::token::ADDRESS, &balance_key).await;
+    let escrow_key = token::balance_key(token::escrow_key(&token::auction::ADDRESS));
+    let escrow_balance = query(&dbs, &token::token::ADDRESS, &escrow_key).await;
+    let create_request = |deadline, block_state| {
+        token::create_request::build_solution(token::create_request::BuildSolution {
+            item_id: ITEM_ID,
+            hashed_key: alice_hashed_key,
+            budget: INITIAL_COST,
+            payment_token: token::token::ADDRESS,
+            deadline,
+            block_state,
+            current_balance: Query(balance.0.clone()),
+            current_escrow_balance: Query(escrow_balance.0.clone()),
+            contract: token::auction::ADDRESS,
+        })
+        .unwrap()
+    };
+
+    // A request can't close at or before the current block.
+    submit_and_fail(&dbs, create_request(10, token::block_state::ADDRESS)).await;
+
+    // Nor can it be measured against any other block state contract.
+    submit_and_fail(&dbs, create_request(11, token::token::ADDRESS)).await;
+
+    submit_and_build(&dbs, create_request(11, token::block_state::ADDRESS)).await;
+    let deadline = query(
+        &dbs,
+        &token::auction::ADDRESS,
+        &token::request_deadline_key(ITEM_ID),
+    )
+    .await;
+    assert_eq!(deadline.as_opt_int().unwrap(), Some(11));
+}
+
+#[tokio::test]
+async fn bid_intents_are_filled_by_a_solver_until_they_expire() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+    mint(&dbs, bob_hashed_key, FUNDS).await;
+    create_auction(&dbs, ITEM_ID, alice_hashed_key).await;
+    create_auction(&dbs, 1, alice_hashed_key).await;
+    submit_and_build(&dbs, advance(10)).await;
+
+    // Bob's intent is filled at the current price within his limit.
+    let intent = bid_intent(&mut wallet, ITEM_ID, "bob", 10);
+    let block_number = query(
+        &dbs,
+        &token::block_state::ADDRESS,
+        &token::block_number_key(),
+    )
+    .await;
+    let solution = fill_bid_intent_solution(&dbs, intent, block_number)
+        .await
+        .unwrap();
+    submit_and_build(&dbs, solution).await;
+    let owner = query(
+        &dbs,
+        &token::auction::ADDRESS,
+        &token::get_item_owner_key(ITEM_ID),
+    )
+    .await;
+    assert_eq!(token::item_owner(owner).unwrap(), bob_hashed_key);
+    assert_eq!(balance_of(&dbs, bob_hashed_key).await, FUNDS - INITIAL_COST);
+    assert_eq!(balance_of(&dbs, alice_hashed_key).await, INITIAL_COST);
+
+    // Once the block passes its expiry the intent can't be filled.
+    let intent = bid_intent(&mut wallet, 1, "bob", 10);
+    submit_and_build(&dbs, advance(11)).await;
+    let block_number = query(
+        &dbs,
+        &token::block_state::ADDRESS,
+        &token::block_number_key(),
+    )
+    .await;
+    let result = fill_bid_intent_solution(&dbs, intent.clone(), block_number).await;
+    assert_eq!(
+        result.unwrap_err(),
+        token::AuctionError::Violation(token::validate::Rule::IntentUnexpired)
+    );
+
+    // Nor can a solver fill it by claiming an earlier block.
+    let stale = Query(Some(vec![10]));
+    let solution = fill_bid_intent_solution(&dbs, intent, stale).await.unwrap();
+    submit_and_fail(&dbs, solution).await;
+    let owner = query(
+        &dbs,
+        &token::auction::ADDRESS,
+        &token::get_item_owner_key(1),
+    )
+    .await;
+    assert_eq!(token::item_owner(owner).unwrap(), alice_hashed_key);
+    assert_eq!(balance_of(&dbs, bob_hashed_key).await, FUNDS - INITIAL_COST);
+}
+
+#[tokio::test]
+async fn requests_pay_the_lowest_ask_and_refund_the_rest() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+    let carol_hashed_key = new_account(&mut wallet, "carol");
+    let token = token::token::ADDRESS;
+    let escrow_balance_key = token::balance_key(token::escrow_key(&token::auction::ADDRESS));
+    mint(&dbs, alice_hashed_key, FUNDS).await;
+    submit_and_build(&dbs, advance(1)).await;
+
+    // Alice escrows her budget for a request that closes at block 5.
+    let solution = token::create_request::build_solution(token::create_request::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_key: alice_hashed_key,
+        budget: INITIAL_COST,
+        payment_token: token,
+        deadline: 5,
+        block_state: token::block_state::ADDRESS,
+        current_balance: query(&dbs, &token, &token::balance_key(alice_hashed_key)).await,
+        current_escrow_balance: query(&dbs, &token, &escrow_balance_key).await,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+    assert_eq!(
+        balance_of(&dbs, alice_hashed_key).await,
+        FUNDS - INITIAL_COST
+    );
+
+    // Each ask has to beat the lowest so far.
+    let bob_ask = INITIAL_COST - DECREASE_AMT;
+    let carol_ask = bob_ask - DECREASE_AMT;
+    submit_and_build(
+        &dbs,
+        submit_ask_solution(&dbs, bob_hashed_key, bob_ask).await,
+    )
+    .await;
+    submit_and_build(
+        &dbs,
+        submit_ask_solution(&dbs, carol_hashed_key, carol_ask).await,
+    )
+    .await;
+    let solution = submit_ask_solution(&dbs, bob_hashed_key, carol_ask + INCREMENT).await;
+    submit_and_fail(&dbs, solution).await;
+
+    // Nothing is paid out before the deadline.
+    submit_and_fail(&dbs, settle_request_solution(&dbs, carol_hashed_key).await).await;
+    submit_and_fail(&dbs, refund_request_solution(&dbs, alice_hashed_key).await).await;
+
+    // Asks close at the deadline, and the buyer is only refunded once the
+    // winner is paid.
+    submit_and_build(&dbs, advance(5)).await;
+    let solution = submit_ask_solution(&dbs, bob_hashed_key, carol_ask - INCREMENT).await;
+    submit_and_fail(&dbs, solution).await;
+    submit_and_fail(&dbs, refund_request_solution(&dbs, alice_hashed_key).await).await;
+
+    // Carol is paid her ask out of escrow, and only once.
+    submit_and_build(&dbs, settle_request_solution(&dbs, carol_hashed_key).await).await;
+    assert_eq!(balance_of(&dbs, carol_hashed_key).await, carol_ask);
+    submit_and_fail(&dbs, settle_request_solution(&dbs, carol_hashed_key).await).await;
+
+    // Alice gets back what was left of her budget, and only once.
+    submit_and_build(&dbs, refund_request_solution(&dbs, alice_hashed_key).await).await;
+    assert_eq!(balance_of(&dbs, alice_hashed_key).await, FUNDS - carol_ask);
+    submit_and_fail(&dbs, refund_request_solution(&dbs, alice_hashed_key).await).await;
+    let escrow_balance = query(&dbs, &token, &escrow_balance_key).await;
+    assert_eq!(token::balance(escrow_balance).unwrap(), 0);
+}
+
+#[tokio::test]
+async fn bundle_bids_are_won_together() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+    let charlie_hashed_key = new_account(&mut wallet, "charlie");
+    let auction = token::auction::ADDRESS;
+    let first = ITEM_ID;
+    let second = ITEM_ID + 1;
+    let third = ITEM_ID + 2;
+    mint(&dbs, alice_hashed_key, FUNDS).await;
+    for item_id in [first, second, third] {
+        create_auction(&dbs, item_id, bob_hashed_key).await;
+    }
+
+    // The builder checks the total against the balance up front.
+    let err = bundle_solution(&dbs, alice_hashed_key, &[(first, FUNDS), (second, 1)])
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        token::AuctionError::InsufficientBalance { .. }
+    ));
+    let err = bundle_solution(&dbs, alice_hashed_key, &[(first, Word::MAX), (second, 1)])
+        .await
+        .unwrap_err();
+    assert_eq!(err, token::AuctionError::Overflow);
+
+    // A bundle that escrows less than its total is rejected.
+    let mut solution = bundle_solution(
+        &dbs,
+        alice_hashed_key,
+        &[(first, INITIAL_COST), (second, INITIAL_COST)],
+    )
+    .await
+    .unwrap();
+    let balance_key = token::balance_key(alice_hashed_key);
+    let escrow_key = token::balance_key(token::escrow_key(&token::auction::ADDRESS));
+    let transfer = token::transfer::build_solution_data(
+        token::token::ADDRESS,
+        token::transfer::BuildSolution {
+            hashed_from_key: alice_hashed_key,
+            hashed_to_key: token::escrow_key(&token::auction::ADDRESS),
+            amount: INITIAL_COST,
+            current_from_balance: query(&dbs, &token::token::ADDRESS, &balance_key).await,
+            current_to_balance: query(&dbs, &token::token::ADDRESS, &escrow_key).await,
+            authority: 0,
+        },
+    )
+    .unwrap();
+    *solution.data.last_mut().unwrap() = transfer;
+    submit_and_fail(&dbs, solution).await;
+
+    // Charlie wins the second item before Alice's bundle lands, so none of
+    // the bundle goes through.
+    let solution = bundle_solution(
+        &dbs,
+        alice_hashed_key,
+        &[(first, INITIAL_COST), (second, INITIAL_COST)],
+    )
+    .await
+    .unwrap();
+    mint(&dbs, charlie_hashed_key, FUNDS).await;
+    let bid = place_bid_solution_for(&dbs, second, charlie_hashed_key, INITIAL_COST)
+        .await
+        .unwrap();
+    submit_and_build(&dbs, bid).await;
+    submit_and_fail(&dbs, solution).await;
+    let owner = query(&dbs, &auction, &token::get_item_owner_key(first)).await;
+    assert_eq!(token::item_owner(owner).unwrap(), bob_hashed_key);
+    let balance = query(&dbs, &token::token::ADDRESS, &balance_key).await;
+    assert_eq!(token::balance(balance).unwrap(), FUNDS);
+
+    // Alice wins the first and third items together.
+    let solution = bundle_solution(
+        &dbs,
+        alice_hashed_key,
+        &[(first, INITIAL_COST), (third, INITIAL_COST + 1)],
+    )
+    .await
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+    for item_id in [first, third] {
+        let owner = query(&dbs, &auction, &token::get_item_owner_key(item_id)).await;
+        assert_eq!(token::item_owner(owner).unwrap(), alice_hashed_key);
+    }
+    let balance = query(&dbs, &token::token::ADDRESS, &balance_key).await;
+    assert_eq!(
+        token::balance(balance).unwrap(),
+        FUNDS - 2 * INITIAL_COST - 1
+    );
+    let proceeds = query(&dbs, &auction, &token::proceeds_key(third)).await;
+    assert_eq!(proceeds.as_opt_int().unwrap(), Some(INITIAL_COST + 1));
+
+    // Bob claims the proceeds of each item from escrow, once.
+    let claim = |item_id| {
+        let dbs = &dbs;
+        let auction = &auction;
+        async move {
+            let bob_balance = token::balance_key(bob_hashed_key);
+            token::claim_proceeds::build_solution(token::claim_proceeds::BuildSolution {
+                item_id,
+                hashed_seller_key: bob_hashed_key,
+                payment_token: token::token::ADDRESS,
+                current_proceeds: query(dbs, auction, &token::proceeds_key(item_id)).await,
+                current_seller_balance: query(dbs, &token::token::ADDRESS, &bob_balance).await,
+                current_escrow_balance: query(
+                    dbs,
+                    &token::token::ADDRESS,
+                    &token::balance_key(token::escrow_key(&token::auction::ADDRESS)),
+                )
+                .await,
+                contract: token::auction::ADDRESS,
+            })
+        }
+    };
+    submit_and_build(&dbs, claim(first).await.unwrap()).await;
+    submit_and_build(&dbs, claim(third).await.unwrap()).await;
+    assert!(claim(first).await.is_err());
+    let balance = query(
+        &dbs,
+        &token::token::ADDRESS,
+        &token::balance_key(bob_hashed_key),
+    )
+    .await;
+    assert_eq!(token::balance(balance).unwrap(), 3 * INITIAL_COST + 1);
+}
+
+#[tokio::test]
+async fn escrowed_sales_cannot_be_bundled() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+    let charlie_hashed_key = new_account(&mut wallet, "charlie");
+    let auction = token::auction::ADDRESS;
+    let first = ITEM_ID;
+    let second = ITEM_ID + 1;
+    let bids = [(first, INITIAL_COST), (second, INITIAL_COST)];
+    mint(&dbs, alice_hashed_key, FUNDS).await;
+    for item_id in [first, second] {
+        create_auction(&dbs, item_id, bob_hashed_key).await;
+    }
+
+    // Bob sells the second item in escrow under Charlie.
+    set_arbiter(&dbs, second, bob_hashed_key, charlie_hashed_key).await;
+
+    // The builder refuses the bundle.
+    assert!(bundle_solution(&dbs, alice_hashed_key, &bids)
+        .await
+        .is_err());
+
+    // A bundle built as if there were no arbiter is rejected too.
+    let mut items = Vec::with_capacity(bids.len());
+    for (item_id, amount) in bids {
+        items.push(token::place_bid::BundleItem {
+            item_id,
+            amount,
+            current_cost: query(&dbs, &auction, &token::cost_key(item_id)).await,
+            current_owner: query(&dbs, &auction, &token::get_item_owner_key(item_id)).await,
+            current_arbiter: Query(None),
+            current_collection: Query(None),
+            current_royalty_rate: Query(None),
+            current_royalties: Query(None),
+        });
+    }
+    let balance_key = token::balance_key(alice_hashed_key);
+    let escrow_key = token::balance_key(token::escrow_key(&token::auction::ADDRESS));
+    let solution = token::place_bid::build_bundle(token::place_bid::BuildBundle {
+        hashed_from_key: alice_hashed_key,
+        items,
+        payment_token: token::token::ADDRESS,
+        current_from_balance: query(&dbs, &token::token::ADDRESS, &balance_key).await,
+        current_escrow_balance: query(&dbs, &token::token::ADDRESS, &escrow_key).await,
+        block_state: token::block_state::ADDRESS,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap();
+    submit_and_fail(&dbs, solution).await;
+    assert_eq!(balance_of(&dbs, alice_hashed_key).await, FUNDS);
+
+    // The item without an arbiter can still be bundled on its own.
+    let solution = bundle_solution(&dbs, alice_hashed_key, &bids[..1])
+        .await
+        .unwrap();
+    submit_and_build(&dbs, solution).await;
+    let owner = query(&dbs, &auction, &token::get_item_owner_key(first)).await;
+    assert_eq!(token::item_owner(owner).unwrap(), alice_hashed_key);
+
+    // While the sale of the second item is held it can't be split.
+    let solution = place_bid_solution_for(&dbs, second, alice_hashed_key, INITIAL_COST)
+        .await
+        .unwrap();
+    submit_and_build(&dbs, solution).await;
+    let solution = fractionalize_solution(&dbs, second, alice_hashed_key, 3, |to_sign| {
+        sign(&mut wallet, to_sign, "alice")
+    })
+    .await
+    .unwrap();
+    submit_and_fail(&dbs, solution).await;
+}
+
+#[tokio::test]
+async fn escrowed_sales_settle_once_by_the_buyer_or_the_arbiter() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+    let charlie_hashed_key = new_account(&mut wallet, "charlie");
+    let auction = token::auction::ADDRESS;
+    let disputed = ITEM_ID;
+    let confirmed = ITEM_ID + 1;
+    let refund = 30;
+
+    // Bob buys two of Alice's items in escrow under Charlie.
+    mint(&dbs, bob_hashed_key, FUNDS).await;
+    for item_id in [disputed, confirmed] {
+        create_auction(&dbs, item_id, alice_hashed_key).await;
+        set_arbiter(&dbs, item_id, alice_hashed_key, charlie_hashed_key).await;
+        let solution = place_bid_solution_for(&dbs, item_id, bob_hashed_key, INITIAL_COST)
+            .await
+            .unwrap();
+        submit_and_build(&dbs, solution).await;
+    }
+    assert_eq!(
+        balance_of(&dbs, bob_hashed_key).await,
+        FUNDS - 2 * INITIAL_COST
+    );
+
+    // Only the buyer can confirm delivery, and only of the sale that is held.
+    let solution = confirm_delivery_solution(&dbs, confirmed, bob_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "charlie")
+    })
+    .await
+    .unwrap();
+    submit_and_fail(&dbs, solution).await;
+    let stale = token::confirm_delivery::data_to_sign(confirmed, 0);
+    let solution = confirm_delivery_solution(&dbs, confirmed, bob_hashed_key, |_| {
+        sign(&mut wallet, &stale, "bob")
+    })
+    .await
+    .unwrap();
+    submit_and_fail(&dbs, solution).await;
+
+    // Bob confirms, releasing the sale to Alice once.
+    let solution = confirm_delivery_solution(&dbs, confirmed, bob_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "bob")
+    })
+    .await
+    .unwrap();
+    submit_and_build(&dbs, solution.clone()).await;
+    submit_and_fail(&dbs, solution).await;
+    let status = query(&dbs, &auction, &token::escrow_status_key(confirmed)).await;
+    assert_eq!(token::cost(status).unwrap(), token::ESCROW_SETTLED);
+    let proceeds = query(&dbs, &auction, &token::proceeds_key(confirmed)).await;
+    assert_eq!(token::cost(proceeds).unwrap(), INITIAL_COST);
+
+    // Only the buyer can dispute the other sale.
+    let solution = open_dispute_solution(&dbs, disputed, bob_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "alice")
+    })
+    .await
+    .unwrap();
+    submit_and_fail(&dbs, solution).await;
+    let solution = open_dispute_solution(&dbs, disputed, bob_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "bob")
+    })
+    .await
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    // A disputed sale is left to the arbiter.
+    assert!(
+        confirm_delivery_solution(&dbs, disputed, bob_hashed_key, |to_sign| {
+            sign(&mut wallet, to_sign, "bob")
+        })
+        .await
+        .is_err()
+    );
+    let solution = resolve_solution(&dbs, disputed, refund, |to_sign| {
+        sign(&mut wallet, to_sign, "alice")
+    })
+    .await
+    .unwrap();
+    submit_and_fail(&dbs, solution).await;
+
+    // Charlie returns part of the sale to Bob and the rest goes to Alice.
+    let solution = resolve_solution(&dbs, disputed, refund, |to_sign| {
+        sign(&mut wallet, to_sign, "charlie")
+    })
+    .await
+    .unwrap();
+    submit_and_build(&dbs, solution.clone()).await;
+    submit_and_fail(&dbs, solution).await;
+    let proceeds = query(&dbs, &auction, &token::proceeds_key(disputed)).await;
+    assert_eq!(token::cost(proceeds).unwrap(), INITIAL_COST - refund);
+
+    // Bob reclaims his part of the sale.
+    let solution = claim_refund_solution(&dbs, bob_hashed_key).await;
+    submit_and_build(&dbs, solution).await;
+    assert_eq!(
+        balance_of(&dbs, bob_hashed_key).await,
+        FUNDS - 2 * INITIAL_COST + refund
+    );
+}
+
+#[tokio::test]
+async fn proxy_bids_escrow_every_maximum() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+    let charlie_hashed_key = new_account(&mut wallet, "charlie");
+    let dave_hashed_key = new_account(&mut wallet, "dave");
+    let auction = token::auction::ADDRESS;
+    for hashed_key in [alice_hashed_key, charlie_hashed_key, dave_hashed_key] {
+        mint(&dbs, hashed_key, FUNDS).await;
+    }
+    submit_and_build(&dbs, advance(1)).await;
+    let solution = token::create_ascending_auction::build_solution(
+        token::create_ascending_auction::BuildSolution {
+            item_id: ITEM_ID,
+            hashed_key: bob_hashed_key,
+            reserve: INITIAL_COST,
+            increment: INCREMENT,
+            deadline: 5,
+            reveals_close: 8,
+            payment_token: token::token::ADDRESS,
+            block_state: token::block_state::ADDRESS,
+            collection: None,
+            current_curator_admin: Query(None),
+            current_curated_item_count: Query(None),
+            contract: token::auction::ADDRESS,
+        },
+    )
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    // Each maximum is sealed behind a deposit that covers it.
+    let solution = commit_bid_solution(&dbs, alice_hashed_key, 300, INITIAL_COST - 1).await;
+    assert!(solution.is_err());
+    let mut solution = commit_bid_solution(&dbs, alice_hashed_key, 300, 300)
+        .await
+        .unwrap();
+    solution.data.truncate(1);
+    submit_and_fail(&dbs, solution).await;
+    for (hashed_key, max, deposit) in [
+        (alice_hashed_key, 300, 300),
+        (charlie_hashed_key, 200, 250),
+        (dave_hashed_key, 400, 400),
+    ] {
+        let solution = commit_bid_solution(&dbs, hashed_key, max, deposit)
+            .await
+            .unwrap();
+        submit_and_build(&dbs, solution).await;
+    }
+    assert_eq!(balance_of(&dbs, charlie_hashed_key).await, FUNDS - 250);
+    // Only one maximum per bidder.
+    let solution = commit_bid_solution(&dbs, alice_hashed_key, 350, 350).await;
+    assert!(solution.is_err());
+
+    // No maximum is revealed before bidding closes.
+    let solution = proxy_bid_solution(&dbs, alice_hashed_key, 300)
+        .await
+        .unwrap();
+    submit_and_fail(&dbs, solution).await;
+    submit_and_build(&dbs, advance(5)).await;
+    assert!(proxy_bid_solution(&dbs, alice_hashed_key, 250)
+        .await
+        .is_err());
+
+    // The first reveal outbids no one, so it can't credit anyone a refund.
+    let mut solution = proxy_bid_solution(&dbs, alice_hashed_key, 300)
+        .await
+        .unwrap();
+    solution.data[0].transient_data = token::auction::ProxyBid::PubVars {
+        item_id: ITEM_ID,
+        bidder: alice_hashed_key,
+        max: 300,
+        salt: BID_SALT,
+        previous_leader: charlie_hashed_key,
+        block_state: word_4_from_u8_32(token::block_state::ADDRESS.0),
+    }
+    .into();
+    let forged: Vec<essential_types::solution::Mutation> = token::auction::storage::mutations()
+        .refunds(|map| map.entry(ITEM_ID, |map| map.entry(charlie_hashed_key, 500)))
+        .into();
+    solution.data[0].state_mutations.extend(forged);
+    submit_and_fail(&dbs, solution).await;
+
+    // Alice leads at the reserve with a maximum of 300.
+    let solution = proxy_bid_solution(&dbs, alice_hashed_key, 300)
+        .await
+        .unwrap();
+    submit_and_build(&dbs, solution).await;
+    let cost = query(&dbs, &auction, &token::cost_key(ITEM_ID)).await;
+    assert_eq!(token::cost(cost).unwrap(), INITIAL_COST);
+
+    // Charlie's lower maximum only raises the price.
+    let solution = proxy_bid_solution(&dbs, charlie_hashed_key, 200)
+        .await
+        .unwrap();
+    submit_and_build(&dbs, solution).await;
+    let cost = query(&dbs, &auction, &token::cost_key(ITEM_ID)).await;
+    assert_eq!(token::cost(cost).unwrap(), 200 + INCREMENT);
+    let leader = query(&dbs, &auction, &token::leader_key(ITEM_ID)).await;
+    assert_eq!(token::leader(leader).unwrap(), Some(alice_hashed_key));
+
+    // Charlie does not lead, so the deposit can be reclaimed straight away.
+    submit_and_build(&dbs, claim_refund_solution(&dbs, charlie_hashed_key).await).await;
+    assert_eq!(balance_of(&dbs, charlie_hashed_key).await, FUNDS);
+
+    // Dave beats Alice's maximum, so she can reclaim hers.
+    let solution = proxy_bid_solution(&dbs, dave_hashed_key, 400)
+        .await
+        .unwrap();
+    submit_and_build(&dbs, solution).await;
+    let cost = query(&dbs, &auction, &token::cost_key(ITEM_ID)).await;
+    assert_eq!(token::cost(cost).unwrap(), 300 + INCREMENT);
+    submit_and_build(&dbs, claim_refund_solution(&dbs, alice_hashed_key).await).await;
+    assert_eq!(balance_of(&dbs, alice_hashed_key).await, FUNDS);
+
+    // Once reveals close Dave wins at the visible price.
+    submit_and_build(&dbs, advance(8)).await;
+    let solution = settle_ascending_solution(&dbs, ITEM_ID).await.unwrap();
+    submit_and_build(&dbs, solution).await;
+    let owner = query(&dbs, &auction, &token::get_item_owner_key(ITEM_ID)).await;
+    assert_eq!(token::item_owner(owner).unwrap(), dave_hashed_key);
+    submit_and_build(&dbs, claim_refund_solution(&dbs, dave_hashed_key).await).await;
+    assert_eq!(
+        balance_of(&dbs, dave_hashed_key).await,
+        FUNDS - 300 - INCREMENT
+    );
+}
+
+#[tokio::test]
+async fn ascending_auctions_no_one_leads_can_be_cancelled() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+    let charlie_hashed_key = new_account(&mut wallet, "charlie");
+    let dave_hashed_key = new_account(&mut wallet, "dave");
+    let auction = token::auction::ADDRESS;
+    for hashed_key in [charlie_hashed_key, dave_hashed_key] {
+        mint(&dbs, hashed_key, FUNDS).await;
+    }
+    submit_and_build(&dbs, advance(1)).await;
+    let solution = token::create_ascending_auction::build_solution(
+        token::create_ascending_auction::BuildSolution {
+            item_id: ITEM_ID,
+            hashed_key: bob_hashed_key,
+            reserve: INITIAL_COST,
+            increment: INCREMENT,
+            deadline: 5,
+            reveals_close: 8,
+            payment_token: token::token::ADDRESS,
+            block_state: token::block_state::ADDRESS,
+            collection: None,
+            current_curator_admin: Query(None),
+            current_curated_item_count: Query(None),
+            contract: token::auction::ADDRESS,
+        },
+    )
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    // Charlie's maximum is below the reserve and Dave never reveals.
+    for (hashed_key, max) in [
+        (charlie_hashed_key, INITIAL_COST - 1),
+        (dave_hashed_key, 200),
+    ] {
+        let solution = commit_bid_solution(&dbs, hashed_key, max, 200)
+            .await
+            .unwrap();
+        submit_and_build(&dbs, solution).await;
+    }
+    submit_and_build(&dbs, advance(5)).await;
+    let solution = proxy_bid_solution(&dbs, charlie_hashed_key, INITIAL_COST - 1)
+        .await
+        .unwrap();
+    submit_and_build(&dbs, solution).await;
+    let leader = query(&dbs, &auction, &token::leader_key(ITEM_ID)).await;
+    assert_eq!(token::leader(leader).unwrap(), None);
+
+    // No one can cancel until reveals close.
+    let cancel = || {
+        token::cancel_ascending::build_solution(token::cancel_ascending::BuildSolution {
+            item_id: ITEM_ID,
+            block_state: token::block_state::ADDRESS,
+            current_leader: Query(None),
+            contract: token::auction::ADDRESS,
+        })
+        .unwrap()
+    };
+    submit_and_fail(&dbs, cancel()).await;
+    submit_and_build(&dbs, advance(8)).await;
+    submit_and_build(&dbs, cancel()).await;
+    let owner = query(&dbs, &auction, &token::get_item_owner_key(ITEM_ID)).await;
+    assert_eq!(token::item_owner(owner).unwrap(), bob_hashed_key);
+    let ascending = query(&dbs, &auction, &token::ascending_key(ITEM_ID)).await;
+    assert_eq!(ascending.as_opt_bool().unwrap(), Some(false));
+
+    // Both deposits can still be reclaimed, Dave's once revealed.
+    submit_and_build(&dbs, claim_refund_solution(&dbs, charlie_hashed_key).await).await;
+    assert_eq!(balance_of(&dbs, charlie_hashed_key).await, FUNDS);
+    let solution = proxy_bid_solution(&dbs, dave_hashed_key, 200)
+        .await
+        .unwrap();
+    submit_and_build(&dbs, solution).await;
+    submit_and_build(&dbs, claim_refund_solution(&dbs, dave_hashed_key).await).await;
+    assert_eq!(balance_of(&dbs, dave_hashed_key).await, FUNDS);
+}
+
+#[tokio::test]
+async fn curation_gates_every_listing_until_approved() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+    let charlie_hashed_key = new_account(&mut wallet, "charlie");
+    let dave_hashed_key = new_account(&mut wallet, "dave");
+    let auction = token::auction::ADDRESS;
+    let listed = ITEM_ID;
+    let raffled = ITEM_ID + 1;
+    mint(&dbs, dave_hashed_key, FUNDS).await;
+    submit_and_build(&dbs, advance(1)).await;
+
+    // Only the admin the contract was deployed with turns on curation.
+    let admin = token::CURATOR_ADMIN;
+    let set_admin = |hashed_key, signature| {
+        token::set_curator_admin::build_solution(token::set_curator_admin::BuildSolution {
+            hashed_key,
+            current_admin: Query(None),
+            signature,
+            contract: token::auction::ADDRESS,
+        })
+    };
+    let to_sign = token::set_curator_admin::data_to_sign(alice_hashed_key);
+    let signature = sign(&mut wallet, &to_sign, "alice");
+    assert_eq!(
+        set_admin(alice_hashed_key, signature).unwrap_err(),
+        token::AuctionError::Violation(token::validate::Rule::DeployedAdminOnly)
+    );
+    let to_sign = token::set_curator_admin::data_to_sign(admin);
+    let solution = set_admin(admin, sign(&mut wallet, &to_sign, "alice")).unwrap();
+    submit_and_fail(&dbs, solution).await;
+    let solution = set_admin(admin, sign_as_curator_admin(&to_sign)).unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    // Appointing a curator takes the admin's signature.
+    let solution = set_curator_solution(&dbs, bob_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "alice")
+    })
+    .await;
+    submit_and_fail(&dbs, solution).await;
+    let solution = set_curator_solution(&dbs, bob_hashed_key, sign_as_curator_admin).await;
+    submit_and_build(&dbs, solution).await;
+
+    // Charlie lists one item at a fixed price and raffles another,
+    // both wait on a curator.
+    let solution = list_solution(&dbs, listed, charlie_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "charlie")
+    })
+    .await;
+    submit_and_build(&dbs, solution).await;
+    let solution = token::create_raffle::build_solution(token::create_raffle::BuildSolution {
+        item_id: raffled,
+        hashed_key: charlie_hashed_key,
+        ticket_price: TICKET_PRICE,
+        entries_close: 3,
+        reveals_close: 5,
+        secret: 7,
+        salt: RAFFLE_SALT,
+        payment_token: token::token::ADDRESS,
+        block_state: token::block_state::ADDRESS,
+        collection: None,
+        current_curator_admin: query(&dbs, &auction, &token::curator_admin_key()).await,
+        current_curated_item_count: query(&dbs, &auction, &token::curated_item_count_key()).await,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+    let count = query(&dbs, &auction, &token::curated_item_count_key()).await;
+    assert_eq!(count.as_int().unwrap(), 2);
+
+    submit_and_fail(&dbs, buy_solution(&dbs, dave_hashed_key).await.unwrap()).await;
+    let solution = enter_raffle_solution(&dbs, raffled, dave_hashed_key, 11).await;
+    submit_and_fail(&dbs, solution).await;
+
+    // Reviews take the curator's signature, so no one else can approve as Bob.
+    let solution = review_listing_solution(&dbs, listed, bob_hashed_key, true, |to_sign| {
+        sign(&mut wallet, to_sign, "charlie")
+    })
+    .await;
+    submit_and_fail(&dbs, solution).await;
+
+    // Bob approves the listing and rejects the raffle.
+    for (item_id, approved) in [(listed, true), (raffled, false)] {
+        let solution =
+            review_listing_solution(&dbs, item_id, bob_hashed_key, approved, |to_sign| {
+                sign(&mut wallet, to_sign, "bob")
+            })
+            .await;
+        submit_and_build(&dbs, solution).await;
+    }
+    submit_and_build(&dbs, buy_solution(&dbs, dave_hashed_key).await.unwrap()).await;
+    let owner = query(&dbs, &auction, &token::get_item_owner_key(listed)).await;
+    assert_eq!(token::item_owner(owner).unwrap(), dave_hashed_key);
+    let solution = enter_raffle_solution(&dbs, raffled, dave_hashed_key, 11).await;
+    submit_and_fail(&dbs, solution).await;
+
+    // Only Charlie can take the rejected raffle off sale, with his signature.
+    let mut release = |hashed_key, current_owner, current_nonce: Query, account| {
+        let nonce = token::offer(current_nonce.clone()).unwrap();
+        let to_sign = token::release_listing::data_to_sign(raffled, nonce);
+        token::release_listing::build_solution(token::release_listing::BuildSolution {
+            item_id: raffled,
+            hashed_key,
+            current_owner,
+            current_status: Query(Some(vec![token::LISTING_REJECTED])),
+            current_nonce,
+            signature: sign(&mut wallet, &to_sign, account),
+            contract: token::auction::ADDRESS,
+        })
+    };
+    let owner = query(&dbs, &auction, &token::get_item_owner_key(raffled)).await;
+    let nonce = query(&dbs, &auction, &token::nonce_key(dave_hashed_key)).await;
+    assert!(release(dave_hashed_key, owner, nonce.clone(), "dave").is_err());
+    let forged = Query(Some(dave_hashed_key.to_vec()));
+    let solution = release(dave_hashed_key, forged, nonce, "dave").unwrap();
+    submit_and_fail(&dbs, solution).await;
+    let owner = query(&dbs, &auction, &token::get_item_owner_key(raffled)).await;
+    let nonce = query(&dbs, &auction, &token::nonce_key(charlie_hashed_key)).await;
+    let solution = release(charlie_hashed_key, owner.clone(), nonce.clone(), "dave").unwrap();
+    submit_and_fail(&dbs, solution).await;
+    let solution = release(charlie_hashed_key, owner, nonce, "charlie").unwrap();
+    submit_and_build(&dbs, solution).await;
+    let ended = query(&dbs, &auction, &token::auction_status_key(raffled)).await;
+    assert_eq!(ended.as_opt_bool().unwrap(), Some(true));
+}
+
+#[tokio::test]
+async fn raffles_draw_a_revealed_winner_and_refund_the_rest() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+    let charlie_hashed_key = new_account(&mut wallet, "charlie");
+    let dave_hashed_key = new_account(&mut wallet, "dave");
+    let auction = token::auction::ADDRESS;
+    let drawn = ITEM_ID;
+    let unrevealed = ITEM_ID + 1;
+    for hashed_key in [
+        alice_hashed_key,
+        bob_hashed_key,
+        charlie_hashed_key,
+        dave_hashed_key,
+    ] {
+        mint(&dbs, hashed_key, FUNDS).await;
+    }
+    submit_and_build(&dbs, advance(1)).await;
+
+    // Bob raffles two items, entries close at block 3 and reveals at block 5.
+    for item_id in [drawn, unrevealed] {
+        let solution = token::create_raffle::build_solution(token::create_raffle::BuildSolution {
+            item_id,
+            hashed_key: bob_hashed_key,
+            ticket_price: TICKET_PRICE,
+            entries_close: 3,
+            reveals_close: 5,
+            secret: 7,
+            salt: RAFFLE_SALT,
+            payment_token: token::token::ADDRESS,
+            block_state: token::block_state::ADDRESS,
+            collection: None,
+            current_curator_admin: Query(None),
+            current_curated_item_count: Query(None),
+            contract: token::auction::ADDRESS,
+        })
+        .unwrap();
+        submit_and_build(&dbs, solution).await;
+    }
+
+    let solution = enter_raffle_solution(&dbs, drawn, alice_hashed_key, 11).await;
+    submit_and_build(&dbs, solution).await;
+    let solution = enter_raffle_solution(&dbs, drawn, charlie_hashed_key, 13).await;
+    submit_and_build(&dbs, solution).await;
+    let solution = enter_raffle_solution(&dbs, unrevealed, alice_hashed_key, 17).await;
+    submit_and_build(&dbs, solution).await;
+    assert_eq!(
+        balance_of(&dbs, alice_hashed_key).await,
+        FUNDS - 2 * TICKET_PRICE
+    );
+
+    // One ticket per entrant, and the seller can't enter.
+    let solution = enter_raffle_solution(&dbs, drawn, alice_hashed_key, 11).await;
+    submit_and_fail(&dbs, solution).await;
+    let solution = enter_raffle_solution(&dbs, drawn, bob_hashed_key, 5).await;
+    submit_and_fail(&dbs, solution).await;
+
+    // Secrets can't be revealed while entries are open.
+    let solution = reveal_raffle_solution(&dbs, drawn, bob_hashed_key, 7).await;
+    submit_and_fail(&dbs, solution).await;
+
+    submit_and_build(&dbs, advance(3)).await;
+    let solution = enter_raffle_solution(&dbs, drawn, dave_hashed_key, 19).await;
+    submit_and_fail(&dbs, solution).await;
+
+    // Only the committed secret can be revealed, and only once.
+    let solution = reveal_raffle_solution(&dbs, drawn, bob_hashed_key, 8).await;
+    submit_and_fail(&dbs, solution).await;
+    let solution = reveal_raffle_solution(&dbs, drawn, bob_hashed_key, 7).await;
+    submit_and_build(&dbs, solution).await;
+    let solution = reveal_raffle_solution(&dbs, drawn, bob_hashed_key, 7).await;
+    submit_and_fail(&dbs, solution).await;
+    let solution = reveal_raffle_solution(&dbs, drawn, alice_hashed_key, 11).await;
+    submit_and_build(&dbs, solution).await;
+    let seed = query(&dbs, &auction, &token::raffle_seed_key(drawn)).await;
+    assert_eq!(seed.as_opt_int().unwrap(), Some(7 + 11));
+
+    // Alice's entry is picked, but the raffle can't be drawn until reveals close.
+    let index = token::draw_raffle::winning_index(7 + 11, 2).unwrap();
+    assert_eq!(index, 0);
+    submit_and_fail(&dbs, draw_raffle_solution(&dbs, drawn, index).await).await;
+
+    // The seed picks the winner, no other entry can be drawn.
+    submit_and_build(&dbs, advance(5)).await;
+    submit_and_fail(&dbs, draw_raffle_solution(&dbs, drawn, 1).await).await;
+    submit_and_build(&dbs, draw_raffle_solution(&dbs, drawn, index).await).await;
+    let owner = query(&dbs, &auction, &token::get_item_owner_key(drawn)).await;
+    assert_eq!(token::item_owner(owner).unwrap(), alice_hashed_key);
+
+    // Charlie's ticket is refunded once, Alice's pays the seller.
+    let solution = refund_ticket_solution(&dbs, drawn, alice_hashed_key)
+        .await
+        .unwrap();
+    submit_and_fail(&dbs, solution).await;
+    let solution = refund_ticket_solution(&dbs, drawn, charlie_hashed_key)
+        .await
+        .unwrap();
+    submit_and_build(&dbs, solution).await;
+    assert!(refund_ticket_solution(&dbs, drawn, charlie_hashed_key)
+        .await
+        .is_err());
+    assert_eq!(balance_of(&dbs, charlie_hashed_key).await, FUNDS);
+    let proceeds = query(&dbs, &auction, &token::proceeds_key(drawn)).await;
+    assert_eq!(proceeds.as_opt_int().unwrap(), Some(TICKET_PRICE));
+
+    // A drawn raffle can't be closed.
+    assert!(close_raffle_solution(&dbs, drawn).await.is_err());
+
+    // Bob never revealed on the other raffle, so it can't be drawn and the
+    // item is stuck in it until the raffle is closed.
+    submit_and_fail(&dbs, draw_raffle_solution(&dbs, unrevealed, 0).await).await;
+    let solution = list_solution(&dbs, unrevealed, bob_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "bob")
+    })
+    .await;
+    submit_and_fail(&dbs, solution).await;
+    let solution = close_raffle_solution(&dbs, unrevealed).await.unwrap();
+    submit_and_build(&dbs, solution).await;
+    let owner = query(&dbs, &auction, &token::get_item_owner_key(unrevealed)).await;
+    assert_eq!(token::item_owner(owner).unwrap(), bob_hashed_key);
+    let solution = list_solution(&dbs, unrevealed, bob_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "bob")
+    })
+    .await;
+    submit_and_build(&dbs, solution).await;
+
+    // Alice's ticket is still refunded once the item is for sale again.
+    let solution = refund_ticket_solution(&dbs, unrevealed, alice_hashed_key)
+        .await
+        .unwrap();
+    submit_and_build(&dbs, solution).await;
+    assert_eq!(
+        balance_of(&dbs, alice_hashed_key).await,
+        FUNDS - TICKET_PRICE
+    );
+}
+
+#[tokio::test]
+async fn shares_are_transferred_by_their_holder_and_redeemed() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+    let charlie_hashed_key = new_account(&mut wallet, "charlie");
+    let dave_hashed_key = new_account(&mut wallet, "dave");
+    let auction = token::auction::ADDRESS;
+    let payment_token = token::token::ADDRESS;
+
+    sell_item(&dbs, alice_hashed_key, bob_hashed_key).await;
+    mint(&dbs, dave_hashed_key, FUNDS).await;
+
+    // Bob splits the item into three shares, only with his signature.
+    assert!(fractionalize_solution(
+        &dbs,
+        ITEM_ID,
+        bob_hashed_key,
+        token::fractionalize::MAX_SUPPLY + 1,
+        |to_sign| sign(&mut wallet, to_sign, "bob"),
+    )
+    .await
+    .is_err());
+    let solution = fractionalize_solution(&dbs, ITEM_ID, bob_hashed_key, 3, |to_sign| {
+        sign(&mut wallet, to_sign, "charlie")
+    })
+    .await
+    .unwrap();
+    submit_and_fail(&dbs, solution).await;
+    let solution = fractionalize_solution(&dbs, ITEM_ID, bob_hashed_key, 3, |to_sign| {
+        sign(&mut wallet, to_sign, "bob")
+    })
+    .await
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    // Charlie can't move Bob's shares.
+    let solution =
+        transfer_shares_solution(&dbs, bob_hashed_key, charlie_hashed_key, 1, |to_sign| {
+            sign(&mut wallet, to_sign, "charlie")
+        })
+        .await;
+    submit_and_fail(&dbs, solution).await;
+
+    // Bob gives Charlie a share, and the signature can't be replayed.
+    let mut signature = None;
+    let solution =
+        transfer_shares_solution(&dbs, bob_hashed_key, charlie_hashed_key, 1, |to_sign| {
+            let sig = sign(&mut wallet, to_sign, "bob");
+            signature = Some(sig);
+            sig
+        })
+        .await;
+    submit_and_build(&dbs, solution).await;
+    let signature = signature.unwrap();
+    let solution =
+        transfer_shares_solution(&dbs, bob_hashed_key, charlie_hashed_key, 1, |_| signature).await;
+    submit_and_fail(&dbs, solution).await;
+    let shares = query(
+        &dbs,
+        &auction,
+        &token::share_balance_key(ITEM_ID, charlie_hashed_key),
+    )
+    .await;
+    assert_eq!(token::share_balance(shares).unwrap(), 1);
+
+    // Dave buys the whole item out.
+    let solution = token::buyout::build_solution(token::buyout::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_key: dave_hashed_key,
+        payment_token,
+        current_reserve: query(&dbs, &auction, &token::buyout_reserve_key(ITEM_ID)).await,
+        current_balance: query(&dbs, &payment_token, &token::balance_key(dave_hashed_key)).await,
+        current_escrow_balance: query(
+            &dbs,
+            &payment_token,
+            &token::balance_key(token::escrow_key(&token::auction::ADDRESS)),
+        )
+        .await,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+    let owner = query(&dbs, &auction, &token::get_item_owner_key(ITEM_ID)).await;
+    assert_eq!(token::item_owner(owner).unwrap(), dave_hashed_key);
+
+    // Shares are frozen and redeemed for their rounded down part of the reserve.
+    let solution =
+        transfer_shares_solution(&dbs, bob_hashed_key, charlie_hashed_key, 1, |to_sign| {
+            sign(&mut wallet, to_sign, "bob")
+        })
+        .await;
+    submit_and_fail(&dbs, solution).await;
+    let bob_balance = balance_of(&dbs, bob_hashed_key).await;
+    let charlie_balance = balance_of(&dbs, charlie_hashed_key).await;
+    let solution = redeem_shares_solution(&dbs, bob_hashed_key).await.unwrap();
+    submit_and_build(&dbs, solution).await;
+    let solution = redeem_shares_solution(&dbs, charlie_hashed_key)
+        .await
+        .unwrap();
+    submit_and_build(&dbs, solution).await;
+    assert_eq!(
+        balance_of(&dbs, bob_hashed_key).await,
+        bob_balance + INITIAL_COST * 2 / 3
+    );
+    assert_eq!(
+        balance_of(&dbs, charlie_hashed_key).await,
+        charlie_balance + INITIAL_COST / 3
+    );
+    assert!(redeem_shares_solution(&dbs, charlie_hashed_key)
+        .await
+        .is_err());
+
+    // The last redemption clears the split, so Dave can split the item again.
+    let supply = query(&dbs, &auction, &token::share_supply_key(ITEM_ID)).await;
+    assert_eq!(supply.as_opt_int().unwrap(), Some(0));
+    let solution = fractionalize_solution(&dbs, ITEM_ID, dave_hashed_key, 2, |to_sign| {
+        sign(&mut wallet, to_sign, "dave")
+    })
+    .await
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    // The new shares can't be redeemed against the last buyout.
+    let solution = redeem_shares_solution(&dbs, dave_hashed_key).await.unwrap();
+    submit_and_fail(&dbs, solution).await;
+}
+
+#[tokio::test]
+async fn rented_items_are_not_sold_until_the_lease_ends() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    let (mut wallet, dbs) = setup().await;
+    let alice_hashed_key = new_account(&mut wallet, "alice");
+    let bob_hashed_key = new_account(&mut wallet, "bob");
+    let charlie_hashed_key = new_account(&mut wallet, "charlie");
+    let owned = ITEM_ID;
+    let auctioned = ITEM_ID + 1;
+
+    sell_item(&dbs, alice_hashed_key, bob_hashed_key).await;
+    create_auction(&dbs, auctioned, alice_hashed_key).await;
+    mint(&dbs, charlie_hashed_key, FUNDS).await;
+    submit_and_build(&dbs, advance(1)).await;
+
+    // An offer must be signed by the owner.
+    let solution = offer_rental(&dbs, owned, bob_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "charlie")
+    })
+    .await;
+    submit_and_fail(&dbs, solution).await;
+
+    // Charlie rents Bob's item until block 3.
+    let solution = offer_rental(&dbs, owned, bob_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "bob")
+    })
+    .await;
+    submit_and_build(&dbs, solution).await;
+    let solution = rent_solution(&dbs, owned, charlie_hashed_key)
+        .await
+        .unwrap();
+    submit_and_build(&dbs, solution).await;
+
+    // The item Alice has up for sale can be offered but not leased.
+    let solution = offer_rental(&dbs, auctioned, alice_hashed_key, |to_sign| {
+        sign(&mut wallet, to_sign, "alice")
+    })
+    .await;
+    submit_and_build(&dbs, solution).await;
+    let err = rent_solution(&dbs, auctioned, charlie_hashed_key)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        token::AuctionError::Violation(token::validate::Rule::NotForSale)
+    );
+    let build = rent_build(&dbs, auctioned, charlie_hashed_key).await;
+    let solution = token::rent::build_solution(token::rent::BuildSolution {
+        current_auction_ended: Query(None),
+        ..build
+    })
+    .unwrap();
+    submit_and_fail(&dbs, solution).await;
+
+    // Bob can't put the rented item up for sale in any form.
+    let solution = token::create_auction::build_solution(token::create_auction::BuildSolution {
+        item_id: owned,
+        hashed_key: bob_hashed_key,
+        amount: INITIAL_COST,
+        payment_token: token::token::ADDRESS,
+        collection: None,
+        current_curator_admin: Query(None),
+        current_curated_item_count: Query(None),
+        block_state: token::block_state::ADDRESS,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap();
+    submit_and_fail(&dbs, solution).await;
+    let solution = token::create_ascending_auction::build_solution(
+        token::create_ascending_auction::BuildSolution {
+            item_id: owned,
+            hashed_key: bob_hashed_key,
+            reserve: INITIAL_COST,
+            increment: INCREMENT,
+            deadline: 5,
+            reveals_close: 8,
+            payment_token: token::token::ADDRESS,
+            block_state: token::block_state::ADDRESS,
+            collection: None,
+            current_curator_admin: Query(None),
+            current_curated_item_count: Query(None),
+            contract: token::auction::ADDRESS,
+        },
+    )
+    .unwrap();
+    submit_and_fail(&dbs, solution).await;
+    let solution = token::create_raffle::build_solution(token::create_raffle::BuildSolution {
+        item_id: owned,
+        hashed_key: bob_hashed_key,
+        ticket_price: TICKET_PRICE,
+        entries_close: 5,
+        reveals_close: 7,
+        secret: 7,
+        salt: RAFFLE_SALT,
+        payment_token: token::token::ADDRESS,
+        block_state: token::block_state::ADDRESS,
+        collection: None,
+        current_curator_admin: Query(None),
+        current_curated_item_count: Query(None),
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap();
+    submit_and_fail(&dbs, solution).await;
+    let solution = fractionalize_solution(&dbs, owned, bob_hashed_key, 3, |to_sign| {
+        sign(&mut wallet, to_sign, "bob")
+    })
+    .await
+    .unwrap();
+    submit_and_fail(&dbs, solution).await;
+
+    // Once the lease ends Bob can split the item.
+    submit_and_build(&dbs, advance(3)).await;
+    let solution = fractionalize_solution(&dbs, owned, bob_hashed_key, 3, |to_sign| {
+        sign(&mut wallet, to_sign, "bob")
+    })
+    .await
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+}
+
+// Helper function to compile and deploy the contract into fresh databases
+async fn setup() -> (Wallet, Dbs) {
+    // This requires `pint` be available on PATH
+    let token_contract =
+        compile_pint_project(concat!(env!("CARGO_MANIFEST_DIR"), "/../pint/token").into())
+            .await
+            .unwrap();
+    let block_state_contract =
+        compile_pint_project(concat!(env!("CARGO_MANIFEST_DIR"), "/../pint/block_state").into())
+            .await
+            .unwrap();
+    let auction_contract =
+        compile_pint_project(concat!(env!("CARGO_MANIFEST_DIR"), "/../pint/auction").into())
+            .await
+            .unwrap();
+    let wallet = essential_wallet::Wallet::temp().unwrap();
+    let dbs = utils::db::new_dbs().await;
+    essential_app_utils::deploy::deploy_contract(&dbs.builder, &token_contract)
+        .await
+        .unwrap();
+    essential_app_utils::deploy::deploy_contract(&dbs.builder, &block_state_contract)
+        .await
+        .unwrap();
+    essential_app_utils::deploy::deploy_contract(&dbs.builder, &auction_contract)
+        .await
+        .unwrap();
+
+    // The auction holds its escrow in the token before taking any payments.
+    let solution = token::hold_escrow::build_solution(token::hold_escrow::BuildSolution {
+        payment_token: token::token::ADDRESS,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap();
+    submit_and_build(&dbs, solution).await;
+    (wallet, dbs)
+}
+
+// Helper function to create an account and return its hashed key
+fn new_account(wallet: &mut Wallet, name: &str) -> [Word; 4] {
+    wallet
+        .new_key_pair(name, essential_wallet::Scheme::Secp256k1)
+        .unwrap();
+    hash_key(wallet, name)
+}
+
+// Helper function to build a bid the same way the CLI does
+async fn place_bid_solution(
+    dbs: &Dbs,
+    hashed_from_key: [Word; 4],
+    amount: Word,
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    place_bid_solution_for(dbs, ITEM_ID, hashed_from_key, amount).await
+}
+
+// Helper function to build a bid on `item_id` the same way the CLI does
+async fn place_bid_solution_for(
+    dbs: &Dbs,
+    item_id: Word,
+    hashed_from_key: [Word; 4],
+    amount: Word,
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    let build = place_bid_build(dbs, item_id, hashed_from_key, amount).await?;
+    token::place_bid::build_solution(build)
+}
+
+// Helper function to gather what a bid on `item_id` needs, the same way the CLI does
+async fn place_bid_build(
+    dbs: &Dbs,
+    item_id: Word,
+    hashed_from_key: [Word; 4],
+    amount: Word,
+) -> Result<token::place_bid::BuildSolution, token::AuctionError> {
+    let auction = token::auction::ADDRESS;
+    let owner = query(dbs, &auction, &token::get_item_owner_key(item_id)).await;
+    let hashed_to_key = token::item_owner(owner)?;
+    let payment_token = query(dbs, &auction, &token::payment_token_key(item_id)).await;
+    let payment_token = token::payment_token(payment_token)?;
+    let from_balance = query(dbs, &payment_token, &token::balance_key(hashed_from_key)).await;
+    let arbiter = query(dbs, &auction, &token::arbiter_key(item_id)).await;
+    let current_collection = query(dbs, &auction, &token::item_collection_key(item_id)).await;
+    let collection = token::item_collection(current_collection.clone())?;
+    // A sale with an arbiter or in a collection is paid into escrow rather
+    // than to the owner.
+    let to_key = match (token::arbiter(arbiter.clone())?, collection) {
+        (None, None) => hashed_to_key,
+        _ => token::escrow_key(&token::auction::ADDRESS),
+    };
+    let to_balance = query(dbs, &payment_token, &token::balance_key(to_key)).await;
+    let current_cost = query(dbs, &auction, &token::cost_key(item_id)).await;
+    let current_escrow_sale = query(dbs, &auction, &token::escrow_sale_key(item_id)).await;
+    let collection = collection.unwrap_or_default();
+
+    Ok(token::place_bid::BuildSolution {
+        item_id,
+        hashed_from_key,
+        hashed_to_key,
+        amount,
+        current_from_balance: from_balance,
+        current_to_balance: to_balance,
+        current_cost,
+        payment_token,
+        current_arbiter: arbiter,
+        current_escrow_sale,
+        current_collection,
+        current_royalty_rate: query(dbs, &auction, &token::collection_royalty_key(collection))
+            .await,
+        current_royalties: query(
+            dbs,
+            &auction,
+            &token::collection_royalties_key(collection, &payment_token),
+        )
+        .await,
+        current_proceeds: query(dbs, &auction, &token::proceeds_key(item_id)).await,
+        block_state: token::block_state::ADDRESS,
+        contract: token::auction::ADDRESS,
+    })
+}
+
+// Helper function to sign a bid intent for `item_id` at the initial cost
+fn bid_intent(
+    wallet: &mut Wallet,
+    item_id: Word,
+    account_name: &str,
+    expiry: Word,
+) -> token::bid_intent::BidIntent {
+    let payment_token = token::token::ADDRESS;
+    let to_sign = token::bid_intent::data_to_sign(item_id, INITIAL_COST, expiry, &payment_token);
+    token::bid_intent::BidIntent {
+        item_id,
+        bidder: hash_key(wallet, account_name),
+        limit: INITIAL_COST,
+        expiry,
+        payment_token,
+        signature: sign(wallet, &to_sign, account_name),
+    }
+}
+
+// Helper function to fill a bid intent the same way the solver does
+async fn fill_bid_intent_solution(
+    dbs: &Dbs,
+    intent: token::bid_intent::BidIntent,
+    current_block_number: Query,
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    let auction = token::auction::ADDRESS;
+    let item_id = intent.item_id;
+    let payment_token = intent.payment_token.clone();
+    let current_owner = query(dbs, &auction, &token::get_item_owner_key(item_id)).await;
+    let owner = token::item_owner(current_owner.clone())?;
+    let current_collection = query(dbs, &auction, &token::item_collection_key(item_id)).await;
+    let collection = token::item_collection(current_collection.clone())?;
+    let to_key = match collection {
+        Some(_) => token::escrow_key(&token::auction::ADDRESS),
+        None => owner,
+    };
+    let collection = collection.unwrap_or_default();
+    token::bid_intent::build_solution(token::bid_intent::BuildSolution {
+        block_state: token::block_state::ADDRESS,
+        current_block_number,
+        current_cost: query(dbs, &auction, &token::cost_key(item_id)).await,
+        current_owner,
+        current_fixed_price: query(dbs, &auction, &token::fixed_price_key(item_id)).await,
+        current_bidder_balance: query(dbs, &payment_token, &token::balance_key(intent.bidder))
+            .await,
+        current_owner_balance: query(dbs, &payment_token, &token::balance_key(to_key)).await,
+        current_collection,
+        current_royalty_rate: query(dbs, &auction, &token::collection_royalty_key(collection))
+            .await,
+        current_royalties: query(
+            dbs,
+            &auction,
+            &token::collection_royalties_key(collection, &payment_token),
+        )
+        .await,
+        current_proceeds: query(dbs, &auction, &token::proceeds_key(item_id)).await,
+        intent,
+        contract: token::auction::ADDRESS,
+    })
+}
+
+// Helper function to build a bundle of bids the same way the CLI does
+async fn bundle_solution(
+    dbs: &Dbs,
+    hashed_from_key: [Word; 4],
+    bids: &[(Word, Word)],
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    let auction = token::auction::ADDRESS;
+    let payment_token = token::token::ADDRESS;
+    let mut items = Vec::with_capacity(bids.len());
+    for &(item_id, amount) in bids {
+        let current_collection = query(dbs, &auction, &token::item_collection_key(item_id)).await;
+        let collection = token::item_collection(current_collection.clone())?.unwrap_or_default();
+        items.push(token::place_bid::BundleItem {
+            item_id,
+            amount,
+            current_cost: query(dbs, &auction, &token::cost_key(item_id)).await,
+            current_owner: query(dbs, &auction, &token::get_item_owner_key(item_id)).await,
+            current_arbiter: query(dbs, &auction, &token::arbiter_key(item_id)).await,
+            current_collection,
+            current_royalty_rate: query(dbs, &auction, &token::collection_royalty_key(collection))
+                .await,
+            current_royalties: query(
+                dbs,
+                &auction,
+                &token::collection_royalties_key(collection, &payment_token),
+            )
+            .await,
+        });
+    }
+    let balance_key = token::balance_key(hashed_from_key);
+    let escrow_key = token::balance_key(token::escrow_key(&token::auction::ADDRESS));
+    token::place_bid::build_bundle(token::place_bid::BuildBundle {
+        hashed_from_key,
+        items,
+        payment_token: token::token::ADDRESS,
+        current_from_balance: query(dbs, &token::token::ADDRESS, &balance_key).await,
+        current_escrow_balance: query(dbs, &token::token::ADDRESS, &escrow_key).await,
+        block_state: token::block_state::ADDRESS,
+        contract: token::auction::ADDRESS,
+    })
+}
+
+// Helper function to seal a proxy bid on the item the same way the CLI does
+async fn commit_bid_solution(
+    dbs: &Dbs,
+    hashed_key: [Word; 4],
+    max: Word,
+    deposit: Word,
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    let auction = token::auction::ADDRESS;
+    let commitment_key = token::bid_commitment_key(ITEM_ID, hashed_key);
+    let balance_key = token::balance_key(hashed_key);
+    let escrow_key = token::balance_key(token::escrow_key(&token::auction::ADDRESS));
+    token::commit_bid::build_solution(token::commit_bid::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_key,
+        max,
+        salt: BID_SALT,
+        deposit,
+        payment_token: token::token::ADDRESS,
+        block_state: token::block_state::ADDRESS,
+        current_cost: query(dbs, &auction, &token::cost_key(ITEM_ID)).await,
+        current_commitment: query(dbs, &auction, &commitment_key).await,
+        current_balance: query(dbs, &token::token::ADDRESS, &balance_key).await,
+        current_escrow_balance: query(dbs, &token::token::ADDRESS, &escrow_key).await,
+        contract: token::auction::ADDRESS,
+    })
+}
+
+// Helper function to reveal a proxy bid on the item the same way the CLI does
+async fn proxy_bid_solution(
+    dbs: &Dbs,
+    hashed_key: [Word; 4],
+    max: Word,
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    let auction = token::auction::ADDRESS;
+    let leader = query(dbs, &auction, &token::leader_key(ITEM_ID)).await;
+    let leader_refund = match token::leader(Query(leader.0.clone()))? {
+        Some(leader) => query(dbs, &auction, &token::refund_key(ITEM_ID, leader)).await,
+        None => Query(None),
+    };
+    token::proxy_bid::build_solution(token::proxy_bid::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_key,
+        max,
+        salt: BID_SALT,
+        block_state: token::block_state::ADDRESS,
+        current_block_number: query(
+            dbs,
+            &token::block_state::ADDRESS,
+            &token::block_number_key(),
+        )
+        .await,
+        current_reveals_close: query(dbs, &auction, &token::bid_reveal_deadline_key(ITEM_ID)).await,
+        current_cost: query(dbs, &auction, &token::cost_key(ITEM_ID)).await,
+        current_increment: query(dbs, &auction, &token::bid_increment_key(ITEM_ID)).await,
+        current_leader: leader,
+        current_leader_escrow: query(dbs, &auction, &token::leader_escrow_key(ITEM_ID)).await,
+        current_leader_refund: leader_refund,
+        current_refund: query(dbs, &auction, &token::refund_key(ITEM_ID, hashed_key)).await,
+        current_commitment: query(
+            dbs,
+            &auction,
+            &token::bid_commitment_key(ITEM_ID, hashed_key),
+        )
+        .await,
+        current_deposit: query(dbs, &auction, &token::bid_deposit_key(ITEM_ID, hashed_key)).await,
+        current_revealed: query(dbs, &auction, &token::bid_revealed_key(ITEM_ID, hashed_key)).await,
+        contract: token::auction::ADDRESS,
+    })
+}
+
+// Helper function to have `owner` sell `item_id` in escrow under `arbiter`
+async fn set_arbiter(dbs: &Dbs, item_id: Word, owner: [Word; 4], arbiter: [Word; 4]) {
+    let auction = token::auction::ADDRESS;
+    let solution = token::set_arbiter::build_solution(token::set_arbiter::BuildSolution {
+        item_id,
+        hashed_key: owner,
+        arbiter,
+        current_owner: query(dbs, &auction, &token::get_item_owner_key(item_id)).await,
+        current_escrow_status: query(dbs, &auction, &token::escrow_status_key(item_id)).await,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap();
+    submit_and_build(dbs, solution).await;
+}
+
+// Helper function to confirm delivery of `item_id` the same way the CLI does
+async fn confirm_delivery_solution(
+    dbs: &Dbs,
+    item_id: Word,
+    hashed_key: [Word; 4],
+    sign: impl FnOnce(&[Word]) -> RecoverableSignature,
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    let auction = token::auction::ADDRESS;
+    let escrow_sale = query(dbs, &auction, &token::escrow_sale_key(item_id)).await;
+    let to_sign = token::confirm_delivery::data_to_sign(item_id, token::cost(escrow_sale.clone())?);
+    token::confirm_delivery::build_solution(token::confirm_delivery::BuildSolution {
+        item_id,
+        hashed_key,
+        current_buyer: query(dbs, &auction, &token::escrow_buyer_key(item_id)).await,
+        current_seller: query(dbs, &auction, &token::escrow_seller_key(item_id)).await,
+        current_escrow_status: query(dbs, &auction, &token::escrow_status_key(item_id)).await,
+        current_escrow_sale: escrow_sale,
+        current_escrow_amount: query(dbs, &auction, &token::escrow_amount_key(item_id)).await,
+        current_proceeds: query(dbs, &auction, &token::proceeds_key(item_id)).await,
+        signature: sign(&to_sign),
+        contract: token::auction::ADDRESS,
+    })
+}
+
+// Helper function to dispute the sale of `item_id` the same way the CLI does
+async fn open_dispute_solution(
+    dbs: &Dbs,
+    item_id: Word,
+    hashed_key: [Word; 4],
+    sign: impl FnOnce(&[Word]) -> RecoverableSignature,
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    let auction = token::auction::ADDRESS;
+    let escrow_sale = query(dbs, &auction, &token::escrow_sale_key(item_id)).await;
+    let to_sign = token::open_dispute::data_to_sign(item_id, token::cost(escrow_sale.clone())?);
+    token::open_dispute::build_solution(token::open_dispute::BuildSolution {
+        item_id,
+        hashed_key,
+        current_buyer: query(dbs, &auction, &token::escrow_buyer_key(item_id)).await,
+        current_escrow_status: query(dbs, &auction, &token::escrow_status_key(item_id)).await,
+        current_escrow_sale: escrow_sale,
+        signature: sign(&to_sign),
+        contract: token::auction::ADDRESS,
+    })
+}
+
+// Helper function to rule on the disputed sale of `item_id` the same way the CLI does
+async fn resolve_solution(
+    dbs: &Dbs,
+    item_id: Word,
+    refund: Word,
+    sign: impl FnOnce(&[Word]) -> RecoverableSignature,
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    let auction = token::auction::ADDRESS;
+    let buyer = query(dbs, &auction, &token::escrow_buyer_key(item_id)).await;
+    let hashed_buyer_key = token::escrow_buyer(buyer.clone())?;
+    let escrow_sale = query(dbs, &auction, &token::escrow_sale_key(item_id)).await;
+    let to_sign = token::resolve::data_to_sign(item_id, refund, token::cost(escrow_sale.clone())?);
+    token::resolve::build_solution(token::resolve::BuildSolution {
+        item_id,
+        refund,
+        current_buyer: buyer,
+        current_seller: query(dbs, &auction, &token::escrow_seller_key(item_id)).await,
+        current_escrow_status: query(dbs, &auction, &token::escrow_status_key(item_id)).await,
+        current_escrow_sale: escrow_sale,
+        current_escrow_amount: query(dbs, &auction, &token::escrow_amount_key(item_id)).await,
+        current_buyer_refund: query(dbs, &auction, &token::refund_key(item_id, hashed_buyer_key))
+            .await,
+        current_proceeds: query(dbs, &auction, &token::proceeds_key(item_id)).await,
+        signature: sign(&to_sign),
+        contract: token::auction::ADDRESS,
+    })
+}
+
+// Helper function to reclaim a bidder's escrow on the item
+async fn claim_refund_solution(
+    dbs: &Dbs,
+    hashed_key: [Word; 4],
+) -> essential_types::solution::Solution {
+    let refund_key = token::refund_key(ITEM_ID, hashed_key);
+    let balance_key = token::balance_key(hashed_key);
+    let escrow_key = token::balance_key(token::escrow_key(&token::auction::ADDRESS));
+    token::claim_refund::build_solution(token::claim_refund::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_bidder_key: hashed_key,
+        payment_token: token::token::ADDRESS,
+        current_refund: query(dbs, &token::auction::ADDRESS, &refund_key).await,
+        current_bidder_balance: query(dbs, &token::token::ADDRESS, &balance_key).await,
+        current_escrow_balance: query(dbs, &token::token::ADDRESS, &escrow_key).await,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap()
+}
+
+// Helper function to enter a raffle with `secret`
+async fn enter_raffle_solution(
+    dbs: &Dbs,
+    item_id: Word,
+    hashed_key: [Word; 4],
+    secret: Word,
+) -> essential_types::solution::Solution {
+    let auction = token::auction::ADDRESS;
+    let balance_key = token::balance_key(hashed_key);
+    let escrow_key = token::balance_key(token::escrow_key(&token::auction::ADDRESS));
+    token::enter_raffle::build_solution(token::enter_raffle::BuildSolution {
+        item_id,
+        hashed_key,
+        secret,
+        salt: RAFFLE_SALT,
+        payment_token: token::token::ADDRESS,
+        block_state: token::block_state::ADDRESS,
+        current_ticket_price: query(dbs, &auction, &token::ticket_price_key(item_id)).await,
+        current_entry_count: query(dbs, &auction, &token::raffle_entry_count_key(item_id)).await,
+        current_balance: query(dbs, &token::token::ADDRESS, &balance_key).await,
+        current_escrow_balance: query(dbs, &token::token::ADDRESS, &escrow_key).await,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap()
+}
+
+// Helper function to reveal a raffle secret
+async fn reveal_raffle_solution(
+    dbs: &Dbs,
+    item_id: Word,
+    hashed_key: [Word; 4],
+    secret: Word,
+) -> essential_types::solution::Solution {
+    let seed_key = token::raffle_seed_key(item_id);
+    token::reveal_raffle::build_solution(token::reveal_raffle::BuildSolution {
+        item_id,
+        hashed_key,
+        secret,
+        salt: RAFFLE_SALT,
+        block_state: token::block_state::ADDRESS,
+        current_seed: query(dbs, &token::auction::ADDRESS, &seed_key).await,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap()
+}
+
+// Helper function to draw entry `index` of a raffle
+async fn draw_raffle_solution(
+    dbs: &Dbs,
+    item_id: Word,
+    index: Word,
+) -> essential_types::solution::Solution {
+    let auction = token::auction::ADDRESS;
+    let payment_token = query(dbs, &auction, &token::ticket_token_key(item_id)).await;
+    let payment_token = token::payment_token(payment_token).unwrap();
+    let current_collection = query(dbs, &auction, &token::item_collection_key(item_id)).await;
+    let collection = token::item_collection(current_collection.clone())
+        .unwrap()
+        .unwrap_or_default();
+    token::draw_raffle::build_solution(token::draw_raffle::BuildSolution {
+        item_id,
+        block_state: token::block_state::ADDRESS,
+        current_owner: query(dbs, &auction, &token::get_item_owner_key(item_id)).await,
+        current_ticket_price: query(dbs, &auction, &token::ticket_price_key(item_id)).await,
+        current_winner: query(dbs, &auction, &token::raffle_entrant_key(item_id, index)).await,
+        current_collection,
+        current_royalty_rate: query(dbs, &auction, &token::collection_royalty_key(collection))
+            .await,
+        current_royalties: query(
+            dbs,
+            &auction,
+            &token::collection_royalties_key(collection, &payment_token),
+        )
+        .await,
+        payment_token,
+        index,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap()
+}
+
+// Helper function to settle an ascending auction once reveals close
+async fn settle_ascending_solution(
+    dbs: &Dbs,
+    item_id: Word,
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    let auction = token::auction::ADDRESS;
+    let leader = query(dbs, &auction, &token::leader_key(item_id)).await;
+    let winner = token::leader(leader.clone())?.unwrap_or_default();
+    let payment_token = query(dbs, &auction, &token::payment_token_key(item_id)).await;
+    let payment_token = token::payment_token(payment_token)?;
+    let current_collection = query(dbs, &auction, &token::item_collection_key(item_id)).await;
+    let collection = token::item_collection(current_collection.clone())?.unwrap_or_default();
+    token::settle_ascending::build_solution(token::settle_ascending::BuildSolution {
+        item_id,
+        block_state: token::block_state::ADDRESS,
+        current_owner: query(dbs, &auction, &token::get_item_owner_key(item_id)).await,
+        current_cost: query(dbs, &auction, &token::cost_key(item_id)).await,
+        current_leader: leader,
+        current_leader_escrow: query(dbs, &auction, &token::leader_escrow_key(item_id)).await,
+        current_leader_refund: query(dbs, &auction, &token::refund_key(item_id, winner)).await,
+        current_collection,
+        current_royalty_rate: query(dbs, &auction, &token::collection_royalty_key(collection))
+            .await,
+        current_royalties: query(
+            dbs,
+            &auction,
+            &token::collection_royalties_key(collection, &payment_token),
+        )
+        .await,
+        payment_token,
+        contract: token::auction::ADDRESS,
+    })
+}
+
+// Helper function to take an undrawable raffle's item out of the raffle
+async fn close_raffle_solution(
+    dbs: &Dbs,
+    item_id: Word,
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    let auction = token::auction::ADDRESS;
+    let owner = query(dbs, &auction, &token::get_item_owner_key(item_id)).await;
+    let owner = token::item_owner(owner)?;
+    token::close_raffle::build_solution(token::close_raffle::BuildSolution {
+        item_id,
+        block_state: token::block_state::ADDRESS,
+        current_seller_revealed: query(dbs, &auction, &token::raffle_revealed_key(item_id, owner))
+            .await,
+        current_entry_count: query(dbs, &auction, &token::raffle_entry_count_key(item_id)).await,
+        contract: token::auction::ADDRESS,
+    })
+}
+
+// Helper function to reclaim a raffle ticket
+async fn refund_ticket_solution(
+    dbs: &Dbs,
+    item_id: Word,
+    hashed_key: [Word; 4],
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    let auction = token::auction::ADDRESS;
+    let balance_key = token::balance_key(hashed_key);
+    let escrow_key = token::balance_key(token::escrow_key(&token::auction::ADDRESS));
+    token::refund_ticket::build_solution(token::refund_ticket::BuildSolution {
+        item_id,
+        hashed_entrant_key: hashed_key,
+        payment_token: token::token::ADDRESS,
+        block_state: token::block_state::ADDRESS,
+        current_ticket_held: query(dbs, &auction, &token::ticket_held_key(item_id, hashed_key))
+            .await,
+        current_ticket_price: query(dbs, &auction, &token::ticket_price_key(item_id)).await,
+        current_entrant_balance: query(dbs, &token::token::ADDRESS, &balance_key).await,
+        current_escrow_balance: query(dbs, &token::token::ADDRESS, &escrow_key).await,
+        contract: token::auction::ADDRESS,
+    })
+}
+
+// Helper function to read an account's balance of the bundled token
+async fn balance_of(dbs: &Dbs, hashed_key: [Word; 4]) -> Word {
+    let balance = query(dbs, &token::token::ADDRESS, &token::balance_key(hashed_key)).await;
+    token::balance(balance).unwrap()
+}
+
+// Helper function to move the block state forward to `number`, signed by the keeper
+fn advance(number: Word) -> essential_types::solution::Solution {
+    let secret = essential_sign::secp256k1::SecretKey::from_slice(&KEEPER_SECRET).unwrap();
+    let signature = essential_sign::sign_hash(essential_hash::hash_words(&[number]), &secret);
+    advance_signed(number, signature)
+}
+
+// Helper function to sign `words` as the development curator admin
+fn sign_as_curator_admin(words: &[Word]) -> RecoverableSignature {
+    let secret = essential_sign::secp256k1::SecretKey::from_slice(&CURATOR_ADMIN_SECRET).unwrap();
+    essential_sign::sign_hash(essential_hash::hash_words(words), &secret)
+}
+
+// Helper function to appoint `curator`, signed as the curator admin
+async fn set_curator_solution(
+    dbs: &Dbs,
+    curator: [Word; 4],
+    sign: impl FnOnce(&[Word]) -> RecoverableSignature,
+) -> essential_types::solution::Solution {
+    let auction = token::auction::ADDRESS;
+    let hashed_key = token::CURATOR_ADMIN;
+    let nonce = query(dbs, &auction, &token::nonce_key(hashed_key)).await;
+    let to_sign =
+        token::set_curator::data_to_sign(curator, true, token::offer(nonce.clone()).unwrap());
+    token::set_curator::build_solution(token::set_curator::BuildSolution {
+        hashed_key,
+        curator,
+        appointed: true,
+        current_admin: query(dbs, &auction, &token::curator_admin_key()).await,
+        current_nonce: nonce,
+        signature: sign(&to_sign),
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap()
+}
+
+// Helper function to review a pending listing, signed by the curator
+async fn review_listing_solution(
+    dbs: &Dbs,
+    item_id: Word,
+    hashed_key: [Word; 4],
+    approved: bool,
+    sign: impl FnOnce(&[Word]) -> RecoverableSignature,
+) -> essential_types::solution::Solution {
+    let auction = token::auction::ADDRESS;
+    let nonce = query(dbs, &auction, &token::nonce_key(hashed_key)).await;
+    let to_sign = token::review_listing::data_to_sign(
+        item_id,
+        approved,
+        token::offer(nonce.clone()).unwrap(),
+    );
+    token::review_listing::build_solution(token::review_listing::BuildSolution {
+        item_id,
+        hashed_key,
+        approved,
+        current_curator: query(dbs, &auction, &token::curator_key(hashed_key)).await,
+        current_status: query(dbs, &auction, &token::listing_status_key(item_id)).await,
+        current_nonce: nonce,
+        signature: sign(&to_sign),
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap()
+}
+
+// Helper function to advance the block number with the given signature
+fn advance_signed(
+    number: Word,
+    signature: RecoverableSignature,
+) -> essential_types::solution::Solution {
+    let pub_vars = token::block_state::Advance::PubVars { number };
+    let vars = token::block_state::Advance::Vars {
+        sig: token::signature_words(&signature),
+    };
+    let mutations = token::block_state::storage::mutations().number(number);
+    essential_types::solution::Solution {
+        data: vec![essential_types::solution::SolutionData {
+            predicate_to_solve: token::block_state::Advance::ADDRESS,
+            decision_variables: vars.into(),
+            transient_data: pub_vars.into(),
+            state_mutations: mutations.into(),
+        }],
+    }
+}
+
+// Helper function to submit a solution and check it is included in a block
+async fn submit_and_build(dbs: &Dbs, solution: essential_types::solution::Solution) {
+    utils::builder::submit(&dbs.builder, solution.clone())
+        .await
+        .unwrap();
+    utils::node::validate_solution(&dbs.node, solution)
+        .await
+        .unwrap();
+    let o = utils::builder::build_default(dbs).await.unwrap();
+    assert!(o.failed.is_empty(), "{:?}", o.failed);
+}
+
+// Sends a client's solutions to the in-process builder
+struct LocalBuilder<'a>(&'a Dbs);
+
+impl token::client::Submit for LocalBuilder<'_> {
+    async fn submit(
+        &self,
+        solution: &essential_types::solution::Solution,
+    ) -> anyhow::Result<essential_types::ContentAddress> {
+        utils::builder::submit(&self.0.builder, solution.clone()).await?;
+        Ok(essential_hash::content_addr(solution))
+    }
+}
+
+// Helper function to build a block and check every submitted solution is included
+async fn build_block(dbs: &Dbs) {
+    let o = utils::builder::build_default(dbs).await.unwrap();
+    assert!(o.failed.is_empty(), "{:?}", o.failed);
+}
+
+// Helper function to submit a solution that the contracts must reject
+async fn submit_and_fail(dbs: &Dbs, solution: essential_types::solution::Solution) {
+    utils::builder::submit(&dbs.builder, solution)
+        .await
+        .unwrap();
+    let o = utils::builder::build_default(dbs).await.unwrap();
+    assert_eq!(o.failed.len(), 1, "{:?}", o.failed);
+}
+
+// Helper function to read a single value from the node
+async fn query(
+    dbs: &Dbs,
+    contract: &essential_types::ContentAddress,
+    key: &essential_types::Key,
+) -> Query {
+    Query(
+        utils::node::query_state_head(&dbs.node, contract, key)
+            .await
+            .unwrap(),
+    )
+}
+
+// Helper function to read the state an action requires, then build and submit it
+async fn run_action(dbs: &Dbs, action: &impl token::SolutionBuilder) {
+    let state = read_state(dbs, action).await;
+    submit_and_build(dbs, action.build(&state).unwrap()).await;
+}
+
+// Helper function to read the state an action requires
+async fn read_state(dbs: &Dbs, action: &impl token::SolutionBuilder) -> token::action::State {
+    let mut state = token::action::State::default();
+    for (contract, key) in action.required_keys() {
+        let value = utils::node::query_state_head(&dbs.node, &contract, &key)
+            .await
+            .unwrap();
+        state.insert(contract, key, value);
+    }
+    state
+}
+
+// Helper function to mint a fresh account's tokens
+async fn mint(dbs: &Dbs, hashed_key: [Word; 4], amount: Word) {
+    let balance_key = token::balance_key(hashed_key);
+    let solution = token::mint::build_solution(token::mint::BuildSolution {
+        current_balance: query(dbs, &token::token::ADDRESS, &balance_key).await,
+        hashed_key,
+        amount,
+    })
+    .unwrap();
+    submit_and_build(dbs, solution).await;
+}
+
+// Helper function to put `item_id` up for auction by `seller` at the initial cost
+async fn create_auction(dbs: &Dbs, item_id: Word, seller: [Word; 4]) {
+    let solution = token::create_auction::build_solution(token::create_auction::BuildSolution {
+        item_id,
+        hashed_key: seller,
+        amount: INITIAL_COST,
+        payment_token: token::token::ADDRESS,
+        collection: None,
+        current_curator_admin: Query(None),
+        current_curated_item_count: Query(None),
+        block_state: token::block_state::ADDRESS,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap();
+    submit_and_build(dbs, solution).await;
+}
+
+// Helper function to auction the item from `seller` to `buyer` at its initial cost
+//
+// The buyer is funded with `FUNDS` first, so the item ends up owned by the
+// buyer and no longer for sale.
+async fn sell_item(dbs: &Dbs, seller: [Word; 4], buyer: [Word; 4]) {
+    mint(dbs, buyer, FUNDS).await;
+    create_auction(dbs, ITEM_ID, seller).await;
+    let solution = place_bid_solution(dbs, buyer, INITIAL_COST).await.unwrap();
+    submit_and_build(dbs, solution).await;
+}
+
+// Helper function to buy the item the same way the CLI does
+async fn buy_solution(
+    dbs: &Dbs,
+    hashed_from_key: [Word; 4],
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    let auction = token::auction::ADDRESS;
+    let payment_token = token::token::ADDRESS;
+    let owner = query(dbs, &auction, &token::get_item_owner_key(ITEM_ID)).await;
+    let hashed_to_key = token::item_owner(owner)?;
+    token::buy::build_solution(token::buy::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_from_key,
+        hashed_to_key,
+        current_from_balance: query(dbs, &payment_token, &token::balance_key(hashed_from_key))
+            .await,
+        current_to_balance: query(dbs, &payment_token, &token::balance_key(hashed_to_key)).await,
+        current_cost: query(dbs, &auction, &token::cost_key(ITEM_ID)).await,
+        payment_token,
+        current_collection: Query(None),
+        current_royalty_rate: Query(None),
+        current_royalties: Query(None),
+        current_proceeds: Query(None),
+        contract: token::auction::ADDRESS,
+    })
+}
+
+// Helper function to add `item_id` to a collection, signed by its creator
+async fn add_collection_item_solution(
+    dbs: &Dbs,
+    collection: Word,
+    item_id: Word,
+    hashed_key: [Word; 4],
+    sign: impl FnOnce(&[Word]) -> RecoverableSignature,
+) -> essential_types::solution::Solution {
+    let auction = token::auction::ADDRESS;
+    let nonce = query(dbs, &auction, &token::nonce_key(hashed_key)).await;
+    let to_sign = token::add_collection_item::data_to_sign(
+        collection,
+        item_id,
+        token::offer(nonce.clone()).unwrap(),
+    );
+    token::add_collection_item::build_solution(token::add_collection_item::BuildSolution {
+        collection,
+        item_id,
+        hashed_key,
+        current_creator: query(dbs, &auction, &token::collection_creator_key(collection)).await,
+        current_owner: query(dbs, &auction, &token::get_item_owner_key(item_id)).await,
+        current_item_count: query(dbs, &auction, &token::collection_item_count_key(collection))
+            .await,
+        current_collection: query(dbs, &auction, &token::item_collection_key(item_id)).await,
+        current_nonce: nonce,
+        signature: sign(&to_sign),
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap()
+}
+
+// Helper function to allow `seller` to sell a closed collection, signed by its creator
+async fn allow_seller_solution(
+    dbs: &Dbs,
+    collection: Word,
+    hashed_key: [Word; 4],
+    seller: [Word; 4],
+    sign: impl FnOnce(&[Word]) -> RecoverableSignature,
+) -> essential_types::solution::Solution {
+    let auction = token::auction::ADDRESS;
+    let nonce = query(dbs, &auction, &token::nonce_key(hashed_key)).await;
+    let to_sign = token::allow_seller::data_to_sign(
+        collection,
+        seller,
+        true,
+        token::offer(nonce.clone()).unwrap(),
+    );
+    token::allow_seller::build_solution(token::allow_seller::BuildSolution {
+        collection,
+        hashed_key,
+        seller,
+        allowed: true,
+        current_creator: query(dbs, &auction, &token::collection_creator_key(collection)).await,
+        current_nonce: nonce,
+        signature: sign(&to_sign),
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap()
+}
+
+// Helper function to offer `item_id` for rent by its owner, for two blocks a lease
+async fn offer_rental(
+    dbs: &Dbs,
+    item_id: Word,
+    owner: [Word; 4],
+    sign: impl FnOnce(&[Word]) -> RecoverableSignature,
+) -> essential_types::solution::Solution {
+    let auction = token::auction::ADDRESS;
+    let nonce = query(dbs, &auction, &token::nonce_key(owner)).await;
+    let to_sign = token::offer_rental::data_to_sign(
+        item_id,
+        INCREMENT,
+        2,
+        token::offer(nonce.clone()).unwrap(),
+    );
+    token::offer_rental::build_solution(token::offer_rental::BuildSolution {
+        item_id,
+        hashed_key: owner,
+        price: INCREMENT,
+        blocks: 2,
+        current_owner: query(dbs, &auction, &token::get_item_owner_key(item_id)).await,
+        current_nonce: nonce,
+        signature: sign(&to_sign),
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap()
+}
+
+// Helper function to gather what renting `item_id` needs, the same way the CLI does
+async fn rent_build(dbs: &Dbs, item_id: Word, hashed_key: [Word; 4]) -> token::rent::BuildSolution {
+    let auction = token::auction::ADDRESS;
+    let payment_token = token::token::ADDRESS;
+    let current_owner = query(dbs, &auction, &token::get_item_owner_key(item_id)).await;
+    let owner = token::item_owner(current_owner.clone()).unwrap();
+    token::rent::BuildSolution {
+        item_id,
+        hashed_key,
+        payment_token,
+        block_state: token::block_state::ADDRESS,
+        current_block_number: query(
+            dbs,
+            &token::block_state::ADDRESS,
+            &token::block_number_key(),
+        )
+        .await,
+        current_owner,
+        current_auction_ended: query(dbs, &auction, &token::auction_status_key(item_id)).await,
+        current_price: query(dbs, &auction, &token::rental_price_key(item_id)).await,
+        current_blocks: query(dbs, &auction, &token::rental_blocks_key(item_id)).await,
+        current_renter: query(dbs, &auction, &token::renter_key(item_id)).await,
+        current_expiry: query(dbs, &auction, &token::rental_expiry_key(item_id)).await,
+        current_renter_balance: query(dbs, &payment_token, &token::balance_key(hashed_key)).await,
+        current_owner_balance: query(dbs, &payment_token, &token::balance_key(owner)).await,
+        contract: token::auction::ADDRESS,
+    }
+}
+
+// Helper function to rent `item_id` the same way the CLI does
+async fn rent_solution(
+    dbs: &Dbs,
+    item_id: Word,
+    hashed_key: [Word; 4],
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    token::rent::build_solution(rent_build(dbs, item_id, hashed_key).await)
+}
+
+// Helper function to transfer shares the same way the CLI does
+async fn transfer_shares_solution(
+    dbs: &Dbs,
+    hashed_from_key: [Word; 4],
+    hashed_to_key: [Word; 4],
+    amount: Word,
+    sign: impl FnOnce(&[Word]) -> RecoverableSignature,
+) -> essential_types::solution::Solution {
+    let auction = token::auction::ADDRESS;
+    let current_nonce = query(dbs, &auction, &token::share_nonce_key(hashed_from_key)).await;
+    let to_sign = token::transfer_shares::data_to_sign(
+        ITEM_ID,
+        hashed_to_key,
+        amount,
+        token::offer(current_nonce.clone()).unwrap(),
+    );
+    token::transfer_shares::build_solution(token::transfer_shares::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_from_key,
+        hashed_to_key,
+        amount,
+        current_from_shares: query(
+            dbs,
+            &auction,
+            &token::share_balance_key(ITEM_ID, hashed_from_key),
+        )
+        .await,
+        current_to_shares: query(
+            dbs,
+            &auction,
+            &token::share_balance_key(ITEM_ID, hashed_to_key),
+        )
+        .await,
+        current_nonce,
+        signature: sign(&to_sign),
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap()
+}
+
+// Helper function to split an item into `supply` shares the same way the CLI does
+async fn fractionalize_solution(
+    dbs: &Dbs,
+    item_id: Word,
+    hashed_key: [Word; 4],
+    supply: Word,
+    sign: impl FnOnce(&[Word]) -> RecoverableSignature,
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    let auction = token::auction::ADDRESS;
+    let payment_token = token::token::ADDRESS;
+    let nonce = query(dbs, &auction, &token::nonce_key(hashed_key)).await.0;
+    let to_sign = token::fractionalize::data_to_sign(
+        item_id,
+        supply,
+        INITIAL_COST,
+        &payment_token,
+        token::offer(Query(nonce.clone()))?,
+    );
+    token::fractionalize::build_solution(token::fractionalize::BuildSolution {
+        item_id,
+        hashed_key,
+        supply,
+        reserve: INITIAL_COST,
+        payment_token,
+        block_state: token::block_state::ADDRESS,
+        current_owner: query(dbs, &auction, &token::get_item_owner_key(item_id)).await,
+        current_nonce: Query(nonce),
+        signature: sign(&to_sign),
+        contract: token::auction::ADDRESS,
+    })
+}
+
+// Helper function to redeem shares the same way the CLI does
+async fn redeem_shares_solution(
+    dbs: &Dbs,
+    hashed_holder_key: [Word; 4],
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    let auction = token::auction::ADDRESS;
+    let payment_token = token::token::ADDRESS;
+    token::redeem_shares::build_solution(token::redeem_shares::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_holder_key,
+        payment_token,
+        current_supply: query(dbs, &auction, &token::share_supply_key(ITEM_ID)).await,
+        current_reserve: query(dbs, &auction, &token::buyout_reserve_key(ITEM_ID)).await,
+        current_shares: query(
+            dbs,
+            &auction,
+            &token::share_balance_key(ITEM_ID, hashed_holder_key),
+        )
+        .await,
+        current_redeemed: query(dbs, &auction, &token::shares_redeemed_key(ITEM_ID)).await,
+        current_holder_balance: query(dbs, &payment_token, &token::balance_key(hashed_holder_key))
+            .await,
+        current_escrow_balance: query(
+            dbs,
+            &payment_token,
+            &token::balance_key(token::escrow_key(&token::auction::ADDRESS)),
+        )
+        .await,
+        contract: token::auction::ADDRESS,
+    })
+}
+
+// Helper function to sign words with an account of the wallet
+fn sign(wallet: &mut Wallet, words: &[Word], account_name: &str) -> RecoverableSignature {
+    let essential_signer::Signature::Secp256k1(signature) =
+        wallet.sign_words(words, account_name).unwrap()
+    else {
+        panic!("Invalid signature")
+    };
+    signature
+}
+
+// Helper function to build an offer the same way the CLI does
+async fn make_offer_solution(
+    dbs: &Dbs,
+    hashed_key: [Word; 4],
+    amount: Word,
+    sign: impl FnOnce(&[Word]) -> RecoverableSignature,
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    let auction = token::auction::ADDRESS;
+    let payment_token = token::token::ADDRESS;
+    let nonce = query(dbs, &auction, &token::offer_nonce_key(hashed_key))
+        .await
+        .0;
+    let to_sign = token::make_offer::data_to_sign(
+        ITEM_ID,
+        amount,
+        &payment_token,
+        token::offer(Query(nonce.clone()))?,
+    );
+    token::make_offer::build_solution(token::make_offer::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_key,
+        amount,
+        current_offer: query(dbs, &auction, &token::offer_key(ITEM_ID, hashed_key)).await,
+        current_offer_count: query(dbs, &auction, &token::offer_count_key(ITEM_ID)).await,
+        current_balance: query(dbs, &payment_token, &token::balance_key(hashed_key)).await,
+        current_escrow_balance: query(
+            dbs,
+            &payment_token,
+            &token::balance_key(token::escrow_key(&token::auction::ADDRESS)),
+        )
+        .await,
+        payment_token,
+        current_nonce: Query(nonce),
+        signature: sign(&to_sign),
+        contract: token::auction::ADDRESS,
+    })
+}
+
+// Helper function to ask `ask` on the request for the item
+async fn submit_ask_solution(
+    dbs: &Dbs,
+    provider: [Word; 4],
+    ask: Word,
+) -> essential_types::solution::Solution {
+    let auction = token::auction::ADDRESS;
+    token::submit_ask::build_solution(token::submit_ask::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_key: provider,
+        ask,
+        block_state: token::block_state::ADDRESS,
+        current_budget: query(dbs, &auction, &token::request_budget_key(ITEM_ID)).await,
+        current_best_ask: query(dbs, &auction, &token::best_ask_key(ITEM_ID)).await,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap()
+}
+
+// Helper function to pay the lowest ask on the request for the item
+async fn settle_request_solution(
+    dbs: &Dbs,
+    provider: [Word; 4],
+) -> essential_types::solution::Solution {
+    let auction = token::auction::ADDRESS;
+    let token = token::token::ADDRESS;
+    token::settle_request::build_solution(token::settle_request::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_provider_key: provider,
+        payment_token: token,
+        block_state: token::block_state::ADDRESS,
+        current_best_ask: query(dbs, &auction, &token::best_ask_key(ITEM_ID)).await,
+        current_provider_balance: query(dbs, &token, &token::balance_key(provider)).await,
+        current_escrow_balance: query(
+            dbs,
+            &token,
+            &token::balance_key(token::escrow_key(&token::auction::ADDRESS)),
+        )
+        .await,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap()
+}
+
+// Helper function to refund the rest of the budget of the request for the item
+async fn refund_request_solution(
+    dbs: &Dbs,
+    buyer: [Word; 4],
+) -> essential_types::solution::Solution {
+    let auction = token::auction::ADDRESS;
+    let token = token::token::ADDRESS;
+    token::refund_request::build_solution(token::refund_request::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_buyer_key: buyer,
+        payment_token: token,
+        block_state: token::block_state::ADDRESS,
+        current_budget: query(dbs, &auction, &token::request_budget_key(ITEM_ID)).await,
+        current_best_ask: query(dbs, &auction, &token::best_ask_key(ITEM_ID)).await,
+        current_buyer_balance: query(dbs, &token, &token::balance_key(buyer)).await,
+        current_escrow_balance: query(
+            dbs,
+            &token,
+            &token::balance_key(token::escrow_key(&token::auction::ADDRESS)),
+        )
+        .await,
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap()
+}
+
+// Helper function to list `item_id` at the initial cost the same way the CLI does
+async fn list_solution(
+    dbs: &Dbs,
+    item_id: Word,
+    hashed_key: [Word; 4],
+    sign: impl FnOnce(&[Word]) -> RecoverableSignature,
+) -> essential_types::solution::Solution {
+    let auction = token::auction::ADDRESS;
+    let payment_token = token::token::ADDRESS;
+    let nonce = query(dbs, &auction, &token::nonce_key(hashed_key)).await.0;
+    let to_sign = token::list::data_to_sign(
+        item_id,
+        INITIAL_COST,
+        &payment_token,
+        token::offer(Query(nonce.clone())).unwrap(),
+    );
+    token::list::build_solution(token::list::BuildSolution {
+        item_id,
+        hashed_key,
+        price: INITIAL_COST,
+        payment_token,
+        block_state: None,
+        collection: None,
+        current_curator_admin: query(dbs, &auction, &token::curator_admin_key()).await,
+        current_curated_item_count: query(dbs, &auction, &token::curated_item_count_key()).await,
+        current_nonce: Query(nonce),
+        signature: sign(&to_sign),
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap()
+}
+
+// Helper function to take `item_id` off sale the same way the CLI does
+async fn delist_solution(
+    dbs: &Dbs,
+    item_id: Word,
+    hashed_key: [Word; 4],
+    sign: impl FnOnce(&[Word]) -> RecoverableSignature,
+) -> essential_types::solution::Solution {
+    let auction = token::auction::ADDRESS;
+    let nonce = query(dbs, &auction, &token::nonce_key(hashed_key)).await.0;
+    let to_sign = token::delist::data_to_sign(item_id, token::offer(Query(nonce.clone())).unwrap());
+    token::delist::build_solution(token::delist::BuildSolution {
+        item_id,
+        hashed_key,
+        current_nonce: Query(nonce),
+        signature: sign(&to_sign),
+        contract: token::auction::ADDRESS,
+    })
+    .unwrap()
+}
+
+// Helper function to accept an offer the same way the CLI does
+async fn accept_offer_solution(
+    dbs: &Dbs,
+    hashed_key: [Word; 4],
+    hashed_buyer_key: [Word; 4],
+    sign: impl FnOnce(&[Word]) -> RecoverableSignature,
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    let auction = token::auction::ADDRESS;
+    let payment_token = token::token::ADDRESS;
+    let offer = query(dbs, &auction, &token::offer_key(ITEM_ID, hashed_buyer_key))
+        .await
+        .0;
+    let nonce = query(dbs, &auction, &token::offer_nonce_key(hashed_key))
+        .await
+        .0;
+    let current_collection = query(dbs, &auction, &token::item_collection_key(ITEM_ID)).await;
+    let collection = token::item_collection(current_collection.clone())?.unwrap_or_default();
+    let to_sign = token::accept_offer::data_to_sign(
+        ITEM_ID,
+        hashed_buyer_key,
+        token::offer(Query(offer.clone()))?,
+        token::offer(Query(nonce.clone()))?,
+    );
+    token::accept_offer::build_solution(token::accept_offer::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_key,
+        hashed_buyer_key,
+        current_offer: Query(offer),
+        current_balance: query(dbs, &payment_token, &token::balance_key(hashed_key)).await,
+        current_escrow_balance: query(
+            dbs,
+            &payment_token,
+            &token::balance_key(token::escrow_key(&token::auction::ADDRESS)),
+        )
+        .await,
+        current_nonce: Query(nonce),
+        signature: sign(&to_sign),
+        current_collection,
+        current_royalty_rate: query(dbs, &auction, &token::collection_royalty_key(collection))
+            .await,
+        current_royalties: query(
+            dbs,
+            &auction,
+            &token::collection_royalties_key(collection, &payment_token),
+        )
+        .await,
+        payment_token,
+        contract: token::auction::ADDRESS,
+    })
+}
+
+// Helper function to withdraw an offer the same way the CLI does
+async fn withdraw_offer_solution(
+    dbs: &Dbs,
+    hashed_key: [Word; 4],
+    sign: impl FnOnce(&[Word]) -> RecoverableSignature,
+) -> Result<essential_types::solution::Solution, token::AuctionError> {
+    let auction = token::auction::ADDRESS;
+    let payment_token = token::token::ADDRESS;
+    let nonce = query(dbs, &auction, &token::offer_nonce_key(hashed_key))
+        .await
+        .0;
+    let to_sign = token::withdraw_offer::data_to_sign(
+        ITEM_ID,
+        hashed_key,
+        token::offer(Query(nonce.clone()))?,
+    );
+    token::withdraw_offer::build_solution(token::withdraw_offer::BuildSolution {
+        item_id: ITEM_ID,
+        hashed_key,
+        current_offer: query(dbs, &auction, &token::offer_key(ITEM_ID, hashed_key)).await,
+        current_balance: query(dbs, &payment_token, &token::balance_key(hashed_key)).await,
+        current_escrow_balance: query(
+            dbs,
+            &payment_token,
+            &token::balance_key(token::escrow_key(&token::auction::ADDRESS)),
+        )
+        .await,
+        current_nonce: Query(nonce),
+        payment_token,
+        signature: sign(&to_sign),
+        contract: token::auction::ADDRESS,
+    })
+}
+
+// Helper function to hash a public key
+fn hash_key(wallet: &mut Wallet, account_name: &str) -> [Word; 4] {
+    let public_key = wallet.get_public_key(account_name).unwrap();
+    let essential_signer::PublicKey::Secp256k1(public_key) = public_key else {
+        panic!("Invalid public key")
+    };
+    let encoded = essential_sign::encode::public_key(&public_key);
+    word_4_from_u8_32(essential_hash::hash_words(&encoded))
+}
@@ -12,25 +12,24 @@ app = Flask(__name__)
 BINARY_PATH = os.getenv('BINARY_PATH')  # Update this to the correct binary path
 
 # Helper function to run the Rust binary with provided arguments
+#
+# Raises when the binary exits nonzero, with the error it printed.
 def run_command(args):
-    try:
-        # Start the Rust binary process
-        process = subprocess.Popen(
-            [BINARY_PATH, ] + args, 
-            stdin=subprocess.PIPE,  # Allow input to stdin
-            stdout=subprocess.PIPE,
-            stderr=subprocess.PIPE,
-            text=True
-        )
+    # Start the Rust binary process
+    process = subprocess.Popen(
+        [BINARY_PATH, ] + args, 
+        stdin=subprocess.PIPE,  # Allow input to stdin
+        stdout=subprocess.PIPE,
+        stderr=subprocess.PIPE,
+        text=True
+    )
 
-        # Send the password to the process stdin
-        stdout, _ = process.communicate()
-        
+    # Send the password to the process stdin
+    stdout, stderr = process.communicate()
+    if process.returncode != 0:
+        raise RuntimeError(stderr.strip() or f"{BINARY_PATH} exited with {process.returncode}")
 
-        return stdout
-        
-    except Exception as e:
-        return {"error": str(e)}
+    return stdout
 
 @app.route('/get_items', methods=['GET'])
 def run():
@@ -39,8 +38,8 @@ def run():
         i = 0
         
         while True:
-            result = run_command(args=["view-item-details" , str(i), "https://bigbangblock.builders", "https://bigbangblock.builders" ,"../pint/token"])
-            if "None" in result: 
+            result = run_command(args=["view-item-details" , str(i), "https://bigbangblock.builders", "https://bigbangblock.builders" ,"../pint/auction"])
+            if "has not been listed" in result:
                 break
             
             # Use regex to extract the values
@@ -48,13 +47,17 @@ def run():
             cost_match = re.search(r'Cost:\s*(\d+)', result)
             owner_match = re.search(r'Owner:\s*"([^"]+)"', result)
             active_match = re.search(r'Active:\s*\'([^"]+)\'', result)
+            payment_token_match = re.search(r'Payment token:\s*"([^"]+)"', result)
+            listing_match = re.search(r'Listing:\s*\'([^\']+)\'', result)
 
             # Store them in a dictionary
             result = {
                 "item_id": item_id_match.group(1) if item_id_match else None,
                 "cost": cost_match.group(1) if cost_match else None,
                 "owner": owner_match.group(1) if owner_match else None,
-                "active": active_match.group(1) if active_match else None
+                "active": active_match.group(1) if active_match else None,
+                "payment_token": payment_token_match.group(1) if payment_token_match else None,
+                "listing_type": listing_match.group(1) if listing_match else None
             }
             results.append(result)
             i+=1
@@ -0,0 +1,90 @@
+import unittest
+from unittest import mock
+
+import server
+
+# What `view-item-details` prints to stdout for a listed item.
+ITEM_DETAILS = """getting balance for item_id: {item_id}
+Cost: 100
+Owner: "0x{owner}"
+Active: 'true'
+Payment token: "0x{token}"
+Listing: '{listing}'
+Status: 'uncurated'
+
+"""
+
+# What it prints to stdout for an item that has not been listed.
+NOT_LISTED = """getting balance for item_id: {item_id}
+Item {item_id} has not been listed
+"""
+
+OWNER = "11" * 32
+TOKEN = "22" * 32
+
+
+def item_details(item_id, listing):
+    return ITEM_DETAILS.format(item_id=item_id, owner=OWNER, token=TOKEN, listing=listing)
+
+
+def process(stdout, stderr="", returncode=0):
+    """A finished CLI process with the given output."""
+    process = mock.Mock(returncode=returncode)
+    process.communicate.return_value = (stdout, stderr)
+    return process
+
+
+class GetItemsTest(unittest.TestCase):
+    def get_items(self, processes):
+        with mock.patch.object(server.subprocess, "Popen", side_effect=processes) as popen:
+            response = server.app.test_client().get("/get_items")
+        return response, popen
+
+    def test_items_report_their_listing_type(self):
+        response, popen = self.get_items([
+            process(item_details(0, "fixed")),
+            process(item_details(1, "auction")),
+            process(NOT_LISTED.format(item_id=2)),
+        ])
+
+        self.assertEqual(response.status_code, 200)
+        items = response.get_json()
+        self.assertEqual([item["item_id"] for item in items], ["0", "1"])
+        self.assertEqual([item["listing_type"] for item in items], ["fixed", "auction"])
+        self.assertEqual(items[0]["cost"], "100")
+        self.assertEqual(items[0]["owner"], "0x" + OWNER)
+        self.assertEqual(items[0]["active"], "true")
+        self.assertEqual(items[0]["payment_token"], "0x" + TOKEN)
+        # The scan stops at the first item that has not been listed.
+        self.assertEqual(popen.call_count, 3)
+        self.assertEqual(popen.call_args.args[0][1:3], ["view-item-details", "2"])
+
+    def test_missing_listing_type_is_null(self):
+        response, _ = self.get_items([
+            process(item_details(0, "fixed").replace("Listing: 'fixed'\n", "")),
+            process(NOT_LISTED.format(item_id=1)),
+        ])
+
+        self.assertIsNone(response.get_json()[0]["listing_type"])
+
+    def test_a_failed_command_stops_the_scan(self):
+        # The CLI prints the error to stderr and exits nonzero.
+        response, popen = self.get_items([
+            process(item_details(0, "fixed")),
+            process(
+                "getting balance for item_id: 1\n",
+                "Command failed because: error sending request for url\n",
+                returncode=1,
+            ),
+        ])
+
+        self.assertEqual(response.status_code, 400)
+        self.assertEqual(
+            response.get_json(),
+            {"error": "Command failed because: error sending request for url"},
+        )
+        self.assertEqual(popen.call_count, 2)
+
+
+if __name__ == "__main__":
+    unittest.main()